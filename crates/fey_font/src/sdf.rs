@@ -0,0 +1,372 @@
+use ab_glyph::{Outline, OutlineCurve, Point};
+use fey_math::{Vec2, vec2};
+
+const RED: u8 = 0b001;
+const GREEN: u8 = 0b010;
+const BLUE: u8 = 0b100;
+const CYAN: u8 = GREEN | BLUE;
+const MAGENTA: u8 = RED | BLUE;
+const YELLOW: u8 = RED | GREEN;
+const WHITE: u8 = RED | GREEN | BLUE;
+
+/// Directions that differ by more than this (the sine of ~3 radians) are a corner.
+const CORNER_THRESHOLD: f32 = 0.141;
+
+/// A glyph outline flattened into line segments, in pixel space.
+#[derive(Debug, Clone)]
+pub(crate) struct Shape {
+    segments: Vec<Segment>,
+    orientation: f32,
+}
+
+/// A straight piece of the outline, with the color channels it contributes to.
+#[derive(Debug, Copy, Clone)]
+struct Segment {
+    a: Vec2<f32>,
+    b: Vec2<f32>,
+    color: u8,
+}
+
+/// The closest point on a segment found so far.
+#[derive(Debug, Copy, Clone)]
+struct Closest {
+    dist: f32,
+    orthogonality: f32,
+    side: f32,
+}
+
+impl Closest {
+    const NONE: Self = Self {
+        dist: f32::INFINITY,
+        orthogonality: 0.0,
+        side: 0.0,
+    };
+
+    /// If this is closer, or equally close but more perpendicular to its segment, which
+    /// resolves which segment's side to use at the corners where they meet.
+    #[inline]
+    fn beats(&self, other: &Self) -> bool {
+        match (self.dist - other.dist).abs() <= 1e-4 {
+            true => self.orthogonality > other.orthogonality,
+            false => self.dist < other.dist,
+        }
+    }
+}
+
+/// One curve of a contour, before it is flattened.
+#[derive(Debug, Copy, Clone)]
+enum Edge {
+    Line(Vec2<f32>, Vec2<f32>),
+    Quad(Vec2<f32>, Vec2<f32>, Vec2<f32>),
+    Cubic(Vec2<f32>, Vec2<f32>, Vec2<f32>, Vec2<f32>),
+}
+
+impl Edge {
+    fn start(&self) -> Vec2<f32> {
+        match *self {
+            Self::Line(a, _) | Self::Quad(a, _, _) | Self::Cubic(a, _, _, _) => a,
+        }
+    }
+
+    fn end(&self) -> Vec2<f32> {
+        match *self {
+            Self::Line(_, b) | Self::Quad(_, _, b) | Self::Cubic(_, _, _, b) => b,
+        }
+    }
+
+    fn start_dir(&self) -> Vec2<f32> {
+        let dir = match *self {
+            Self::Line(a, b) => b - a,
+            Self::Quad(a, b, c) => first_nonzero(&[b - a, c - a]),
+            Self::Cubic(a, b, c, d) => first_nonzero(&[b - a, c - a, d - a]),
+        };
+        dir.norm_safe()
+    }
+
+    fn end_dir(&self) -> Vec2<f32> {
+        let dir = match *self {
+            Self::Line(a, b) => b - a,
+            Self::Quad(a, b, c) => first_nonzero(&[c - b, c - a]),
+            Self::Cubic(a, b, c, d) => first_nonzero(&[d - c, d - b, d - a]),
+        };
+        dir.norm_safe()
+    }
+
+    fn point(&self, t: f32) -> Vec2<f32> {
+        let s = 1.0 - t;
+        match *self {
+            Self::Line(a, b) => a * s + b * t,
+            Self::Quad(a, b, c) => a * (s * s) + b * (2.0 * s * t) + c * (t * t),
+            Self::Cubic(a, b, c, d) => {
+                a * (s * s * s) + b * (3.0 * s * s * t) + c * (3.0 * s * t * t) + d * (t * t * t)
+            }
+        }
+    }
+
+    /// Split the edge into line segments about two pixels long.
+    fn flatten(&self, color: u8, segments: &mut Vec<Segment>) {
+        let len = match *self {
+            Self::Line(..) => 0.0,
+            Self::Quad(a, b, c) => (b - a).len() + (c - b).len(),
+            Self::Cubic(a, b, c, d) => (b - a).len() + (c - b).len() + (d - c).len(),
+        };
+        let count = ((len * 0.5).ceil() as usize).clamp(1, 16);
+        let mut prev = self.start();
+        for i in 1..=count {
+            let next = self.point(i as f32 / count as f32);
+            segments.push(Segment {
+                a: prev,
+                b: next,
+                color,
+            });
+            prev = next;
+        }
+    }
+}
+
+impl Shape {
+    /// Build the shape from an unscaled outline. Points are scaled, flipped so y points down,
+    /// and then `origin` is subtracted from them.
+    pub fn new(outline: &Outline, scale: Vec2<f32>, origin: Vec2<f32>) -> Self {
+        let to_px = |p: Point| vec2(p.x * scale.x, -p.y * scale.y) - origin;
+
+        // split the curves into contours, which are closed loops of connected curves
+        let mut contours: Vec<Vec<Edge>> = Vec::new();
+        for curve in &outline.curves {
+            let edge = match *curve {
+                OutlineCurve::Line(a, b) => Edge::Line(to_px(a), to_px(b)),
+                OutlineCurve::Quad(a, b, c) => Edge::Quad(to_px(a), to_px(b), to_px(c)),
+                OutlineCurve::Cubic(a, b, c, d) => {
+                    Edge::Cubic(to_px(a), to_px(b), to_px(c), to_px(d))
+                }
+            };
+            match contours.last_mut() {
+                Some(contour)
+                    if contour
+                        .last()
+                        .is_some_and(|prev| (prev.end() - edge.start()).sqr_len() < 1e-6) =>
+                {
+                    contour.push(edge);
+                }
+                _ => contours.push(vec![edge]),
+            }
+        }
+
+        // color the edges of each contour and flatten them
+        let mut segments = Vec::new();
+        for contour in &contours {
+            for (edge, color) in contour.iter().zip(edge_colors(contour)) {
+                edge.flatten(color, &mut segments);
+            }
+        }
+
+        // outer contours all wind the same way, and are larger than the holes inside of them,
+        // so the total area tells us which side of a segment is inside
+        let area: f32 = segments.iter().map(|s| s.a.cross(s.b)).sum();
+        let orientation = if area < 0.0 { -1.0 } else { 1.0 };
+
+        Self {
+            segments,
+            orientation,
+        }
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.segments.is_empty()
+    }
+
+    fn closest(&self, seg: &Segment, p: Vec2<f32>) -> Closest {
+        let ab = seg.b - seg.a;
+        let ap = p - seg.a;
+        let len_sq = ab.sqr_len();
+        let t = match len_sq > 0.0 {
+            true => (ap.dot(ab) / len_sq).clamp(0.0, 1.0),
+            false => 0.0,
+        };
+        let to_p = p - (seg.a + ab * t);
+        let dist = to_p.len();
+        let orthogonality = match dist > 0.0 && len_sq > 0.0 {
+            true => (ab.cross(to_p) / (len_sq.sqrt() * dist)).abs(),
+            false => 1.0,
+        };
+        Closest {
+            dist,
+            orthogonality,
+            side: ab.cross(ap) * self.orientation,
+        }
+    }
+
+    /// If the point is inside the shape, using the non-zero winding rule.
+    fn contains(&self, p: Vec2<f32>) -> bool {
+        let mut winding = 0;
+        for seg in &self.segments {
+            let side = (seg.b - seg.a).cross(p - seg.a);
+            if seg.a.y <= p.y {
+                if seg.b.y > p.y && side > 0.0 {
+                    winding += 1;
+                }
+            } else if seg.b.y <= p.y && side < 0.0 {
+                winding -= 1;
+            }
+        }
+        winding != 0
+    }
+
+    /// The distance from the point to the outline, positive inside and negative outside.
+    pub fn distance(&self, p: Vec2<f32>) -> f32 {
+        let dist = self
+            .segments
+            .iter()
+            .map(|seg| self.closest(seg, p).dist)
+            .fold(f32::INFINITY, f32::min);
+        match self.contains(p) {
+            true => dist,
+            false => -dist,
+        }
+    }
+
+    /// The distance from the point to the closest edge of each color channel, positive inside
+    /// and negative outside. The median of the three reproduces sharp corners.
+    pub fn multi_distance(&self, p: Vec2<f32>) -> [f32; 3] {
+        let mut best = [Closest::NONE; 3];
+        for seg in &self.segments {
+            let closest = self.closest(seg, p);
+            for (i, channel) in [RED, GREEN, BLUE].into_iter().enumerate() {
+                if seg.color & channel != 0 && closest.beats(&best[i]) {
+                    best[i] = closest;
+                }
+            }
+        }
+        let channels = best.map(|c| match c.side > 0.0 {
+            true => c.dist,
+            false => -c.dist,
+        });
+
+        // channels can disagree about which side of the outline a point is on far from the
+        // edges they belong to, so fall back to the true distance when the median is wrong
+        let median = median(channels);
+        let dist = self.distance(p);
+        match (median > 0.0) == (dist > 0.0) {
+            true => channels,
+            false => [dist; 3],
+        }
+    }
+}
+
+/// Pick a color for each edge of a contour, so the edges on either side of every corner
+/// share at most one color channel.
+fn edge_colors(contour: &[Edge]) -> Vec<u8> {
+    let count = contour.len();
+    let corners: Vec<usize> = (0..count)
+        .filter(|&i| {
+            let prev = contour[(i + count - 1) % count].end_dir();
+            let next = contour[i].start_dir();
+            prev.dot(next) <= 0.0 || prev.cross(next).abs() > CORNER_THRESHOLD
+        })
+        .collect();
+
+    match corners.len() {
+        // smooth contours don't need separate channels
+        0 => vec![WHITE; count],
+
+        // a teardrop shape, split into three colors starting at the corner
+        1 if count >= 3 => {
+            let start = corners[0];
+            let mut colors = vec![WHITE; count];
+            for i in 0..count {
+                colors[(start + i) % count] = match (i * 3) / count {
+                    0 => CYAN,
+                    1 => WHITE,
+                    _ => YELLOW,
+                };
+            }
+            colors
+        }
+        1 => vec![WHITE; count],
+
+        // cycle through the colors at every corner, making sure the last edge's color is
+        // different from the first
+        n => {
+            const CYCLE: [u8; 3] = [CYAN, MAGENTA, YELLOW];
+            let mut colors = vec![WHITE; count];
+            for (k, &corner) in corners.iter().enumerate() {
+                let color = match n % 3 == 1 && k == n - 1 {
+                    true => CYCLE[1],
+                    false => CYCLE[k % 3],
+                };
+                let end = corners[(k + 1) % n];
+                let mut i = corner;
+                loop {
+                    colors[i] = color;
+                    i = (i + 1) % count;
+                    if i == end {
+                        break;
+                    }
+                }
+            }
+            colors
+        }
+    }
+}
+
+fn first_nonzero(dirs: &[Vec2<f32>]) -> Vec2<f32> {
+    dirs.iter()
+        .copied()
+        .find(|d| d.sqr_len() > 1e-12)
+        .unwrap_or(dirs[0])
+}
+
+#[inline]
+pub(crate) fn median([a, b, c]: [f32; 3]) -> f32 {
+    a.min(b).max(a.max(b).min(c))
+}
+
+/// Map a distance to `0..=255`, where `128` is on the outline and `spread` pixels inside or
+/// outside of it are the extremes.
+#[inline]
+pub(crate) fn encode(dist: f32, spread: f32) -> u8 {
+    ((0.5 + dist / (2.0 * spread)).clamp(0.0, 1.0) * 255.0).round() as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ab_glyph::{Rect, point};
+
+    fn square() -> Outline {
+        let [a, b, c, d] = [
+            point(0.0, 0.0),
+            point(0.0, 10.0),
+            point(10.0, 10.0),
+            point(10.0, 0.0),
+        ];
+        Outline {
+            bounds: Rect { min: a, max: c },
+            curves: vec![
+                OutlineCurve::Line(a, b),
+                OutlineCurve::Line(b, c),
+                OutlineCurve::Line(c, d),
+                OutlineCurve::Line(d, a),
+            ],
+        }
+    }
+
+    #[test]
+    fn square_distances() {
+        let shape = Shape::new(&square(), vec2(1.0, 1.0), vec2(0.0, -10.0));
+        assert_eq!(shape.distance(vec2(5.0, 5.0)), 5.0);
+        assert_eq!(shape.distance(vec2(2.0, 5.0)), 2.0);
+        assert_eq!(shape.distance(vec2(-3.0, 5.0)), -3.0);
+
+        // every edge of a square is a corner, so each gets its own colors
+        let multi = shape.multi_distance(vec2(2.0, 5.0));
+        assert_eq!(median(multi), 2.0);
+        let multi = shape.multi_distance(vec2(-3.0, 5.0));
+        assert_eq!(median(multi), -3.0);
+
+        assert_eq!(encode(0.0, 4.0), 128);
+        assert_eq!(encode(4.0, 4.0), 255);
+        assert_eq!(encode(-8.0, 4.0), 0);
+    }
+}
@@ -24,8 +24,19 @@ function Main:update()
 end
 
 function Main:render()
+    -- dim the line while the window isn't focused
     local mouse = Mouse.pos()
-    Draw.line(Vec2.zero(), mouse, 0xff3377ff)
+    Draw.line(Vec2.zero(), mouse, self.unfocused and 0x801a3cff or 0xff3377ff)
+end
+
+-- optional hooks for window and app events
+function Main:on_focus_changed(focused)
+    self.unfocused = not focused
+end
+
+function Main:on_close_requested()
+    -- return false here to keep the game open
+    return true
 end
 
 return Main
@@ -8,6 +8,7 @@ const COLLISION_RECT: RectF = RectF::new(-4.0, -5.0, 8.0, 6.0);
 const JUMP_HEIGHT: f32 = TILE_SIZE.y as f32 * 4.2;
 const MOVE_SPEED: f32 = 800.0;
 const MAX_X_SPEED: f32 = 80.0;
+const JUMP_BUFFER: f32 = 0.1;
 
 pub struct Guy {
     pub pos: Vec2F,
@@ -43,10 +44,12 @@ impl Guy {
 
     pub fn update(&mut self, ctx: &Context, level: &crate::Level) {
         let input = vec2(self.x_axis.value(), 0.0);
-        if self.jump_btn.pressed() {
+        // buffer jumps, so pressing jump just before landing still works
+        if self.jump_btn.pressed_within(JUMP_BUFFER) {
             if self.is_grounded {
                 self.vel.y = -GRAVITY.y * (2.0 * JUMP_HEIGHT / GRAVITY.y).sqrt();
                 self.squash_timer = 0.0;
+                self.jump_btn.consume();
             }
             // else if self.is_face_smushed {
             //     self.vel.y = -GRAVITY.y * (2.0 * JUMP_HEIGHT / GRAVITY.y).sqrt();
@@ -0,0 +1,77 @@
+---@meta
+
+---@class Audio
+local Audio = {}
+
+---Play a sound. Sounds play through the `"sfx"` bus by default.
+---@param sound Sound
+---@param bus string?
+---@param volume number?
+---@param pan number?
+---@param pitch number?
+---@param looping boolean?
+---@return Voice
+function Audio.play(sound, bus, volume, pan, pitch, looping) end
+
+---Play streamed music. Music plays through the `"music"` bus and loops by default.
+---@param music Music
+---@param bus string?
+---@param volume number?
+---@param looping boolean?
+---@return Voice
+function Audio.play_music(music, bus, volume, looping) end
+
+---How many voices are currently playing.
+---@return integer
+---@nodiscard
+function Audio.voice_count() end
+
+---The output's sample rate in hertz.
+---@return integer
+---@nodiscard
+function Audio.sample_rate() end
+
+---How many channels the output has.
+---@return integer
+---@nodiscard
+function Audio.channels() end
+
+---Volume applied to all audio.
+---@return number
+---@nodiscard
+function Audio.master_volume() end
+
+---Set the volume applied to all audio.
+---@param volume number
+function Audio.set_master_volume(volume) end
+
+---Volume of the bus.
+---@param bus string
+---@return number
+---@nodiscard
+function Audio.bus_volume(bus) end
+
+---Set the volume of the bus.
+---@param bus string
+---@param volume number
+function Audio.set_bus_volume(bus, volume) end
+
+---If the bus is paused.
+---@param bus string
+---@return boolean
+---@nodiscard
+function Audio.bus_paused(bus) end
+
+---Pause or resume all voices playing through the bus.
+---@param bus string
+---@param paused boolean
+function Audio.set_bus_paused(bus, paused) end
+
+---Stop all voices playing through the bus.
+---@param bus string
+function Audio.stop_bus(bus) end
+
+---Stop all voices.
+function Audio.stop_all() end
+
+return Audio
@@ -1,6 +1,45 @@
 ---@meta
 
----@alias BlendMode "normal"|"add"|"subtract"|"multiply"
+---@alias BlendMode
+---     |"normal"
+---     |"add"
+---     |"subtract"
+---     |"multiply"
+---     |"screen"
+---     |"lighten"
+---     |"darken"
+---     |"straight"
+---     |"erase"
+---     |"replace"
+---     |"alpha_only"
+---     |CustomBlendMode
+
+---@alias BlendFactor
+---     |"zero"
+---     |"one"
+---     |"src"
+---     |"one_minus_src"
+---     |"src_alpha"
+---     |"one_minus_src_alpha"
+---     |"dst"
+---     |"one_minus_dst"
+---     |"dst_alpha"
+---     |"one_minus_dst_alpha"
+---     |"src_alpha_saturated"
+
+---@alias BlendOp "add"|"subtract"|"reverse_subtract"|"min"|"max"
+
+---How the source and destination are combined for either the color or alpha channels.
+---@class BlendComponent
+---@field src BlendFactor Factor the source (the pixel being drawn) is multiplied by.
+---@field dst BlendFactor Factor the destination (the backdrop) is multiplied by.
+---@field op BlendOp? Operation that combines them, defaults to `"add"`.
+
+---A blend mode with custom factors and operations.
+---@class CustomBlendMode
+---@field color BlendComponent How the color channels are blended.
+---@field alpha BlendComponent? How the alpha channel is blended, defaults to `color`.
+---@field mask string? The channels that are written, such as `"rgb"`, defaults to `"rgba"`.
 ---@alias Topology "triangles"|"lines"|"points"
 
 ---@class DrawModule
@@ -110,6 +149,51 @@ function Draw.clip_rect(self) end
 ---@param value Rect
 function Draw.set_clip_rect(value) end
 
+---If the current surface has a depth-stencil buffer, which is required for masking and depth
+---testing.
+---@return boolean
+---@nodiscard
+function Draw.has_depth_stencil() end
+
+---If depth testing is enabled.
+---@return boolean
+---@nodiscard
+function Draw.depth_test() end
+
+---Enable or disable depth testing. While enabled, drawing only appears where its depth is less
+---than or equal to what was drawn there before, so it can be sorted regardless of the order it
+---was submitted in. Depth is written even where the drawing is transparent, so this is best
+---suited to opaque sprites.
+---@param enabled boolean
+function Draw.set_depth_test(enabled) end
+
+---The current depth.
+---@return number
+---@nodiscard
+function Draw.depth() end
+
+---Set the depth of future drawing, from `0` (nearest) to `1` (farthest). This is only used when
+---depth testing is enabled.
+---@param value number
+function Draw.set_depth(value) end
+
+---Size of the mask stack.
+---@return integer
+---@nodiscard
+function Draw.mask_count() end
+
+---Push a mask to the top of the stack. Everything drawn inside `f` is written to the mask
+---instead of the surface, and until the mask is popped, future drawing will only appear where
+---it overlaps the mask. Nested masks are intersected with the masks beneath them.
+---
+---Changing the surface or layer, or popping the mask, inside `f` is not allowed, and causes an
+---error once the mask is finished.
+---@param f fun()
+function Draw.push_mask(f) end
+
+---Pop the mask off the top of the stack.
+function Draw.pop_mask() end
+
 ---The current transform.
 ---@return Affine2
 ---@nodiscard
@@ -374,6 +458,14 @@ function Draw.text(text, pos, font, size, color) end
 ---@param size number?
 function Draw.text(text, x, y, font, size, color) end
 
+---Draw text that was laid out with the provided font, at the provided size.
+---@param layout TextLayout
+---@param pos Vec2
+---@param font Font
+---@param color Color?
+---@param size number?
+function Draw.text_layout(layout, pos, font, color, size) end
+
 ---Draw a custom set of vertices & indices.
 ---@param texture Texture?
 ---@param topology Topology
@@ -0,0 +1,41 @@
+---@meta
+
+---@class FileDrop
+local FileDrop = {}
+
+---If files are currently being dragged over the window.
+---@return boolean
+---@nodiscard
+function FileDrop.hovering() end
+
+---Paths of the files currently being dragged over the window.
+---@return string[]
+---@nodiscard
+function FileDrop.hovered_files() end
+
+---Position in the window where files are currently being dragged.
+---@return Vec2?
+---@nodiscard
+function FileDrop.hover_pos() end
+
+---If files were dragged over the window and then away without being dropped this frame.
+---@return boolean
+---@nodiscard
+function FileDrop.hover_cancelled() end
+
+---If files were dropped onto the window this frame.
+---@return boolean
+---@nodiscard
+function FileDrop.dropped() end
+
+---Paths of the files that were dropped onto the window this frame.
+---@return string[]
+---@nodiscard
+function FileDrop.dropped_files() end
+
+---Position in the window where files were dropped this frame.
+---@return Vec2?
+---@nodiscard
+function FileDrop.drop_pos() end
+
+return FileDrop
@@ -1,5 +1,23 @@
 ---@meta
 
+---@alias TextAlign
+---     |"left"
+---     |"center"
+---     |"right"
+---     |"justify"
+
+---@alias TextAnchor
+---     |"top"
+---     |"middle"
+---     |"baseline"
+---     |"bottom"
+
+---@class TextOptions
+---@field max_width number? If set, lines longer than this are wrapped, breaking between words where possible.
+---@field align TextAlign? How lines are aligned horizontally, defaults to `"left"`.
+---@field anchor TextAnchor? Which part of the text is placed at the position vertically, defaults to `"baseline"`.
+---@field line_spacing number? Multiplier for the distance between lines, defaults to 1.
+
 ---@class (exact) Font: FontMethods
 
 ---@class FontModule: FontMethods
@@ -24,6 +42,18 @@ function module.new(size, pixelated) end
 ---@nodiscard
 function module.from_ttf_file(path, size, pixelated, chars) end
 
+---Load a font from a TTF file, rasterizing its glyphs as signed distance fields so they stay
+---sharp at any size. The fields reach `spread` pixels past each glyph's outline, and
+---multi-channel fields keep corners sharp.
+---@param path string
+---@param size number
+---@param spread number
+---@param multi_channel boolean
+---@param chars string?
+---@return Font
+---@nodiscard
+function module.sdf_from_ttf_file(path, size, spread, multi_channel, chars) end
+
 ---The font's baked size.
 ---@param self Font
 ---@return number
@@ -36,6 +66,49 @@ function methods.size(self) end
 ---@nodiscard
 function methods.pixelated(self) end
 
+---If the glyphs are signed distance fields, how many pixels the fields reach past their outlines.
+---@param self Font
+---@return number?
+---@nodiscard
+function methods.sdf_spread(self) end
+
+---How far the font extends above the baseline.
+---@param self Font
+---@return number
+---@nodiscard
+function methods.ascent(self) end
+
+---How far the font extends below the baseline, which is usually negative.
+---@param self Font
+---@return number
+---@nodiscard
+function methods.descent(self) end
+
+---Extra space between the bottom of one line and the top of the next.
+---@param self Font
+---@return number
+---@nodiscard
+function methods.line_gap(self) end
+
+---The font's height (`ascent - descent`).
+---@param self Font
+---@return number
+---@nodiscard
+function methods.height(self) end
+
+---The distance between the baselines of two lines.
+---@param self Font
+---@return number
+---@nodiscard
+function methods.line_height(self) end
+
+---Set the font's vertical metrics.
+---@param self Font
+---@param ascent number
+---@param descent number
+---@param line_gap number
+function methods.set_metrics(self, ascent, descent, line_gap) end
+
 ---Set the character's render glyph.
 ---@param self Font
 ---@param chr string
@@ -66,4 +139,82 @@ function methods.set_kerning(self, left, right, kerning) end
 ---@nodiscard
 function methods.kerning(self, left, right) end
 
+---Lay out the text with this font, with kerning, newlines, wrapping, and alignment.
+---@param self Font
+---@param text string
+---@param opts TextOptions?
+---@return TextLayout
+---@nodiscard
+function methods.layout(self, text, opts) end
+
+---Measure the size of the text when laid out with this font.
+---@param self Font
+---@param text string
+---@param opts TextOptions?
+---@return number w
+---@return number h
+---@nodiscard
+function methods.text_size(self, text, opts) end
+
+---Text that has been laid out for a font. It can be kept and drawn every frame, and only
+---updated when the text changes.
+---@class (exact) TextLayout
+local TextLayout = {}
+
+---The text that was laid out.
+---@param self TextLayout
+---@return string
+---@nodiscard
+function TextLayout.text(self) end
+
+---@param self TextLayout
+---@return number
+---@nodiscard
+function TextLayout.width(self) end
+
+---@param self TextLayout
+---@return number
+---@nodiscard
+function TextLayout.height(self) end
+
+---@param self TextLayout
+---@return Vec2
+---@nodiscard
+function TextLayout.size(self) end
+
+---The box the text is aligned in, from the top of the first line to the bottom of the last.
+---@param self TextLayout
+---@return Rect
+---@nodiscard
+function TextLayout.bounds(self) end
+
+---@param self TextLayout
+---@return integer
+---@nodiscard
+function TextLayout.line_count(self) end
+
+---How many glyphs were laid out, including spaces.
+---@param self TextLayout
+---@return integer
+---@nodiscard
+function TextLayout.glyph_count(self) end
+
+---Get a glyph's char, its position on the baseline, and which line it's on.
+---@param self TextLayout
+---@param index integer
+---@return string? chr
+---@return Vec2? pos
+---@return integer? line
+---@nodiscard
+function TextLayout.glyph(self, index) end
+
+---Lay out new text or options with the font, if they are different from the current ones.
+---Returns `true` if the layout changed.
+---@param self TextLayout
+---@param font Font
+---@param text string
+---@param opts TextOptions?
+---@return boolean
+function TextLayout.update(self, font, text, opts) end
+
 return module
\ No newline at end of file
@@ -2,6 +2,9 @@
 
 ---@alias GamepadStatus "wired"|"draining"|"charging"|"charged"
 
+---Handle to a playing rumble effect.
+---@alias Rumble integer
+
 ---@class (exact) Gamepad: GamepadMethods
 
 ---@class GamepadModule: GamepadMethods
@@ -32,6 +35,15 @@ function module.newly_connected(fill) end
 ---@nodiscard
 function module.last_active() end
 
+---Global rumble intensity from `0` to `1`, which scales all gamepad rumble effects.
+---@return number
+---@nodiscard
+function module.rumble_intensity() end
+
+---Set the global rumble intensity from `0` to `1`.
+---@param intensity number
+function module.set_rumble_intensity(intensity) end
+
 ---The gamepad name.
 ---@param self Gamepad
 ---@return string
@@ -107,4 +119,33 @@ function methods.axis(self, axis) end
 ---@nodiscard
 function methods.axis_changed(self, axis) end
 
+---Rumble the strong (low frequency) and weak (high frequency) motors, each from `0` to `1`. If no
+---duration is provided, the effect plays until stopped. Effects are layered, with each motor
+---running at the level of the strongest effect.
+---@param self Gamepad
+---@param strong number
+---@param weak number
+---@param duration number? Duration in seconds.
+---@return Rumble
+function methods.rumble(self, strong, weak, duration) end
+
+---Stop a rumble effect, or all rumble effects if none is provided.
+---@param self Gamepad
+---@param rumble Rumble?
+function methods.stop_rumble(self, rumble) end
+
+---If the rumble effect is still playing.
+---@param self Gamepad
+---@param rumble Rumble
+---@return boolean
+---@nodiscard
+function methods.is_rumbling(self, rumble) end
+
+---The current levels of the strong and weak motors, after the global intensity is applied.
+---@param self Gamepad
+---@return number strong
+---@return number weak
+---@nodiscard
+function methods.rumble_levels(self) end
+
 return module
\ No newline at end of file
@@ -0,0 +1,81 @@
+---@meta
+
+---@class (exact) GlyphCache: GlyphCacheMethods
+
+---@class GlyphCacheModule: GlyphCacheMethods
+local module = {}
+
+---@class GlyphCacheMethods
+local methods = {}
+
+---Load a TTF font whose glyphs are rasterized the first time they are drawn.
+---@param path string
+---@param pixelated boolean
+---@return GlyphCache
+---@nodiscard
+function module.from_ttf_file(path, pixelated) end
+
+---Create a font of the provided size that draws its glyphs from the cache.
+---@param self GlyphCache
+---@param size number
+---@return Font
+---@nodiscard
+function methods.font(self, size) end
+
+---If the cache's glyphs are pixelated.
+---@param self GlyphCache
+---@return boolean
+---@nodiscard
+function methods.pixelated(self) end
+
+---The width and height of each atlas page.
+---@param self GlyphCache
+---@return integer
+---@nodiscard
+function methods.page_size(self) end
+
+---Set the width and height of each atlas page. This clears the cache.
+---@param self GlyphCache
+---@param size integer
+function methods.set_page_size(self, size) end
+
+---How many pages the cache can have before it starts clearing the least recently drawn one.
+---@param self GlyphCache
+---@return integer
+---@nodiscard
+function methods.max_pages(self) end
+
+---Set how many pages the cache can have before it starts clearing the least recently drawn one.
+---@param self GlyphCache
+---@param max_pages integer
+function methods.set_max_pages(self, max_pages) end
+
+---How many atlas pages the cache currently has.
+---@param self GlyphCache
+---@return integer
+---@nodiscard
+function methods.page_count(self) end
+
+---The texture of each atlas page.
+---@param self GlyphCache
+---@return Texture[]
+---@nodiscard
+function methods.pages(self) end
+
+---How many glyphs are currently cached, across all sizes.
+---@param self GlyphCache
+---@return integer
+---@nodiscard
+function methods.glyph_count(self) end
+
+---Remove all glyphs and pages from the cache.
+---@param self GlyphCache
+function methods.clear(self) end
+
+---Rasterize the characters at the provided size ahead of time.
+---@param self GlyphCache
+---@param size number
+---@param chars string
+function methods.prepare(self, size, chars) end
+
+return module
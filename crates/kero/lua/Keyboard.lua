@@ -33,11 +33,33 @@ function Keyboard.repeated(key) end
 ---@nodiscard
 function Keyboard.pressed_or_repeated(key) end
 
----Text that was typed by the keyboard this frame.
+---Text that was typed by the keyboard this frame, including text committed by the IME.
 ---@return string?
 ---@nodiscard
 function Keyboard.text_input() end
 
+---If an IME (input method editor) is currently active. IME input must first be allowed with `Window.set_ime_allowed`.
+---@return boolean
+---@nodiscard
+function Keyboard.ime_enabled() end
+
+---Text the IME is currently composing, which has not been committed yet. Empty when nothing is being composed.
+---@return string
+---@nodiscard
+function Keyboard.preedit() end
+
+---Byte range of the cursor in the preedit text, usable with `string.sub`. If the end is before the start, it's a caret
+---between them, otherwise it's a selection. Returns nothing if the cursor should be hidden.
+---@return integer? start
+---@return integer? end
+---@nodiscard
+function Keyboard.preedit_cursor() end
+
+---Text the IME committed this frame. This is also included in `Keyboard.text_input`.
+---@return string
+---@nodiscard
+function Keyboard.ime_commit() end
+
 ---If left or right control is down.
 ---@return boolean
 ---@nodiscard
@@ -0,0 +1,30 @@
+---@meta
+
+---A music track that is decoded while it plays.
+---@class (exact) Music: MusicMethods
+
+---@class MusicModule: MusicMethods
+local module = {}
+
+---@class MusicMethods
+local methods = {}
+
+---Load music from a WAV or OGG file.
+---@param path string
+---@return Music
+---@nodiscard
+function module.from_file(path) end
+
+---How many channels the music has.
+---@param self Music
+---@return integer
+---@nodiscard
+function methods.channels(self) end
+
+---The music's sample rate in hertz.
+---@param self Music
+---@return integer
+---@nodiscard
+function methods.sample_rate(self) end
+
+return module
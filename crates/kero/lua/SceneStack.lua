@@ -0,0 +1,90 @@
+---@meta
+
+---@class (exact) SceneStack: SceneStackMethods
+
+---@class SceneStackModule : SceneStackMethods
+local module = {}
+
+---@class SceneStackMethods
+local methods = {}
+
+---A scene managed by a scene stack. All of its methods are optional.
+---@class Scene
+---@field overlay boolean? If `true`, the scenes below are rendered first, so this scene is drawn over them.
+---@field pauses_below boolean? If `false`, the scenes below this overlay keep updating.
+---@field update fun(self: Scene, scenes: Scenes)?
+---@field render fun(self: Scene)?
+---@field on_enter fun(self: Scene)?
+---@field on_exit fun(self: Scene)?
+---@field on_cover fun(self: Scene)?
+---@field on_uncover fun(self: Scene)?
+
+---A transition played when switching scenes.
+---@class SceneTransition
+---@field kind "fade"|"cross_fade"|"wipe"
+---@field duration number Duration in seconds.
+---@field color Color? Color to fade through, defaults to black.
+---@field dir Cardinal? Direction the wipe moves in, defaults to east.
+
+---Handle used to switch scenes. Switches are applied after all scenes have been updated.
+---@class Scenes
+local Scenes = {}
+
+---How many scenes are on the stack.
+---@return integer
+---@nodiscard
+function Scenes:len() end
+
+---Push a scene on top of the stack.
+---@param scene Scene
+---@param transition SceneTransition?
+function Scenes:push(scene, transition) end
+
+---Pop the top scene off the stack. If the stack becomes empty, the game will quit.
+---@param transition SceneTransition?
+function Scenes:pop(transition) end
+
+---Replace the top scene of the stack.
+---@param scene Scene
+---@param transition SceneTransition?
+function Scenes:replace(scene, transition) end
+
+---Replace all the scenes on the stack with a single scene.
+---@param scene Scene
+---@param transition SceneTransition?
+function Scenes:replace_all(scene, transition) end
+
+---Create a new scene stack with the scene on it.
+---@param scene Scene
+---@return SceneStack
+---@nodiscard
+function module.new(scene) end
+
+---Handle used to switch scenes.
+---@param self SceneStack
+---@return Scenes
+---@nodiscard
+function methods.scenes(self) end
+
+---How many scenes are on the stack.
+---@param self SceneStack
+---@return integer
+---@nodiscard
+function methods.len(self) end
+
+---Returns `true` if a transition is playing.
+---@param self SceneStack
+---@return boolean
+---@nodiscard
+function methods.is_transitioning(self) end
+
+---Update the scenes from the top down, stopping at the first one that pauses the scenes below it.
+---Call this from `Main:update()`.
+---@param self SceneStack
+function methods.update(self) end
+
+---Render the visible scenes along with the current transition. Call this from `Main:render()`.
+---@param self SceneStack
+function methods.render(self) end
+
+return module
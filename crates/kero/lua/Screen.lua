@@ -78,6 +78,26 @@ function methods.mouse_x(self) end
 ---@nodiscard
 function methods.mouse_y(self) end
 
+---Position on screen where files are being dragged over the window, accounting for
+---scaling/framing.
+---@param self Screen
+---@return Vec2?
+---@nodiscard
+function methods.hover_pos(self) end
+
+---Position on screen where files were dropped onto the window this frame, accounting for
+---scaling/framing.
+---@param self Screen
+---@return Vec2?
+---@nodiscard
+function methods.drop_pos(self) end
+
+---All touches this frame, with their positions on screen, accounting for scaling/framing.
+---@param self Screen
+---@return Touch[]
+---@nodiscard
+function methods.touches(self) end
+
 ---Update the screen surface and mouse position. Should be called at the
 ---beginning of every frame the screen will be used.
 ---@param self Screen
@@ -26,6 +26,17 @@ local methods = {}
 ---@nodiscard
 function module.default() end
 
+---Returns the shader for drawing signed distance fields, such as SDF fonts. Its effects are
+---off by default, and can be turned on with these parameters, where widths and softness are
+---measured in the field's spread:
+---
+---* `outline_width` (float) and `outline_color` (vec4)
+---* `shadow_offset` (vec2, in texels), `shadow_softness` (float), and `shadow_color` (vec4)
+---* `glow_width` (float) and `glow_color` (vec4)
+---@return Shader
+---@nodiscard
+function module.sdf() end
+
 ---Compile a shader from the source code.
 ---@param source string
 ---@return Shader
@@ -0,0 +1,42 @@
+---@meta
+
+---A fully decoded sound effect.
+---@class (exact) Sound: SoundMethods
+
+---@class SoundModule: SoundMethods
+local module = {}
+
+---@class SoundMethods
+local methods = {}
+
+---Load and decode a sound from a WAV or OGG file.
+---@param path string
+---@return Sound
+---@nodiscard
+function module.from_file(path) end
+
+---How many channels the sound has.
+---@param self Sound
+---@return integer
+---@nodiscard
+function methods.channels(self) end
+
+---The sound's sample rate in hertz.
+---@param self Sound
+---@return integer
+---@nodiscard
+function methods.sample_rate(self) end
+
+---How many sample frames the sound has.
+---@param self Sound
+---@return integer
+---@nodiscard
+function methods.frame_count(self) end
+
+---Duration of the sound in seconds.
+---@param self Sound
+---@return number
+---@nodiscard
+function methods.duration(self) end
+
+return module
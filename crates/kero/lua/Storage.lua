@@ -0,0 +1,54 @@
+---@meta
+
+---@alias StorageDir
+---     |"data"
+---     |"config"
+---     |"preferences"
+
+---@class Storage
+local Storage = {}
+
+---How many backups are kept of each saved file.
+---@return integer
+---@nodiscard
+function Storage.backups() end
+
+---Set how many backups are kept of each saved file.
+---@param backups integer
+function Storage.set_backups(backups) end
+
+---Returns `true` if a value has been saved with the key. Keys are relative paths like `"settings"` or `"saves/slot1"`.
+---@param key string
+---@param dir StorageDir? Defaults to `"data"`.
+---@return boolean
+---@nodiscard
+function Storage.exists(key, dir) end
+
+---Save a value, which can be a table of strings, numbers, booleans, and other tables. The previous file is kept as a
+---backup, and the save is atomic, so a crash will never leave a half-written file behind.
+---@param key string
+---@param value any
+---@param dir StorageDir? Defaults to `"data"`.
+function Storage.save(key, value, dir) end
+
+---Load a value, or `nil` if no value has been saved with the key.
+---@param key string
+---@param dir StorageDir? Defaults to `"data"`.
+---@return any
+---@nodiscard
+function Storage.load(key, dir) end
+
+---Load a backup of a value, where `0` is the most recent one, or `nil` if there is no such backup.
+---@param key string
+---@param index integer
+---@param dir StorageDir? Defaults to `"data"`.
+---@return any
+---@nodiscard
+function Storage.load_backup(key, index, dir) end
+
+---Delete a value along with all of its backups.
+---@param key string
+---@param dir StorageDir? Defaults to `"data"`.
+function Storage.delete(key, dir) end
+
+return Storage
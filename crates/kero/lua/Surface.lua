@@ -8,13 +8,29 @@ local module = {}
 ---@class SurfaceMethods
 local methods = {}
 
----Create a new surface. If no format is provided, will default to `"rgba8"`.
+---Create a new surface. If no format is provided, will default to `"rgba8"`. If `depth_stencil`
+---is true, the surface gets a depth-stencil buffer, which allows masking and depth testing.
 ---@param width integer
 ---@param height integer
 ---@param format TextureFormat?
+---@param depth_stencil boolean?
 ---@return Surface
 ---@nodiscard
-function module.new(width, height, format) end
+function module.new(width, height, format, depth_stencil) end
+
+---Create a new multisampled surface, which smooths the edges of shapes drawn to it. If the
+---sample count isn't supported, the highest supported count below it is used instead.
+---
+---Drawing starts from the multisampled pixels and is resolved into the surface's texture, so
+---pixels uploaded directly to the texture are overwritten the next time the surface is drawn to.
+---@param width integer
+---@param height integer
+---@param samples integer
+---@param format TextureFormat?
+---@param depth_stencil boolean?
+---@return Surface
+---@nodiscard
+function module.new_msaa(width, height, samples, format, depth_stencil) end
 
 ---The surface's target texture.
 ---@param self Surface
@@ -46,6 +62,18 @@ function methods.height(self) end
 ---@nodiscard
 function methods.format(self) end
 
+---How many samples are taken per pixel when drawing to the surface.
+---@param self Surface
+---@return integer
+---@nodiscard
+function methods.sample_count(self) end
+
+---If the surface has a depth-stencil buffer.
+---@param self Surface
+---@return boolean
+---@nodiscard
+function methods.has_depth_stencil(self) end
+
 ---The surface's texture.
 ---@param self Surface
 ---@return Texture
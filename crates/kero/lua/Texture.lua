@@ -31,11 +31,20 @@ function module.default() end
 ---@nodiscard
 function module.from_img(img) end
 
--- ---Uploads pixels from the image to the texture. The image format must be the texture
--- ---format's equivalent (meaning RGB images will always throw an error if used here).
--- ---@param self Texture
--- ---@param img Image
--- function methods.set_pixels(self, img) end
+---Uploads pixels from the image to the texture. The image must be the same size as the
+---texture, and its format must be the texture format's equivalent (meaning RGB images will
+---always throw an error if used here).
+---@param self Texture
+---@param img Image
+function methods.upload(self, img) end
+
+---Uploads pixels from the image to a region of the texture, leaving the rest of its pixels
+---untouched. The image must be the same size as the region, and its format must be the
+---texture format's equivalent (meaning RGB images will always throw an error if used here).
+---@param self Texture
+---@param rect Rect
+---@param img Image
+function methods.upload_region(self, rect, img) end
 
 -- ---The texture ID.
 -- ---@param self Texture
@@ -1,5 +1,10 @@
 ---@meta
 
+---@alias Timestep
+---     |"fixed"
+---     |"variable"
+---     |"semi_fixed"
+
 ---@class Time
 local Time = {}
 
@@ -13,6 +18,29 @@ function Time.fps() end
 ---@nodiscard
 function Time.delta() end
 
+---How far the current frame is between the previous update and the next one, from 0 to 1.
+---Rendering can interpolate between the previous and current state by this amount. This is
+---only meaningful with a fixed timestep, with other timesteps it is always 1.
+---@return number
+---@nodiscard
+function Time.alpha() end
+
+---How the game loop advances time between updates. If the timestep is semi-fixed, its max
+---delta is also returned.
+---@return Timestep
+---@return number?
+---@nodiscard
+function Time.timestep() end
+
+---Set how the game loop advances time between updates.
+---
+---* `fixed`: update at the target FPS with a fixed delta, use `alpha()` to interpolate.
+---* `variable`: update once per frame with the real time since the last frame.
+---* `semi_fixed`: like variable, but split into updates no longer than `max_delta`.
+---@param timestep Timestep
+---@param max_delta number? Required for a semi-fixed timestep, and must be positive.
+function Time.set_timestep(timestep, max_delta) end
+
 ---Total time passed since the app started.
 ---@return number
 ---@nodiscard
@@ -0,0 +1,126 @@
+---@meta
+
+---@alias Ease
+---     |"linear"
+---     |"sine_in"
+---     |"sine_out"
+---     |"sine_in_out"
+---     |"quad_in"
+---     |"quad_out"
+---     |"quad_in_out"
+---     |"cubic_in"
+---     |"cubic_out"
+---     |"cubic_in_out"
+---     |"quart_in"
+---     |"quart_out"
+---     |"quart_in_out"
+---     |"quint_in"
+---     |"quint_out"
+---     |"quint_in_out"
+---     |"expo_in"
+---     |"expo_out"
+---     |"expo_in_out"
+---     |"circ_in"
+---     |"circ_out"
+---     |"circ_in_out"
+---     |"back_in"
+---     |"back_out"
+---     |"back_in_out"
+---     |"elastic_in"
+---     |"elastic_out"
+---     |"elastic_in_out"
+---     |"bounce_in"
+---     |"bounce_out"
+---     |"bounce_in_out"
+
+---Handle to an action running on the timeline.
+---@class ActionId
+
+---@class TweenOptions
+---@field ease Ease? The easing curve, defaults to `"linear"`.
+---@field delay number? Seconds to wait before starting.
+---@field repeat integer? How many more times to play after the first, or negative to repeat forever.
+---@field yoyo boolean? If the tween plays backwards on every other repeat.
+
+---Runs timers, tweens, and coroutines, which are advanced right before every update.
+---@class Timeline
+local Timeline = {}
+
+---Run the function as a coroutine, starting on the next update. Inside it, `Timeline.wait` can
+---be used to pause the coroutine for a while.
+---@param f fun()
+---@return ActionId
+function Timeline.run(f) end
+
+---Pause the running coroutine. If given a number, it waits that many seconds. If given an
+---action, it waits until the action finishes. Otherwise, it waits until the next update.
+---@param what number|ActionId|nil
+function Timeline.wait(what) end
+
+---Call the function once after `seconds` have passed.
+---@param seconds number
+---@param f fun()
+---@return ActionId
+function Timeline.after(seconds, f) end
+
+---Call the function every time `interval` seconds pass, until it is cancelled.
+---@param interval number
+---@param f fun()
+---@return ActionId
+function Timeline.every(interval, f) end
+
+---Tween between two numbers or two vectors over `duration` seconds, passing the value to
+---`apply` every update.
+---@generic T: number|Vec2
+---@param from T
+---@param to T
+---@param duration number
+---@param apply fun(value: T)
+---@param opts TweenOptions?
+---@return ActionId
+function Timeline.tween(from, to, duration, apply, opts) end
+
+---Stop the action before it finishes.
+---@param id ActionId
+function Timeline.cancel(id) end
+
+---If the action is still running.
+---@param id ActionId
+---@return boolean
+---@nodiscard
+function Timeline.is_running(id) end
+
+---Stop all running actions.
+function Timeline.clear() end
+
+---How many actions are running.
+---@return integer
+---@nodiscard
+function Timeline.len() end
+
+---If the timeline is paused, in which case its actions aren't updated.
+---@return boolean
+---@nodiscard
+function Timeline.is_paused() end
+
+function Timeline.pause() end
+
+function Timeline.resume() end
+
+---How fast time passes for the timeline, where 1 is normal speed.
+---@return number
+---@nodiscard
+function Timeline.time_scale() end
+
+---Set how fast time passes for the timeline, where 1 is normal speed.
+---@param scale number
+function Timeline.set_time_scale(scale) end
+
+---Apply the easing curve to the progress, which is clamped between 0 and 1.
+---@param ease Ease
+---@param t number
+---@return number
+---@nodiscard
+function Timeline.ease(ease, t) end
+
+return Timeline
@@ -0,0 +1,83 @@
+---@meta
+
+---@alias TouchPhase
+---| "pressed" # The finger touched the window this frame.
+---| "held" # The finger is still touching the window.
+---| "released" # The finger was lifted this frame.
+
+---@class Touch
+---@field id integer Unique ID of the finger, which stays the same until it is lifted.
+---@field pos Vec2 Position of the finger in the window.
+---@field pressure number? Pressure of the finger from 0 to 1, if the device supports it.
+---@field phase TouchPhase Phase of the touch this frame.
+
+---@class Touches
+local Touches = {}
+
+---All touches this frame, including fingers that were lifted this frame.
+---@return Touch[]
+---@nodiscard
+function Touches.all() end
+
+---Find the touch with the provided finger ID this frame.
+---@param id integer
+---@return Touch?
+---@nodiscard
+function Touches.get(id) end
+
+---How many fingers are touching the window.
+---@return integer
+---@nodiscard
+function Touches.count() end
+
+---If any fingers are touching the window.
+---@return boolean
+---@nodiscard
+function Touches.any_down() end
+
+---If the finger is touching the window.
+---@param id integer
+---@return boolean
+---@nodiscard
+function Touches.down(id) end
+
+---Touches that started this frame.
+---@return Touch[]
+---@nodiscard
+function Touches.pressed() end
+
+---Touches that ended this frame.
+---@return Touch[]
+---@nodiscard
+function Touches.released() end
+
+---How much the user pinched this frame. Positive values zoom in, negative values zoom out.
+---@return number
+---@nodiscard
+function Touches.pinch_delta() end
+
+---How far the user panned with two fingers this frame.
+---@return Vec2
+---@nodiscard
+function Touches.pan_delta() end
+
+---How much the user rotated with two fingers this frame, in radians. Positive values are counter-clockwise.
+---@return number
+---@nodiscard
+function Touches.rotation_delta() end
+
+---If the user double-tapped this frame.
+---@return boolean
+---@nodiscard
+function Touches.double_tapped() end
+
+---If the first finger touching the window is emulating the mouse.
+---@return boolean
+---@nodiscard
+function Touches.emulate_mouse() end
+
+---Set whether the first finger touching the window should emulate the mouse.
+---@param emulate boolean
+function Touches.set_emulate_mouse(emulate) end
+
+return Touches
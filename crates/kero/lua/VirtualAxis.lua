@@ -0,0 +1,83 @@
+---@meta
+
+---@alias ResponseCurve "linear"|"quadratic"|fun(t: number): number
+
+---@class (exact) VirtualAxis: VirtualAxisMethods
+
+---@class VirtualAxisModule: VirtualAxisMethods
+local module = {}
+
+---@class VirtualAxisMethods
+local methods = {}
+
+---Create a new virtual axis. If no gamepad is provided, it will listen to the most recently active
+---gamepad.
+---@param axis GamepadAxis? The gamepad axis to listen to.
+---@param neg Key? Key that pushes the axis negative.
+---@param pos Key? Key that pushes the axis positive.
+---@param gamepad Gamepad?
+---@return VirtualAxis
+---@nodiscard
+function module.new(axis, neg, pos, gamepad) end
+
+---The first gamepad axis this input listens to.
+---@param self VirtualAxis
+---@return GamepadAxis?
+---@nodiscard
+function methods.axis(self) end
+
+---All the gamepad axes this input listens to.
+---@param self VirtualAxis
+---@return GamepadAxis[]
+---@nodiscard
+function methods.axes(self) end
+
+---Set the gamepad axis, replacing any other axes.
+---@param self VirtualAxis
+---@param axis GamepadAxis?
+function methods.set_axis(self, axis) end
+
+---Listen to another gamepad axis.
+---@param self VirtualAxis
+---@param axis GamepadAxis
+function methods.add_axis(self, axis) end
+
+---If the axis state changed this frame.
+---@param self VirtualAxis
+---@return boolean
+---@nodiscard
+function methods.changed(self) end
+
+---The axis value from `-1` to `1`, after applying the deadzone and response curve.
+---@param self VirtualAxis
+---@return number
+---@nodiscard
+function methods.value(self) end
+
+---The axis deadzone.
+---@param self VirtualAxis
+---@return number inner
+---@return number outer
+---@nodiscard
+function methods.deadzone(self) end
+
+---Set the axis deadzone. Values below `inner` are treated as zero, and values above `outer` are
+---treated as fully pushed. If not provided, `outer` defaults to `1`.
+---@param self VirtualAxis
+---@param inner number
+---@param outer number?
+function methods.set_deadzone(self, inner, outer) end
+
+---The axis response curve.
+---@param self VirtualAxis
+---@return ResponseCurve
+---@nodiscard
+function methods.curve(self) end
+
+---Set the axis response curve, which is applied after the deadzone. A custom function maps a
+---magnitude from `0` to `1` to a new magnitude.
+---@param self VirtualAxis
+---@param curve ResponseCurve
+function methods.set_curve(self, curve) end
+
+return module
@@ -0,0 +1,114 @@
+---@meta
+
+---@alias DeadzoneShape "axial"|"radial"|"scaled_radial"
+---@alias StickSnap "none"|"cardinal"|"octal"
+
+---@class (exact) VirtualStick: VirtualStickMethods
+
+---@class VirtualStickModule: VirtualStickMethods
+local module = {}
+
+---@class VirtualStickMethods
+local methods = {}
+
+---Create a new virtual stick from a pair of axes.
+---@param x_axis VirtualAxis?
+---@param y_axis VirtualAxis?
+---@return VirtualStick
+---@nodiscard
+function module.new(x_axis, y_axis) end
+
+---Create a stick that listens to the left thumbstick and the arrow keys. If no gamepad is
+---provided, it will listen to the most recently active gamepad.
+---@param gamepad Gamepad?
+---@return VirtualStick
+---@nodiscard
+function module.left(gamepad) end
+
+---Create a stick that listens to the right thumbstick. If no gamepad is provided, it will
+---listen to the most recently active gamepad.
+---@param gamepad Gamepad?
+---@return VirtualStick
+---@nodiscard
+function module.right(gamepad) end
+
+---The stick's x-axis.
+---@param self VirtualStick
+---@return VirtualAxis
+---@nodiscard
+function methods.x_axis(self) end
+
+---The stick's y-axis.
+---@param self VirtualStick
+---@return VirtualAxis
+---@nodiscard
+function methods.y_axis(self) end
+
+---Set the stick's axes.
+---@param self VirtualStick
+---@param x_axis VirtualAxis
+---@param y_axis VirtualAxis
+function methods.set_axes(self, x_axis, y_axis) end
+
+---If either axes changed this frame.
+---@param self VirtualStick
+---@return boolean
+---@nodiscard
+function methods.changed(self) end
+
+---The stick's raw x-value, before the stick's own processing is applied.
+---@param self VirtualStick
+---@return number
+---@nodiscard
+function methods.x(self) end
+
+---The stick's raw y-value, before the stick's own processing is applied.
+---@param self VirtualStick
+---@return number
+---@nodiscard
+function methods.y(self) end
+
+---The stick's value, after applying the deadzone, response curve, and snapping.
+---@param self VirtualStick
+---@return Vec2
+---@nodiscard
+function methods.value(self) end
+
+---The stick deadzone.
+---@param self VirtualStick
+---@return DeadzoneShape shape
+---@return number inner
+---@return number outer
+---@nodiscard
+function methods.deadzone(self) end
+
+---Set the stick deadzone. If not provided, `outer` defaults to `1`.
+---@param self VirtualStick
+---@param shape DeadzoneShape
+---@param inner number
+---@param outer number?
+function methods.set_deadzone(self, shape, inner, outer) end
+
+---The stick response curve.
+---@param self VirtualStick
+---@return ResponseCurve
+---@nodiscard
+function methods.curve(self) end
+
+---Set the stick response curve, which is applied to its length after the deadzone.
+---@param self VirtualStick
+---@param curve ResponseCurve
+function methods.set_curve(self, curve) end
+
+---How the stick's direction is snapped.
+---@param self VirtualStick
+---@return StickSnap
+---@nodiscard
+function methods.snap(self) end
+
+---Set how the stick's direction is snapped to 4 or 8 directions.
+---@param self VirtualStick
+---@param snap StickSnap
+function methods.set_snap(self, snap) end
+
+return module
@@ -0,0 +1,82 @@
+---@meta
+
+---A playing sound or music track. Once a voice has finished or been stopped,
+---its setters do nothing.
+---@class (exact) Voice: VoiceMethods
+
+---@class VoiceMethods
+local methods = {}
+
+---If the voice is still playing. Paused voices count as playing.
+---@param self Voice
+---@return boolean
+---@nodiscard
+function methods.is_playing(self) end
+
+---Name of the bus the voice plays through.
+---@param self Voice
+---@return string?
+---@nodiscard
+function methods.bus(self) end
+
+---The voice's volume.
+---@param self Voice
+---@return number
+---@nodiscard
+function methods.volume(self) end
+
+---Set the voice's volume.
+---@param self Voice
+---@param volume number
+function methods.set_volume(self, volume) end
+
+---The voice's stereo pan, from `-1` (left) to `1` (right).
+---@param self Voice
+---@return number
+---@nodiscard
+function methods.pan(self) end
+
+---Set the voice's stereo pan, from `-1` (left) to `1` (right).
+---@param self Voice
+---@param pan number
+function methods.set_pan(self, pan) end
+
+---The voice's pitch, where `1` is the original speed.
+---@param self Voice
+---@return number
+---@nodiscard
+function methods.pitch(self) end
+
+---Set the voice's pitch, where `1` is the original speed.
+---@param self Voice
+---@param pitch number
+function methods.set_pitch(self, pitch) end
+
+---If the voice restarts when it reaches the end.
+---@param self Voice
+---@return boolean
+---@nodiscard
+function methods.looping(self) end
+
+---Set whether the voice restarts when it reaches the end.
+---@param self Voice
+---@param looping boolean
+function methods.set_looping(self, looping) end
+
+---If the voice is paused.
+---@param self Voice
+---@return boolean
+---@nodiscard
+function methods.paused(self) end
+
+---Pause the voice.
+---@param self Voice
+function methods.pause(self) end
+
+---Resume the voice if it was paused.
+---@param self Voice
+function methods.resume(self) end
+
+---Stop the voice. It cannot be resumed afterwards.
+---@param self Voice
+function methods.stop(self) end
@@ -35,6 +35,11 @@
 ---     |"zoom_in"
 ---     |"zoom_out"
 
+---@alias CursorGrab
+---     |"none"
+---     |"confined"
+---     |"locked"
+
 ---@class Window
 local Window = {}
 
@@ -184,4 +189,31 @@ function Window.set_minimized(minimized) end
 ---@param cursor CursorIcon
 function Window.set_cursor(cursor) end
 
+---Set a custom image to use as the window's cursor. The hotspot is the pixel in the image positioned on the mouse.
+---@param img Image
+---@param x integer
+---@param y integer
+function Window.set_custom_cursor(img, x, y) end
+
+---Set whether the cursor is visible when the mouse is over the window.
+---@param visible boolean
+function Window.set_cursor_visible(visible) end
+
+---Set how the cursor is grabbed by the window. If the mode isn't supported, the other grab mode is used instead.
+---@param grab CursorGrab
+function Window.set_cursor_grab(grab) end
+
+---Set the window icon.
+---@param img Image
+function Window.set_icon(img) end
+
+---Set whether the window accepts input from an IME (input method editor), needed to type languages like Japanese,
+---Chinese, or Korean. Enable this while a text field has focus.
+---@param allowed boolean
+function Window.set_ime_allowed(allowed) end
+
+---Set the area of the window where text is being edited, so the IME can place its candidate window next to it.
+---@param area Rect
+function Window.set_ime_cursor_area(area) end
+
 return Window
\ No newline at end of file
@@ -0,0 +1,32 @@
+/// An error loading audio or opening an audio output.
+#[derive(Debug, thiserror::Error)]
+pub enum AudioError {
+    #[error("{0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("{0}")]
+    Wav(#[from] hound::Error),
+
+    #[error("{0}")]
+    Ogg(#[from] lewton::VorbisError),
+
+    #[error("unrecognized audio format (expected WAV or OGG)")]
+    UnknownFormat,
+
+    #[error("audio has an invalid channel count ({0}), expected 1 or 2")]
+    InvalidChannels(u16),
+
+    #[error("no audio output device available")]
+    NoDevice,
+
+    #[error("{0}")]
+    Device(String),
+}
+
+#[cfg(feature = "lua")]
+impl From<AudioError> for mlua::Error {
+    #[inline]
+    fn from(value: AudioError) -> Self {
+        mlua::Error::external(value)
+    }
+}
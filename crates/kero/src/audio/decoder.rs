@@ -0,0 +1,113 @@
+use crate::audio::AudioError;
+use lewton::inside_ogg::OggStreamReader;
+use std::io::Cursor;
+use std::sync::Arc;
+
+/// How many samples to decode at a time when streaming WAV files.
+const WAV_BLOCK: usize = 4096;
+
+type Bytes = Cursor<Arc<[u8]>>;
+
+/// Incrementally decodes a WAV or OGG file into interleaved `f32` samples.
+pub(crate) enum Decoder {
+    Wav {
+        reader: hound::WavReader<Bytes>,
+        float: bool,
+        scale: f32,
+    },
+    Ogg {
+        reader: Box<OggStreamReader<Bytes>>,
+    },
+}
+
+impl Decoder {
+    /// Create a decoder, detecting the format from the file header.
+    pub fn new(bytes: Arc<[u8]>) -> Result<Self, AudioError> {
+        let this = if bytes.starts_with(b"RIFF") {
+            let reader = hound::WavReader::new(Cursor::new(bytes))?;
+            let spec = reader.spec();
+            Self::Wav {
+                reader,
+                float: spec.sample_format == hound::SampleFormat::Float,
+                scale: 1.0 / (1u64 << (spec.bits_per_sample.max(1) - 1)) as f32,
+            }
+        } else if bytes.starts_with(b"OggS") {
+            Self::Ogg {
+                reader: Box::new(OggStreamReader::new(Cursor::new(bytes))?),
+            }
+        } else {
+            return Err(AudioError::UnknownFormat);
+        };
+        match this.channels() {
+            1 | 2 => Ok(this),
+            n => Err(AudioError::InvalidChannels(n)),
+        }
+    }
+
+    /// How many channels the audio has.
+    pub fn channels(&self) -> u16 {
+        match self {
+            Self::Wav { reader, .. } => reader.spec().channels,
+            Self::Ogg { reader } => reader.ident_hdr.audio_channels as u16,
+        }
+    }
+
+    /// The audio's sample rate in hertz.
+    pub fn sample_rate(&self) -> u32 {
+        match self {
+            Self::Wav { reader, .. } => reader.spec().sample_rate,
+            Self::Ogg { reader } => reader.ident_hdr.audio_sample_rate,
+        }
+    }
+
+    /// Decode the next block of samples, appending them to `out`. Returns `false` once the end
+    /// of the audio has been reached and no more samples were written.
+    pub fn decode(&mut self, out: &mut Vec<f32>) -> Result<bool, AudioError> {
+        let start = out.len();
+        match self {
+            Self::Wav {
+                reader,
+                float,
+                scale,
+            } => {
+                if *float {
+                    for s in reader.samples::<f32>().take(WAV_BLOCK) {
+                        out.push(s?);
+                    }
+                } else {
+                    for s in reader.samples::<i32>().take(WAV_BLOCK) {
+                        out.push(s? as f32 * *scale);
+                    }
+                }
+            }
+            Self::Ogg { reader } => {
+                // packets can be empty, so keep reading until we get samples or run out
+                while out.len() == start {
+                    match reader.read_dec_packet_itl()? {
+                        Some(packet) => {
+                            out.extend(packet.into_iter().map(|s| s as f32 / 32768.0));
+                        }
+                        None => break,
+                    }
+                }
+            }
+        }
+        Ok(out.len() > start)
+    }
+
+    /// Decode all remaining samples.
+    pub fn decode_all(&mut self) -> Result<Vec<f32>, AudioError> {
+        let mut samples = Vec::new();
+        while self.decode(&mut samples)? {}
+        Ok(samples)
+    }
+
+    /// Seek back to the start of the audio.
+    pub fn rewind(&mut self) -> Result<(), AudioError> {
+        match self {
+            Self::Wav { reader, .. } => reader.seek(0)?,
+            Self::Ogg { reader } => reader.seek_absgp_pg(0)?,
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,265 @@
+use crate::audio::Sound;
+use crate::audio::decoder::Decoder;
+use compact_str::CompactString;
+use fnv::FnvHashMap;
+use std::collections::VecDeque;
+
+/// Mixes all playing voices into an output buffer.
+///
+/// The mixer lives behind a mutex shared between [`Audio`](super::Audio), every
+/// [`Voice`](super::Voice), and the output device's callback thread.
+pub(crate) struct Mixer {
+    pub sample_rate: u32,
+    pub master_volume: f32,
+    pub buses: FnvHashMap<CompactString, Bus>,
+    pub voices: Vec<VoiceState>,
+    next_id: u64,
+}
+
+/// A named group of voices that share a volume and can be paused together.
+#[derive(Debug, Copy, Clone)]
+pub(crate) struct Bus {
+    pub volume: f32,
+    pub paused: bool,
+}
+
+impl Default for Bus {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            volume: 1.0,
+            paused: false,
+        }
+    }
+}
+
+/// Where a voice gets its samples from.
+pub(crate) enum Source {
+    Sound(Sound),
+    Stream(Stream),
+}
+
+impl Source {
+    #[inline]
+    fn sample_rate(&self) -> u32 {
+        match self {
+            Self::Sound(sound) => sound.sample_rate(),
+            Self::Stream(stream) => stream.decoder.sample_rate(),
+        }
+    }
+
+    #[inline]
+    fn frame(&mut self, i: usize, looping: bool) -> Option<[f32; 2]> {
+        match self {
+            Self::Sound(sound) => sound
+                .frame(i)
+                .or_else(|| looping.then(|| sound.frame(0)).flatten()),
+            Self::Stream(stream) => stream.frame(i, looping),
+        }
+    }
+}
+
+/// Decodes music on the fly, buffering just enough frames to mix from.
+pub(crate) struct Stream {
+    decoder: Decoder,
+    frames: VecDeque<[f32; 2]>,
+    offset: usize,
+    scratch: Vec<f32>,
+    ended: bool,
+}
+
+impl Stream {
+    pub fn new(decoder: Decoder) -> Self {
+        Self {
+            decoder,
+            frames: VecDeque::new(),
+            offset: 0,
+            scratch: Vec::new(),
+            ended: false,
+        }
+    }
+
+    fn frame(&mut self, i: usize, looping: bool) -> Option<[f32; 2]> {
+        while i >= self.offset + self.frames.len() {
+            if !self.fill(looping) {
+                return None;
+            }
+        }
+        self.frames.get(i.checked_sub(self.offset)?).copied()
+    }
+
+    fn fill(&mut self, looping: bool) -> bool {
+        if self.ended {
+            return false;
+        }
+        self.scratch.clear();
+        let decoded = match self.decoder.decode(&mut self.scratch) {
+            Ok(true) => true,
+            Ok(false) if looping => {
+                self.decoder.rewind().is_ok()
+                    && matches!(self.decoder.decode(&mut self.scratch), Ok(true))
+            }
+            _ => false,
+        };
+        if !decoded {
+            self.ended = true;
+            return false;
+        }
+        match self.decoder.channels() {
+            1 => self.frames.extend(self.scratch.iter().map(|&s| [s, s])),
+            _ => self
+                .frames
+                .extend(self.scratch.chunks_exact(2).map(|s| [s[0], s[1]])),
+        }
+        true
+    }
+
+    fn discard_before(&mut self, i: usize) {
+        while self.offset < i && self.frames.pop_front().is_some() {
+            self.offset += 1;
+        }
+    }
+}
+
+/// The mixer's state for a single playing voice.
+pub(crate) struct VoiceState {
+    pub id: u64,
+    pub bus: CompactString,
+    pub volume: f32,
+    pub pan: f32,
+    pub pitch: f32,
+    pub looping: bool,
+    pub paused: bool,
+    source: Source,
+    pos: f64,
+    finished: bool,
+}
+
+impl Mixer {
+    pub fn new(sample_rate: u32) -> Self {
+        Self {
+            sample_rate,
+            master_volume: 1.0,
+            buses: FnvHashMap::default(),
+            voices: Vec::new(),
+            next_id: 0,
+        }
+    }
+
+    /// Start playing a new voice, returning its id.
+    pub fn add(
+        &mut self,
+        source: Source,
+        bus: &str,
+        volume: f32,
+        pan: f32,
+        pitch: f32,
+        looping: bool,
+    ) -> u64 {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.voices.push(VoiceState {
+            id,
+            bus: bus.into(),
+            volume: volume.max(0.0),
+            pan: pan.clamp(-1.0, 1.0),
+            pitch: pitch.max(0.0),
+            looping,
+            paused: false,
+            source,
+            pos: 0.0,
+            finished: false,
+        });
+        id
+    }
+
+    #[inline]
+    pub fn voice(&self, id: u64) -> Option<&VoiceState> {
+        self.voices.iter().find(|v| v.id == id)
+    }
+
+    #[inline]
+    pub fn voice_mut(&mut self, id: u64) -> Option<&mut VoiceState> {
+        self.voices.iter_mut().find(|v| v.id == id)
+    }
+
+    #[inline]
+    pub fn bus(&self, name: &str) -> Bus {
+        self.buses.get(name).copied().unwrap_or_default()
+    }
+
+    #[inline]
+    pub fn bus_mut(&mut self, name: &str) -> &mut Bus {
+        self.buses.entry(name.into()).or_default()
+    }
+
+    /// Mix all playing voices into `out`, which is interleaved with `channels` channels.
+    pub fn mix(&mut self, out: &mut [f32], channels: usize) {
+        out.fill(0.0);
+        if channels == 0 {
+            return;
+        }
+
+        let Self {
+            sample_rate,
+            master_volume,
+            buses,
+            voices,
+            ..
+        } = self;
+
+        for voice in voices.iter_mut() {
+            let bus = buses.get(&voice.bus).copied().unwrap_or_default();
+            if voice.paused || bus.paused {
+                continue;
+            }
+
+            let gain = voice.volume * bus.volume * *master_volume;
+            let gain_l = gain * (1.0 - voice.pan).min(1.0);
+            let gain_r = gain * (1.0 + voice.pan).min(1.0);
+            let step = voice.pitch as f64 * voice.source.sample_rate() as f64 / *sample_rate as f64;
+            let len = match &voice.source {
+                Source::Sound(sound) => sound.frame_count() as f64,
+                Source::Stream(_) => f64::INFINITY,
+            };
+
+            for frame in out.chunks_exact_mut(channels) {
+                if voice.looping && voice.pos >= len {
+                    voice.pos %= len;
+                }
+
+                // linearly interpolate between the two nearest source frames
+                let i = voice.pos as usize;
+                let Some(a) = voice.source.frame(i, voice.looping) else {
+                    voice.finished = true;
+                    break;
+                };
+                let b = voice.source.frame(i + 1, voice.looping).unwrap_or(a);
+                let t = voice.pos.fract() as f32;
+                let l = (a[0] + (b[0] - a[0]) * t) * gain_l;
+                let r = (a[1] + (b[1] - a[1]) * t) * gain_r;
+
+                match frame {
+                    [mono] => *mono += (l + r) * 0.5,
+                    [left, right, ..] => {
+                        *left += l;
+                        *right += r;
+                    }
+                    [] => {}
+                }
+
+                voice.pos += step;
+            }
+
+            if let Source::Stream(stream) = &mut voice.source {
+                stream.discard_before(voice.pos as usize);
+            }
+        }
+
+        voices.retain(|v| !v.finished);
+
+        for s in out {
+            *s = s.clamp(-1.0, 1.0);
+        }
+    }
+}
@@ -0,0 +1,15 @@
+//! Sound effects, streamed music, and audio mixing.
+
+mod audio_error;
+mod decoder;
+mod mixer;
+mod music;
+mod output;
+mod sound;
+mod voice;
+
+pub use audio_error::*;
+pub use music::*;
+pub use output::*;
+pub use sound::*;
+pub use voice::*;
@@ -0,0 +1,74 @@
+use crate::audio::AudioError;
+use crate::audio::decoder::Decoder;
+use std::fmt::{Debug, Formatter};
+use std::path::Path;
+use std::sync::Arc;
+
+#[cfg(feature = "lua")]
+pub type MusicObj = fey_lua::UserDataOf<Music>;
+#[cfg(feature = "lua")]
+pub type MusicRef = mlua::UserDataRef<Music>;
+
+/// Handle to a streamed music track.
+///
+/// This handle can be cloned and passed around freely to give objects access to the music.
+///
+/// Unlike [`Sound`](super::Sound), music is kept in its encoded form and decoded a little at a
+/// time while it plays, so long tracks don't need to be held in memory as raw samples.
+#[derive(Clone)]
+pub struct Music(Arc<Inner>);
+
+struct Inner {
+    bytes: Arc<[u8]>,
+    channels: u16,
+    sample_rate: u32,
+}
+
+impl Debug for Music {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Music").finish_non_exhaustive()
+    }
+}
+
+impl PartialEq for Music {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Music {
+    /// Create music from a WAV or OGG file in memory.
+    pub fn from_memory(bytes: impl Into<Arc<[u8]>>) -> Result<Self, AudioError> {
+        let bytes = bytes.into();
+        let decoder = Decoder::new(bytes.clone())?;
+        Ok(Self(Arc::new(Inner {
+            bytes,
+            channels: decoder.channels(),
+            sample_rate: decoder.sample_rate(),
+        })))
+    }
+
+    /// Load music from a WAV or OGG file.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, AudioError> {
+        Self::from_memory(std::fs::read(path)?)
+    }
+
+    /// How many channels the music has.
+    #[inline]
+    pub fn channels(&self) -> u16 {
+        self.0.channels
+    }
+
+    /// The music's sample rate in hertz.
+    #[inline]
+    pub fn sample_rate(&self) -> u32 {
+        self.0.sample_rate
+    }
+
+    /// Create a new decoder positioned at the start of the track.
+    #[inline]
+    pub(crate) fn decoder(&self) -> Result<Decoder, AudioError> {
+        Decoder::new(self.0.bytes.clone())
+    }
+}
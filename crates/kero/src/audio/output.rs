@@ -0,0 +1,296 @@
+use crate::audio::mixer::{Mixer, Source, Stream};
+use crate::audio::{AudioError, Music, Sound, Voice};
+use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+use cpal::{FromSample, SampleFormat, SizedSample, StreamConfig};
+use std::fmt::{Debug, Formatter};
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+
+/// Handle to the audio system.
+///
+/// This handle can be cloned and passed around freely to give objects access to audio playback.
+///
+/// Obtained from [`Context`](crate::core::Context). All audio plays through a named bus, which
+/// has its own volume and can be paused as a group. Sounds play through [`Audio::SFX_BUS`] and
+/// music through [`Audio::MUSIC_BUS`] unless a different bus is requested.
+///
+/// If no output device is available, a null output is used instead. It plays nothing by
+/// itself, but can be mixed manually with [`mix`](Self::mix) for offline rendering or tests.
+#[derive(Clone)]
+pub struct Audio(Rc<Inner>);
+
+struct Inner {
+    mixer: Arc<Mutex<Mixer>>,
+    channels: u16,
+    stream: Option<cpal::Stream>,
+}
+
+impl Debug for Audio {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Audio").finish_non_exhaustive()
+    }
+}
+
+impl PartialEq for Audio {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Audio {
+    /// The bus sounds play through by default.
+    pub const SFX_BUS: &'static str = "sfx";
+
+    /// The bus music plays through by default.
+    pub const MUSIC_BUS: &'static str = "music";
+
+    /// Sample rate used by the null output.
+    pub const NULL_SAMPLE_RATE: u32 = 48000;
+
+    pub(crate) fn new(null: bool) -> Self {
+        if null {
+            return Self::new_null(Self::NULL_SAMPLE_RATE, 2);
+        }
+        Self::new_device().unwrap_or_else(|_| Self::new_null(Self::NULL_SAMPLE_RATE, 2))
+    }
+
+    /// Open the default audio output device.
+    pub fn new_device() -> Result<Self, AudioError> {
+        let device = cpal::default_host()
+            .default_output_device()
+            .ok_or(AudioError::NoDevice)?;
+        let supported = device
+            .default_output_config()
+            .map_err(|e| AudioError::Device(e.to_string()))?;
+        let config = supported.config();
+        let mixer = Arc::new(Mutex::new(Mixer::new(config.sample_rate)));
+        let stream = match supported.sample_format() {
+            SampleFormat::F32 => build_stream::<f32>(&device, &config, mixer.clone()),
+            SampleFormat::I16 => build_stream::<i16>(&device, &config, mixer.clone()),
+            SampleFormat::U16 => build_stream::<u16>(&device, &config, mixer.clone()),
+            SampleFormat::I32 => build_stream::<i32>(&device, &config, mixer.clone()),
+            format => {
+                return Err(AudioError::Device(format!(
+                    "unsupported sample format: {format:?}"
+                )));
+            }
+        }?;
+        stream
+            .play()
+            .map_err(|e| AudioError::Device(e.to_string()))?;
+        Ok(Self(Rc::new(Inner {
+            mixer,
+            channels: config.channels,
+            stream: Some(stream),
+        })))
+    }
+
+    /// Create a null audio output that isn't connected to any device. Audio played through it
+    /// only advances when [`mix`](Self::mix) is called.
+    pub fn new_null(sample_rate: u32, channels: u16) -> Self {
+        Self(Rc::new(Inner {
+            mixer: Arc::new(Mutex::new(Mixer::new(sample_rate))),
+            channels: channels.max(1),
+            stream: None,
+        }))
+    }
+
+    /// If this is a null output with no device attached.
+    #[inline]
+    pub fn is_null(&self) -> bool {
+        self.0.stream.is_none()
+    }
+
+    /// The output's sample rate in hertz.
+    #[inline]
+    pub fn sample_rate(&self) -> u32 {
+        self.0.mixer.lock().unwrap().sample_rate
+    }
+
+    /// How many channels the output has.
+    #[inline]
+    pub fn channels(&self) -> u16 {
+        self.0.channels
+    }
+
+    /// Mix the next block of audio into `out`, interleaved with [`channels`](Self::channels)
+    /// channels. This advances every playing voice, so it should only be used with a null output.
+    pub fn mix(&self, out: &mut [f32]) {
+        self.0
+            .mixer
+            .lock()
+            .unwrap()
+            .mix(out, self.0.channels as usize);
+    }
+
+    /// Play a sound through the sfx bus.
+    #[inline]
+    pub fn play(&self, sound: &Sound) -> Voice {
+        self.play_ext(sound, Self::SFX_BUS, 1.0, 0.0, 1.0, false)
+    }
+
+    /// Play a sound with the provided bus, volume, pan, pitch, and looping.
+    pub fn play_ext(
+        &self,
+        sound: &Sound,
+        bus: &str,
+        volume: f32,
+        pan: f32,
+        pitch: f32,
+        looping: bool,
+    ) -> Voice {
+        let source = Source::Sound(sound.clone());
+        let id = self
+            .0
+            .mixer
+            .lock()
+            .unwrap()
+            .add(source, bus, volume, pan, pitch, looping);
+        Voice::new(self.0.mixer.clone(), id)
+    }
+
+    /// Play looping music through the music bus.
+    #[inline]
+    pub fn play_music(&self, music: &Music) -> Result<Voice, AudioError> {
+        self.play_music_ext(music, Self::MUSIC_BUS, 1.0, true)
+    }
+
+    /// Play music with the provided bus, volume, and looping.
+    pub fn play_music_ext(
+        &self,
+        music: &Music,
+        bus: &str,
+        volume: f32,
+        looping: bool,
+    ) -> Result<Voice, AudioError> {
+        let source = Source::Stream(Stream::new(music.decoder()?));
+        let id = self
+            .0
+            .mixer
+            .lock()
+            .unwrap()
+            .add(source, bus, volume, 0.0, 1.0, looping);
+        Ok(Voice::new(self.0.mixer.clone(), id))
+    }
+
+    /// How many voices are currently playing.
+    #[inline]
+    pub fn voice_count(&self) -> usize {
+        self.0.mixer.lock().unwrap().voices.len()
+    }
+
+    /// Volume applied to all audio.
+    #[inline]
+    pub fn master_volume(&self) -> f32 {
+        self.0.mixer.lock().unwrap().master_volume
+    }
+
+    /// Set the volume applied to all audio.
+    #[inline]
+    pub fn set_master_volume(&self, volume: f32) {
+        self.0.mixer.lock().unwrap().master_volume = volume.max(0.0);
+    }
+
+    /// Volume of the bus.
+    #[inline]
+    pub fn bus_volume(&self, bus: &str) -> f32 {
+        self.0.mixer.lock().unwrap().bus(bus).volume
+    }
+
+    /// Set the volume of the bus.
+    #[inline]
+    pub fn set_bus_volume(&self, bus: &str, volume: f32) {
+        self.0.mixer.lock().unwrap().bus_mut(bus).volume = volume.max(0.0);
+    }
+
+    /// If the bus is paused.
+    #[inline]
+    pub fn bus_paused(&self, bus: &str) -> bool {
+        self.0.mixer.lock().unwrap().bus(bus).paused
+    }
+
+    /// Pause or resume all voices playing through the bus.
+    #[inline]
+    pub fn set_bus_paused(&self, bus: &str, paused: bool) {
+        self.0.mixer.lock().unwrap().bus_mut(bus).paused = paused;
+    }
+
+    /// Stop all voices playing through the bus.
+    #[inline]
+    pub fn stop_bus(&self, bus: &str) {
+        self.0.mixer.lock().unwrap().voices.retain(|v| v.bus != bus);
+    }
+
+    /// Stop all voices.
+    #[inline]
+    pub fn stop_all(&self) {
+        self.0.mixer.lock().unwrap().voices.clear();
+    }
+}
+
+fn build_stream<T: SizedSample + FromSample<f32>>(
+    device: &cpal::Device,
+    config: &StreamConfig,
+    mixer: Arc<Mutex<Mixer>>,
+) -> Result<cpal::Stream, AudioError> {
+    let channels = config.channels as usize;
+    let mut buf = Vec::new();
+    device
+        .build_output_stream(
+            config,
+            move |out: &mut [T], _| {
+                buf.resize(out.len(), 0.0);
+                mixer.lock().unwrap().mix(&mut buf, channels);
+                for (dst, &src) in out.iter_mut().zip(&buf) {
+                    *dst = T::from_sample(src);
+                }
+            },
+            |_| {},
+            None,
+        )
+        .map_err(|e| AudioError::Device(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn null_mixing() {
+        let audio = Audio::new_null(4, 2);
+        let sound = Sound::from_samples(1, 4, vec![1.0, 0.5, 0.25, 0.0]).unwrap();
+        let mut out = [0.0; 8];
+
+        // pan fully left, half volume
+        let voice = audio.play_ext(&sound, "a", 0.5, -1.0, 1.0, false);
+        audio.mix(&mut out);
+        assert_eq!(out, [0.5, 0.0, 0.25, 0.0, 0.125, 0.0, 0.0, 0.0]);
+        audio.mix(&mut out);
+        assert!(!voice.is_playing());
+        assert_eq!(out, [0.0; 8]);
+
+        // buses scale and pause their voices
+        let voice = audio.play_ext(&sound, "a", 1.0, 0.0, 1.0, true);
+        audio.set_bus_volume("a", 0.5);
+        audio.set_bus_paused("a", true);
+        audio.mix(&mut out);
+        assert_eq!(out, [0.0; 8]);
+        audio.set_bus_paused("a", false);
+        audio.mix(&mut out);
+        assert_eq!(out, [0.5, 0.5, 0.25, 0.25, 0.125, 0.125, 0.0, 0.0]);
+
+        // looping voices keep playing until stopped
+        audio.mix(&mut out);
+        assert_eq!(out[0], 0.5);
+        assert!(voice.is_playing());
+        audio.stop_bus("a");
+        assert!(!voice.is_playing());
+        assert_eq!(audio.voice_count(), 0);
+
+        // pitch resamples with linear interpolation
+        audio.play_ext(&sound, "b", 1.0, 0.0, 0.5, false);
+        audio.mix(&mut out);
+        assert_eq!(out, [1.0, 1.0, 0.75, 0.75, 0.5, 0.5, 0.375, 0.375]);
+    }
+}
@@ -0,0 +1,102 @@
+use crate::audio::AudioError;
+use crate::audio::decoder::Decoder;
+use std::fmt::{Debug, Formatter};
+use std::path::Path;
+use std::sync::Arc;
+
+#[cfg(feature = "lua")]
+pub type SoundObj = fey_lua::UserDataOf<Sound>;
+#[cfg(feature = "lua")]
+pub type SoundRef = mlua::UserDataRef<Sound>;
+
+/// Handle to a fully decoded sound effect.
+///
+/// This handle can be cloned and passed around freely to give objects access to the sound.
+///
+/// Sounds are decoded into memory up front, which makes them cheap to play many times at once.
+/// For long tracks such as background music, use [`Music`](super::Music) instead.
+#[derive(Clone)]
+pub struct Sound(Arc<Inner>);
+
+struct Inner {
+    channels: u16,
+    sample_rate: u32,
+    samples: Vec<f32>,
+}
+
+impl Debug for Sound {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Sound").finish_non_exhaustive()
+    }
+}
+
+impl PartialEq for Sound {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Sound {
+    /// Create a sound from interleaved samples. The sound must have 1 or 2 channels.
+    pub fn from_samples(
+        channels: u16,
+        sample_rate: u32,
+        samples: Vec<f32>,
+    ) -> Result<Self, AudioError> {
+        if !matches!(channels, 1 | 2) {
+            return Err(AudioError::InvalidChannels(channels));
+        }
+        Ok(Self(Arc::new(Inner {
+            channels,
+            sample_rate,
+            samples,
+        })))
+    }
+
+    /// Decode a sound from a WAV or OGG file in memory.
+    pub fn from_memory(bytes: &[u8]) -> Result<Self, AudioError> {
+        let mut decoder = Decoder::new(bytes.into())?;
+        let samples = decoder.decode_all()?;
+        Self::from_samples(decoder.channels(), decoder.sample_rate(), samples)
+    }
+
+    /// Load and decode a sound from a WAV or OGG file.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, AudioError> {
+        let bytes = std::fs::read(path)?;
+        Self::from_memory(&bytes)
+    }
+
+    /// How many channels the sound has.
+    #[inline]
+    pub fn channels(&self) -> u16 {
+        self.0.channels
+    }
+
+    /// The sound's sample rate in hertz.
+    #[inline]
+    pub fn sample_rate(&self) -> u32 {
+        self.0.sample_rate
+    }
+
+    /// How many sample frames the sound has.
+    #[inline]
+    pub fn frame_count(&self) -> usize {
+        self.0.samples.len() / self.0.channels as usize
+    }
+
+    /// Duration of the sound in seconds.
+    #[inline]
+    pub fn duration(&self) -> f32 {
+        self.frame_count() as f32 / self.0.sample_rate as f32
+    }
+
+    /// Get a stereo sample frame, or `None` if `i` is past the end of the sound.
+    #[inline]
+    pub(crate) fn frame(&self, i: usize) -> Option<[f32; 2]> {
+        match self.0.channels {
+            1 => self.0.samples.get(i).map(|&s| [s, s]),
+            _ => self.0.samples.get(i * 2..i * 2 + 2).map(|s| [s[0], s[1]]),
+        }
+    }
+}
@@ -0,0 +1,140 @@
+use crate::audio::mixer::{Mixer, VoiceState};
+use std::fmt::{Debug, Formatter};
+use std::sync::{Arc, Mutex};
+
+#[cfg(feature = "lua")]
+pub type VoiceObj = fey_lua::UserDataOf<Voice>;
+#[cfg(feature = "lua")]
+pub type VoiceRef = mlua::UserDataRef<Voice>;
+
+/// Handle to a playing sound or music track.
+///
+/// This handle can be cloned and passed around freely to give objects control over the voice.
+///
+/// Voices are created by playing audio through [`Audio`](super::Audio). Once a voice has
+/// finished or been stopped, its setters do nothing and its getters return default values.
+#[derive(Clone)]
+pub struct Voice {
+    mixer: Arc<Mutex<Mixer>>,
+    id: u64,
+}
+
+impl Debug for Voice {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Voice").finish_non_exhaustive()
+    }
+}
+
+impl PartialEq for Voice {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id && Arc::ptr_eq(&self.mixer, &other.mixer)
+    }
+}
+
+impl Voice {
+    #[inline]
+    pub(crate) fn new(mixer: Arc<Mutex<Mixer>>, id: u64) -> Self {
+        Self { mixer, id }
+    }
+
+    #[inline]
+    fn get<T>(&self, f: impl FnOnce(&VoiceState) -> T) -> Option<T> {
+        self.mixer.lock().unwrap().voice(self.id).map(f)
+    }
+
+    #[inline]
+    fn set(&self, f: impl FnOnce(&mut VoiceState)) {
+        if let Some(voice) = self.mixer.lock().unwrap().voice_mut(self.id) {
+            f(voice);
+        }
+    }
+
+    /// If the voice is still playing. Paused voices count as playing.
+    #[inline]
+    pub fn is_playing(&self) -> bool {
+        self.get(|_| ()).is_some()
+    }
+
+    /// Name of the bus the voice plays through.
+    #[inline]
+    pub fn bus(&self) -> Option<String> {
+        self.get(|v| v.bus.to_string())
+    }
+
+    /// The voice's volume.
+    #[inline]
+    pub fn volume(&self) -> f32 {
+        self.get(|v| v.volume).unwrap_or(0.0)
+    }
+
+    /// Set the voice's volume.
+    #[inline]
+    pub fn set_volume(&self, volume: f32) {
+        self.set(|v| v.volume = volume.max(0.0));
+    }
+
+    /// The voice's stereo pan, from `-1.0` (left) to `1.0` (right).
+    #[inline]
+    pub fn pan(&self) -> f32 {
+        self.get(|v| v.pan).unwrap_or(0.0)
+    }
+
+    /// Set the voice's stereo pan, from `-1.0` (left) to `1.0` (right).
+    #[inline]
+    pub fn set_pan(&self, pan: f32) {
+        self.set(|v| v.pan = pan.clamp(-1.0, 1.0));
+    }
+
+    /// The voice's pitch, where `1.0` is the original speed.
+    #[inline]
+    pub fn pitch(&self) -> f32 {
+        self.get(|v| v.pitch).unwrap_or(1.0)
+    }
+
+    /// Set the voice's pitch, where `1.0` is the original speed.
+    #[inline]
+    pub fn set_pitch(&self, pitch: f32) {
+        self.set(|v| v.pitch = pitch.max(0.0));
+    }
+
+    /// If the voice restarts when it reaches the end.
+    #[inline]
+    pub fn looping(&self) -> bool {
+        self.get(|v| v.looping).unwrap_or(false)
+    }
+
+    /// Set whether the voice restarts when it reaches the end.
+    #[inline]
+    pub fn set_looping(&self, looping: bool) {
+        self.set(|v| v.looping = looping);
+    }
+
+    /// If the voice is paused.
+    #[inline]
+    pub fn paused(&self) -> bool {
+        self.get(|v| v.paused).unwrap_or(false)
+    }
+
+    /// Pause the voice.
+    #[inline]
+    pub fn pause(&self) {
+        self.set(|v| v.paused = true);
+    }
+
+    /// Resume the voice if it was paused.
+    #[inline]
+    pub fn resume(&self) {
+        self.set(|v| v.paused = false);
+    }
+
+    /// Stop the voice. It cannot be resumed afterwards.
+    #[inline]
+    pub fn stop(&self) {
+        self.mixer
+            .lock()
+            .unwrap()
+            .voices
+            .retain(|v| v.id != self.id);
+    }
+}
@@ -1,14 +1,11 @@
 use super::Game;
+use crate::core::frame_step::frame_step;
 use crate::core::frame_timer::FrameTimer;
-use crate::core::{Context, GameBuilder, Time, Window};
+use crate::core::window::image_to_icon;
+use crate::core::{Context, GameBuilder, GameError, Window};
 use crate::gfx::{Draw, Graphics};
-use crate::input::{Gamepads, Keyboard, Mouse};
-use crate::prelude::ContextData;
-use directories::ProjectDirs;
+use crate::math::{Degrees, Radians, vec2};
 use dpi::LogicalSize;
-use std::cell::Cell;
-use std::rc::Rc;
-use std::sync::Arc;
 use winit::application::ApplicationHandler;
 use winit::event::WindowEvent;
 use winit::event_loop::ActiveEventLoop;
@@ -21,16 +18,17 @@ enum AppState<G: Game> {
     },
     Running {
         ctx: Context,
-        draw: Draw,
+        draw: Box<Draw>,
         timer: FrameTimer,
         size: LogicalSize<f64>,
         game: G,
-        has_updated: bool,
     },
+    Stopped,
 }
 
 pub(crate) struct AppHandler<G: Game> {
     state: AppState<G>,
+    error: Option<GameError>,
 }
 
 impl<G: Game> AppHandler<G> {
@@ -40,73 +38,82 @@ impl<G: Game> AppHandler<G> {
                 opts,
                 cfg: Some(cfg),
             },
+            error: None,
         }
     }
+
+    /// The error that stopped the game, if any.
+    pub(crate) fn into_result(self) -> Result<(), GameError> {
+        match self.error {
+            Some(err) => Err(err),
+            None => Ok(()),
+        }
+    }
+
+    /// Stop the game because of an error, which will be returned once the event loop exits.
+    fn stop(&mut self, event_loop: &ActiveEventLoop, err: GameError) {
+        self.state = AppState::Stopped;
+        self.error = Some(err);
+        event_loop.exit();
+    }
 }
 
 impl<G: Game> ApplicationHandler for AppHandler<G> {
     fn resumed(&mut self, event_loop: &ActiveEventLoop) {
-        let AppState::Startup { opts, cfg } = &mut self.state else {
-            return;
+        let (opts, cfg) = match &mut self.state {
+            AppState::Startup { opts, cfg } => (opts, cfg),
+            AppState::Running { ctx, game, .. } => {
+                // the app was resumed after being suspended
+                let result = game.on_resume(ctx).or_else(|err| game.on_error(ctx, err));
+                if let Err(err) = result {
+                    self.stop(event_loop, err);
+                }
+                return;
+            }
+            AppState::Stopped => return,
         };
 
         // create the window
         let size = LogicalSize::new(opts.size.x as f64, opts.size.y as f64);
+        let icon = match opts.icon.as_ref().map(image_to_icon).transpose() {
+            Ok(icon) => icon,
+            Err(err) => return self.stop(event_loop, err.into()),
+        };
         let attrs = WindowAttributes::default()
             .with_title(&opts.title)
-            .with_inner_size(size);
-        let window = Window(Arc::new(
+            .with_inner_size(size)
+            .with_window_icon(icon);
+        let window = Window::new(
             event_loop
                 .create_window(attrs)
                 .expect("failed to create window"),
-        ));
+        );
 
         // initialize the graphics
         let graphics = Graphics::new(window.clone(), opts);
 
         // create the drawing context
-        let draw = Draw::new(
+        let draw = Box::new(Draw::new(
             graphics.device().clone(),
             graphics.queue().clone(),
             graphics.default_shader().clone(),
+            graphics.sdf_shader().clone(),
             graphics.default_texture().clone(),
-        );
-
-        // load the project directories
-        let app_name = if opts.app_name.is_empty() {
-            opts.title.as_str()
-        } else {
-            opts.app_name.as_str()
-        };
-        let dirs = ProjectDirs::from("", &opts.app_organization, app_name)
-            .expect("failed to locate system directories");
+            opts.depth_stencil,
+            graphics.window_sample_count(),
+        ));
 
         // create the game context
-        let ctx = Context(Rc::new(ContextData {
-            window,
-            time: Time::new(),
-            mouse: Mouse::new(),
-            keyboard: Keyboard::new(),
-            gamepads: Gamepads::new(),
-            graphics,
-
-            #[cfg(feature = "lua")]
-            lua: opts.lua.weak(),
-
-            #[cfg(feature = "lua")]
-            reload_lua: Cell::new(false),
-
-            quit_requested: Cell::new(false),
-
-            dirs,
-        }));
+        let ctx = Context::new(opts, window, graphics, false);
 
         // create the frame timer
         let timer = FrameTimer::new(ctx.time.0.clone());
 
         // create the game
-        // TODO: propagate error
-        let game = G::new(&ctx, cfg.take().unwrap()).unwrap();
+        let game = match G::new(&ctx, cfg.take().unwrap()) {
+            Ok(game) => game,
+            Err(err) => return self.stop(event_loop, err),
+        };
 
         // start running the app loop
         self.state = AppState::Running {
@@ -115,10 +122,19 @@ impl<G: Game> ApplicationHandler for AppHandler<G> {
             timer,
             size,
             game,
-            has_updated: false,
         };
     }
 
+    fn suspended(&mut self, event_loop: &ActiveEventLoop) {
+        let AppState::Running { ctx, game, .. } = &mut self.state else {
+            return;
+        };
+        let result = game.on_suspend(ctx).or_else(|err| game.on_error(ctx, err));
+        if let Err(err) = result {
+            self.stop(event_loop, err);
+        }
+    }
+
     fn window_event(
         &mut self,
         event_loop: &ActiveEventLoop,
@@ -131,39 +147,67 @@ impl<G: Game> ApplicationHandler for AppHandler<G> {
             timer,
             size,
             game,
-            has_updated,
         } = &mut self.state
         else {
-            panic!("app not running");
+            return;
         };
 
         // likely redundant, winit probably only calls this for windows created by this process
-        if ctx.window.0.id() != window_id {
+        let Some(window) = ctx.window.winit().filter(|w| w.id() == window_id).cloned() else {
             return;
         };
 
+        // ignore real input while a recording is being replayed
+        if ctx.recorder.is_replaying() && is_input_event(&event) {
+            return;
+        }
+
+        let mut result = Ok(());
         match event {
             WindowEvent::ActivationTokenDone { .. } => {}
             WindowEvent::Resized(new_size) => {
                 ctx.graphics.resized(new_size);
-                *size = new_size.to_logical::<f64>(ctx.window.0.scale_factor());
+                *size = new_size.to_logical::<f64>(window.scale_factor());
+                result = game
+                    .on_resized(ctx, ctx.window.size())
+                    .or_else(|err| game.on_error(ctx, err));
             }
             WindowEvent::Moved(_) => {}
             WindowEvent::CloseRequested => {
-                event_loop.exit();
+                // let the game decide whether to close
+                result = game
+                    .on_close_requested(ctx)
+                    .map(|close| {
+                        if close {
+                            event_loop.exit();
+                        }
+                    })
+                    .or_else(|err| game.on_error(ctx, err));
             }
             WindowEvent::Destroyed => {}
-            WindowEvent::DroppedFile(_) => {}
-            WindowEvent::HoveredFile(_) => {}
-            WindowEvent::HoveredFileCancelled => {}
-            WindowEvent::Focused(_) => {}
+            WindowEvent::DroppedFile(path) => {
+                ctx.file_drop.handle_drop(path, ctx.mouse.pos());
+            }
+            WindowEvent::HoveredFile(path) => {
+                ctx.file_drop.handle_hover(path, ctx.mouse.pos());
+            }
+            WindowEvent::HoveredFileCancelled => {
+                ctx.file_drop.handle_hover_cancelled();
+            }
+            WindowEvent::Focused(focused) => {
+                result = game
+                    .on_focus_changed(ctx, focused)
+                    .or_else(|err| game.on_error(ctx, err));
+            }
             WindowEvent::KeyboardInput { event, .. } => {
                 ctx.keyboard.handle_event(event);
             }
             WindowEvent::ModifiersChanged(_) => {}
-            WindowEvent::Ime(_) => {}
+            WindowEvent::Ime(ime) => {
+                ctx.keyboard.handle_ime(ime);
+            }
             WindowEvent::CursorMoved { position, .. } => {
-                let position = position.to_logical::<f32>(ctx.window.0.scale_factor());
+                let position = position.to_logical::<f32>(window.scale_factor());
                 ctx.mouse.handle_move(position);
             }
             WindowEvent::CursorEntered { .. } => {}
@@ -174,13 +218,31 @@ impl<G: Game> ApplicationHandler for AppHandler<G> {
             WindowEvent::MouseInput { state, button, .. } => {
                 ctx.mouse.handle_input(button, state);
             }
-            WindowEvent::PinchGesture { .. } => {}
-            WindowEvent::PanGesture { .. } => {}
-            WindowEvent::DoubleTapGesture { .. } => {}
-            WindowEvent::RotationGesture { .. } => {}
+            WindowEvent::PinchGesture { delta, .. } => {
+                ctx.touches.handle_pinch(delta as f32);
+            }
+            WindowEvent::PanGesture { delta, .. } => {
+                let delta = delta.to_logical::<f32>(window.scale_factor());
+                ctx.touches.handle_pan(vec2(delta.x, delta.y));
+            }
+            WindowEvent::DoubleTapGesture { .. } => {
+                ctx.touches.handle_double_tap();
+            }
+            WindowEvent::RotationGesture { delta, .. } => {
+                ctx.touches
+                    .handle_rotation(Radians::from_degrees(Degrees(delta)));
+            }
             WindowEvent::TouchpadPressure { .. } => {}
             WindowEvent::AxisMotion { .. } => {}
-            WindowEvent::Touch(_) => {}
+            WindowEvent::Touch(touch) => {
+                let pos = touch.location.to_logical::<f32>(window.scale_factor());
+                ctx.touches.handle_touch(
+                    touch.id,
+                    touch.phase,
+                    vec2(pos.x, pos.y),
+                    touch.force.map(|force| force.normalized() as f32),
+                );
+            }
             WindowEvent::ScaleFactorChanged {
                 scale_factor,
                 mut inner_size_writer,
@@ -192,53 +254,17 @@ impl<G: Game> ApplicationHandler for AppHandler<G> {
                     .expect("failed to update window size");
             }
             WindowEvent::ThemeChanged(_) => {}
-            WindowEvent::Occluded(_) => {}
+            WindowEvent::Occluded(occluded) => {
+                result = game
+                    .on_occluded(ctx, occluded)
+                    .or_else(|err| game.on_error(ctx, err));
+            }
             WindowEvent::RedrawRequested => {
-                let monitor = ctx.window.monitor();
-
-                timer.tick(monitor, || {
-                    *has_updated = true;
+                // update and render a frame
+                result = frame_step(ctx, draw, game, timer);
 
-                    // update gamepad input
-                    ctx.gamepads.update(ctx);
-
-                    // update the game
-                    // TODO: propagate this error somewhere
-                    game.update(ctx).unwrap();
-
-                    // clear input on-frame events (eg. pressed, released)
-                    ctx.mouse.clear_phase();
-                    ctx.keyboard.clear_phase();
-                    ctx.gamepads.clear_phase();
-                });
-
-                // switch to the render phase for input
-                ctx.mouse.set_render_phase();
-                ctx.keyboard.set_render_phase();
-                ctx.gamepads.set_render_phase();
-
-                // begin rendering a frame
-                draw.begin_frame(ctx.window.size());
-
-                // only do render callbacks after we've started updating
-                if *has_updated {
-                    // render the game
-                    // TODO: propagate this error somewhere
-                    game.render(ctx, draw).unwrap();
-                }
-
-                // finish rendering a frame
-                draw.end_frame(timer.time.frame.get(), ctx.graphics.surface(), &ctx.window);
-
-                // clear input on-frame events (eg. pressed, released)
-                ctx.mouse.clear_phase();
-                ctx.keyboard.clear_phase();
-                ctx.gamepads.clear_phase();
-
-                // switch back to the update phase for input
-                ctx.mouse.set_update_phase();
-                ctx.keyboard.set_update_phase();
-                ctx.gamepads.set_update_phase();
+                // create the custom cursor if the game set one
+                ctx.window.apply_custom_cursor(event_loop);
 
                 // quit if the user requested it
                 if ctx.quit_requested() {
@@ -246,5 +272,23 @@ impl<G: Game> ApplicationHandler for AppHandler<G> {
                 }
             }
         }
+
+        // stop the game if an error went unhandled
+        if let Err(err) = result {
+            self.stop(event_loop, err);
+        }
     }
 }
+
+/// If the event is keyboard, mouse, or touch input.
+fn is_input_event(event: &WindowEvent) -> bool {
+    matches!(
+        event,
+        WindowEvent::KeyboardInput { .. }
+            | WindowEvent::Ime(_)
+            | WindowEvent::CursorMoved { .. }
+            | WindowEvent::MouseWheel { .. }
+            | WindowEvent::MouseInput { .. }
+            | WindowEvent::Touch(_)
+    )
+}
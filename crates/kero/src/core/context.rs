@@ -1,7 +1,9 @@
-use super::Time;
-use crate::core::Window;
+use super::{Storage, Time};
+use crate::audio::Audio;
+use crate::core::{GameBuilder, Window};
 use crate::gfx::Graphics;
-use crate::input::{Gamepads, Keyboard, Mouse};
+use crate::input::{FileDrop, Gamepads, InputRecorder, Keyboard, Mouse, Touches, VirtualButtons};
+use crate::tween::Timeline;
 use directories::ProjectDirs;
 use std::cell::Cell;
 use std::fmt::{Debug, Formatter};
@@ -20,10 +22,17 @@ pub struct Context(pub(crate) Rc<ContextData>);
 pub struct ContextData {
     pub window: Window,
     pub time: Time,
+    pub timeline: Timeline,
     pub mouse: Mouse,
+    pub touches: Touches,
     pub keyboard: Keyboard,
     pub gamepads: Gamepads,
+    pub file_drop: FileDrop,
+    pub recorder: InputRecorder,
+    pub(crate) virtual_buttons: VirtualButtons,
     pub graphics: Graphics,
+    pub audio: Audio,
+    pub storage: Storage,
 
     #[cfg(feature = "lua")]
     pub lua: mlua::WeakLua,
@@ -52,6 +61,71 @@ impl Debug for Context {
 }
 
 impl Context {
+    pub(crate) fn new(
+        opts: &GameBuilder,
+        window: Window,
+        graphics: Graphics,
+        headless: bool,
+    ) -> Self {
+        // load the project directories
+        let app_name = if opts.app_name.is_empty() {
+            opts.title.as_str()
+        } else {
+            opts.app_name.as_str()
+        };
+        let dirs = ProjectDirs::from("", &opts.app_organization, app_name)
+            .expect("failed to locate system directories");
+
+        let gamepads = if headless {
+            Gamepads::new_headless()
+        } else {
+            Gamepads::new()
+        };
+
+        let mouse = Mouse::new();
+        let touches = Touches::new(&mouse);
+        let keyboard = Keyboard::new();
+
+        // start recording or replaying input from the first update
+        let recorder = InputRecorder::new(
+            keyboard.clone(),
+            mouse.clone(),
+            touches.clone(),
+            gamepads.clone(),
+        );
+        if let Some(recording) = &opts.input_replay {
+            recorder.start_replay(recording.clone());
+        } else if opts.record_input {
+            recorder.start_recording(recorder.seed());
+        }
+
+        Self(Rc::new(ContextData {
+            window,
+            time: Time::new(opts.timestep),
+            timeline: Timeline::new(),
+            mouse,
+            touches,
+            keyboard,
+            gamepads,
+            file_drop: FileDrop::new(),
+            recorder,
+            virtual_buttons: VirtualButtons::default(),
+            graphics,
+            audio: Audio::new(headless || opts.null_audio),
+            storage: Storage::new(&dirs),
+
+            #[cfg(feature = "lua")]
+            lua: opts.lua.weak(),
+
+            #[cfg(feature = "lua")]
+            reload_lua: Cell::new(false),
+
+            quit_requested: Cell::new(false),
+
+            dirs,
+        }))
+    }
+
     #[cfg(feature = "lua")]
     pub fn from_lua(lua: &mlua::Lua) -> mlua::AppDataRef<'_, Self> {
         lua.app_data_ref::<Self>().unwrap()
@@ -0,0 +1,96 @@
+use crate::core::frame_timer::FrameTimer;
+use crate::core::{Context, Game, GameError};
+use crate::gfx::Draw;
+
+/// Run the updates for a single frame and then render it. This is shared by the windowed app
+/// loop and the headless runner, so both run the game in exactly the same way.
+///
+/// A windowed game's updates are timed by the wall clock, while a headless game always advances
+/// by exactly one frame. The game is only rendered once it has been updated at least once.
+pub(crate) fn frame_step<G: Game>(
+    ctx: &Context,
+    draw: &mut Draw,
+    game: &mut G,
+    timer: &mut FrameTimer,
+) -> Result<(), GameError> {
+    let mut result = Ok(());
+    let update_fn = || {
+        // stop updating if an error occurred
+        if result.is_err() {
+            return;
+        }
+
+        // update gamepad input, unless it's being replayed
+        if !ctx.recorder.is_replaying() {
+            ctx.gamepads.update(ctx);
+        }
+
+        // record or replay this update's input
+        ctx.recorder.update(ctx);
+
+        // buffer virtual button presses and releases
+        ctx.virtual_buttons.update();
+
+        // advance timers and tweens, then update the game
+        result = ctx
+            .timeline
+            .update(ctx)
+            .and_then(|_| game.update(ctx))
+            .or_else(|err| game.on_error(ctx, err));
+
+        // play any rumble effects started by the update
+        ctx.gamepads.update_rumble(ctx.time.delta());
+
+        // clear input on-frame events (eg. pressed, released)
+        clear_input_phase(ctx);
+    };
+    if ctx.window.is_headless() {
+        timer.step(update_fn);
+    } else {
+        timer.tick(ctx.window.monitor(), update_fn);
+    }
+
+    // switch to the render phase for input
+    ctx.mouse.set_render_phase();
+    ctx.touches.set_render_phase();
+    ctx.keyboard.set_render_phase();
+    ctx.gamepads.set_render_phase();
+    ctx.file_drop.set_render_phase();
+
+    // begin rendering a frame
+    draw.begin_frame(ctx.window.size());
+
+    // only do render callbacks after we've started updating
+    if timer.has_updated && result.is_ok() {
+        // render the game
+        result = game
+            .render(ctx, draw)
+            .or_else(|err| game.on_error(ctx, err));
+    }
+
+    // finish rendering a frame
+    let frame = ctx.graphics.acquire_frame();
+    draw.end_frame(ctx.time.frame(), frame.texture());
+    ctx.graphics.present_frame(frame);
+
+    // clear input on-frame events (eg. pressed, released)
+    clear_input_phase(ctx);
+
+    // switch back to the update phase for input
+    ctx.mouse.set_update_phase();
+    ctx.touches.set_update_phase();
+    ctx.keyboard.set_update_phase();
+    ctx.gamepads.set_update_phase();
+    ctx.file_drop.set_update_phase();
+
+    result
+}
+
+/// Clear the on-frame events (eg. pressed, released) of every input device.
+fn clear_input_phase(ctx: &Context) {
+    ctx.mouse.clear_phase();
+    ctx.touches.clear_phase();
+    ctx.keyboard.clear_phase();
+    ctx.gamepads.clear_phase();
+    ctx.file_drop.clear_phase();
+}
@@ -1,4 +1,4 @@
-use crate::core::{Monitor, TimeState};
+use crate::core::{Monitor, TimeState, Timestep};
 use std::mem::replace;
 use std::rc::Rc;
 use std::time::{Duration, Instant};
@@ -15,6 +15,7 @@ pub(crate) struct FrameTimer {
     pub prev_frame: Option<Instant>,
     pub snapshots: Vec<f64>,
     pub last_unfixed: Option<Instant>,
+    pub has_updated: bool,
 }
 
 impl FrameTimer {
@@ -26,10 +27,76 @@ impl FrameTimer {
             prev_frame: None,
             snapshots: Vec::new(),
             last_unfixed: None,
+            has_updated: false,
+        }
+    }
+
+    /// Advance by exactly one frame without looking at the wall clock, so that simulated
+    /// runs are deterministic. The frame lasts exactly as long as one step at the target FPS.
+    pub fn step<F: FnMut()>(&mut self, mut update_fn: F) {
+        let target_fps = self.time.target_fps.get().unwrap_or(60.0);
+        let frame_time = 1.0 / target_fps;
+        self.time.unfixed_delta.set(frame_time as f32);
+        self.time.fps.set(target_fps.round() as u32);
+        match self.time.timestep.get() {
+            Timestep::Fixed => {
+                self.time.alpha.set(0.0);
+                self.update(frame_time, &mut update_fn);
+            }
+            Timestep::Variable => {
+                self.time.alpha.set(1.0);
+                self.update(frame_time, &mut update_fn);
+            }
+            Timestep::SemiFixed { max_delta } => {
+                self.time.alpha.set(1.0);
+                self.semi_fixed(frame_time, max_delta as f64, &mut update_fn);
+            }
         }
     }
 
     pub fn tick<F: FnMut()>(&mut self, display: Option<Monitor>, mut update_fn: F) {
+        // check how much time has passed since the last render
+        let curr_time = Instant::now();
+        let mut delta = (curr_time - self.prev_time.unwrap_or(curr_time)).as_secs_f64();
+        self.prev_time = Some(curr_time);
+
+        // if the delta is close to a nice framerate, snap it
+        for fps in COMMON_FRAMERATES {
+            delta = ((delta - 1.0 / fps).abs() < SNAP_THRESHOLD)
+                .then(|| 1.0 / fps)
+                .unwrap_or(delta)
+        }
+
+        match self.time.timestep.get() {
+            Timestep::Fixed => self.fixed(display, delta, &mut update_fn),
+            Timestep::Variable => {
+                self.time.alpha.set(1.0);
+                self.update(delta, &mut update_fn);
+            }
+            Timestep::SemiFixed { max_delta } => {
+                self.time.alpha.set(1.0);
+                self.semi_fixed(delta, max_delta as f64, &mut update_fn);
+            }
+        }
+
+        // track the FPS over the last 60 frames
+        let curr_frame = Instant::now();
+        let frame_diff = (curr_frame - self.prev_frame.unwrap_or(curr_frame)).as_secs_f64();
+        self.prev_frame = Some(curr_frame);
+        self.snapshots.push(frame_diff);
+        if self.snapshots.len() > 60 {
+            self.snapshots.rotate_left(1);
+            self.snapshots.pop();
+        }
+        self.time.unfixed_delta.set(frame_diff as f32);
+        self.time.fps.set(
+            (1.0 / (self.snapshots.iter().sum::<f64>() / (self.snapshots.len() as f64))).round()
+                as u32,
+        );
+    }
+
+    /// Run updates with a fixed delta, accumulating time so we know when to trigger them.
+    fn fixed<F: FnMut()>(&mut self, display: Option<Monitor>, delta: f64, update_fn: &mut F) {
         let refresh_rate = display
             .and_then(|monitor| monitor.refresh_rate_mhz())
             .unwrap_or(60000);
@@ -45,18 +112,6 @@ impl FrameTimer {
         let frame_duration = Duration::from_secs_f64(1.0 / target_fps);
         self.time.delta.set(frame_duration.as_secs_f32());
 
-        // check how much time has passed since the last render
-        let curr_time = Instant::now();
-        let mut delta = (curr_time - self.prev_time.unwrap_or(curr_time)).as_secs_f64();
-        self.prev_time = Some(curr_time);
-
-        // if the delta is close to a nice framerate, snap it
-        for fps in COMMON_FRAMERATES {
-            delta = ((delta - 1.0 / fps).abs() < SNAP_THRESHOLD)
-                .then(|| 1.0 / fps)
-                .unwrap_or(delta)
-        }
-
         // accumulate time so we know when to trigger a frame
         self.accum = self.accum + Duration::from_secs_f64(delta); //.min(max_duration);
 
@@ -89,19 +144,66 @@ impl FrameTimer {
             }
         }
 
-        // track the FPS over the last 60 frames
-        let curr_frame = Instant::now();
-        let frame_diff = (curr_frame - self.prev_frame.unwrap_or(curr_frame)).as_secs_f64();
-        self.prev_frame = Some(curr_frame);
-        self.snapshots.push(frame_diff);
-        if self.snapshots.len() > 60 {
-            self.snapshots.rotate_left(1);
-            self.snapshots.pop();
+        // the leftover time is how far we are between this update and the next
+        self.time
+            .alpha
+            .set((self.accum.as_secs_f64() / frame_duration.as_secs_f64()) as f32);
+    }
+
+    /// Split the delta into updates no longer than `max_delta`, dropping any time left over
+    /// once the maximum number of updates for a frame have run.
+    fn semi_fixed<F: FnMut()>(&mut self, mut delta: f64, max_delta: f64, update_fn: &mut F) {
+        let mut max_frames = self.time.max_frame_skip.get() + 1;
+        while delta > 0.0 && max_frames > 0 {
+            max_frames -= 1;
+            // an invalid max delta would never use up the delta, so update with all of it
+            let step = if max_delta > 0.0 {
+                delta.min(max_delta)
+            } else {
+                delta
+            };
+            delta -= step;
+            self.update(step, update_fn);
         }
-        self.time.unfixed_delta.set(frame_diff as f32);
-        self.time.fps.set(
-            (1.0 / (self.snapshots.iter().sum::<f64>() / (self.snapshots.len() as f64))).round()
-                as u32,
-        );
+    }
+
+    /// Run a single update with the provided delta.
+    fn update<F: FnMut()>(&mut self, delta: f64, update_fn: &mut F) {
+        self.time.delta.set(delta as f32);
+        self.time.since_startup.update(|t| t + delta as f32);
+        self.time.frame.update(|f| f + 1);
+        self.has_updated = true;
+        update_fn();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn semi_fixed_steps() {
+        let time = Rc::new(TimeState::default());
+        time.max_frame_skip.set(3);
+        let mut timer = FrameTimer::new(time.clone());
+
+        let mut deltas = Vec::new();
+        timer.semi_fixed(0.05, 0.02, &mut || deltas.push(time.delta.get()));
+        assert_eq!(deltas.len(), 3);
+        assert_eq!(deltas[0], 0.02);
+        assert!((deltas[2] - 0.01).abs() < 1e-6);
+
+        // time beyond the maximum number of updates is dropped
+        deltas.clear();
+        timer.semi_fixed(1.0, 0.1, &mut || deltas.push(time.delta.get()));
+        assert_eq!(deltas, [0.1; 4]);
+        assert_eq!(time.frame.get(), 7);
+
+        // an invalid max delta runs a single update
+        deltas.clear();
+        timer.semi_fixed(0.05, 0.0, &mut || deltas.push(time.delta.get()));
+        assert_eq!(deltas, [0.05]);
+        assert_eq!(Timestep::semi_fixed(0.0), None);
+        assert_eq!(Timestep::semi_fixed(f32::NAN), None);
     }
 }
@@ -1,5 +1,6 @@
 use crate::core::{Context, GameError};
 use crate::gfx::Draw;
+use crate::math::Vec2U;
 
 /// Represents a game that can be passed to [`new_game()`](crate::new_game).
 pub trait Game: 'static {
@@ -16,6 +17,58 @@ pub trait Game: 'static {
 
     /// Called every frame refresh in order to perform game rendering.
     fn render(&mut self, ctx: &Context, draw: &mut Draw) -> Result<(), GameError>;
+
+    /// Called when the window gains or loses focus, for example to pause the game when the
+    /// player switches to another window.
+    #[inline]
+    fn on_focus_changed(&mut self, _ctx: &Context, _focused: bool) -> Result<(), GameError> {
+        Ok(())
+    }
+
+    /// Called when the window is resized, with its new DPI-independent size.
+    #[inline]
+    fn on_resized(&mut self, _ctx: &Context, _size: Vec2U) -> Result<(), GameError> {
+        Ok(())
+    }
+
+    /// Called when the window becomes hidden from view (eg. it was minimized or is completely
+    /// covered by other windows) or becomes visible again.
+    #[inline]
+    fn on_occluded(&mut self, _ctx: &Context, _occluded: bool) -> Result<(), GameError> {
+        Ok(())
+    }
+
+    /// Called when the user tries to close the window. Return `false` to keep the game running,
+    /// for example to ask the player to save first. The game can then quit later with
+    /// [`Context::quit`].
+    #[inline]
+    fn on_close_requested(&mut self, _ctx: &Context) -> Result<bool, GameError> {
+        Ok(true)
+    }
+
+    /// Called when the app is suspended by the operating system, such as when it is sent to
+    /// the background on mobile platforms.
+    #[inline]
+    fn on_suspend(&mut self, _ctx: &Context) -> Result<(), GameError> {
+        Ok(())
+    }
+
+    /// Called when the app is resumed after being suspended.
+    #[inline]
+    fn on_resume(&mut self, _ctx: &Context) -> Result<(), GameError> {
+        Ok(())
+    }
+
+    /// Called when [update()](Game::update), [render()](Game::render) or any of the event
+    /// callbacks returns an error.
+    ///
+    /// By default the error is returned, which stops the game and returns the error from
+    /// [`GameBuilder::run`](super::GameBuilder::run). Return `Ok(())` instead to keep the game
+    /// running, for example after switching to an error screen.
+    #[inline]
+    fn on_error(&mut self, _ctx: &Context, err: GameError) -> Result<(), GameError> {
+        Err(err)
+    }
 }
 
 impl Game for () {
@@ -1,5 +1,7 @@
 use crate::core::app_handler::AppHandler;
-use crate::core::{Game, GameError};
+use crate::core::{Game, GameError, HeadlessRunner, Timestep};
+use crate::img::ImageRgba8;
+use crate::input::InputRecording;
 use crate::math::Vec2U;
 use winit::event_loop::EventLoop;
 
@@ -7,10 +9,21 @@ use winit::event_loop::EventLoop;
 pub struct GameBuilder {
     pub title: String,
     pub size: Vec2U,
+    pub icon: Option<ImageRgba8>,
 
     pub app_organization: String,
     pub app_name: String,
 
+    pub null_audio: bool,
+
+    pub timestep: Timestep,
+
+    pub depth_stencil: bool,
+    pub msaa: u32,
+
+    pub record_input: bool,
+    pub input_replay: Option<InputRecording>,
+
     #[cfg(feature = "lua")]
     pub lua: mlua::Lua,
 }
@@ -20,10 +33,21 @@ impl GameBuilder {
         let this = Self {
             title: "New Game".to_string(),
             size: (1280, 720).into(),
+            icon: None,
 
             app_organization: String::new(),
             app_name: String::new(),
 
+            null_audio: false,
+
+            timestep: Timestep::Fixed,
+
+            depth_stencil: false,
+            msaa: 1,
+
+            record_input: false,
+            input_replay: None,
+
             #[cfg(feature = "lua")]
             lua: {
                 let lua = mlua::Lua::new();
@@ -43,28 +67,39 @@ impl GameBuilder {
                 .with_modules::<fey_math::MathModules>()?
                 .with_module::<fey_rand::RandModule>()?
                 .with_module::<AppModule>()?
+                .with_module::<AudioModule>()?
                 .with_module::<ColorModeModule>()?
                 .with_module::<DrawModule>()?
+                .with_module::<FileDropModule>()?
                 .with_module::<FontModule>()?
                 .with_module::<IndexBufferModule>()?
                 .with_module::<GamepadModule>()?
                 .with_module::<GamepadButtonModule>()?
                 .with_module::<GamepadAxisModule>()?
+                .with_module::<GlyphCacheModule>()?
                 .with_module::<KeyModule>()?
                 .with_module::<KeyboardModule>()?
                 .with_module::<MonitorModule>()?
                 .with_module::<MouseModule>()?
                 .with_module::<MouseButtonModule>()?
+                .with_module::<MusicModule>()?
                 .with_module::<SamplerModule>()?
+                .with_module::<SceneStackModule>()?
                 .with_module::<ScreenModule>()?
                 .with_module::<ShaderModule>()?
+                .with_module::<SoundModule>()?
+                .with_module::<StorageModule>()?
                 .with_module::<SubTextureModule>()?
                 .with_module::<SurfaceModule>()?
                 .with_module::<TextureModule>()?
                 .with_module::<TimeModule>()?
+                .with_module::<TimelineModule>()?
+                .with_module::<TouchesModule>()?
                 .with_module::<VertexBufferModule>()?
                 .with_module::<VertexModule>()?
                 .with_module::<VideoModeModule>()?
+                .with_module::<VirtualAxisModule>()?
+                .with_module::<VirtualStickModule>()?
                 .with_module::<WindowModule>()?
         };
 
@@ -95,6 +130,14 @@ impl GameBuilder {
         }
     }
 
+    /// Set the icon of the game window.
+    pub fn with_icon(self, icon: ImageRgba8) -> Self {
+        Self {
+            icon: Some(icon),
+            ..self
+        }
+    }
+
     /// Set the app information used to determine system directories.
     pub fn with_app_info(self, organization: &str, name: &str) -> Self {
         Self {
@@ -104,6 +147,54 @@ impl GameBuilder {
         }
     }
 
+    /// Use a null audio output instead of opening the default audio device.
+    pub fn with_null_audio(self) -> Self {
+        Self {
+            null_audio: true,
+            ..self
+        }
+    }
+
+    /// Set how the game loop advances time between updates.
+    pub fn with_timestep(self, timestep: Timestep) -> Self {
+        Self { timestep, ..self }
+    }
+
+    /// Give the window a depth-stencil buffer, which allows masking and depth testing when
+    /// drawing to it.
+    pub fn with_depth_stencil(self) -> Self {
+        Self {
+            depth_stencil: true,
+            ..self
+        }
+    }
+
+    /// Multisample drawing to the window, which smooths the edges of shapes. If `samples` isn't
+    /// supported by the graphics device, the highest supported count below it is used instead.
+    pub fn with_msaa(self, samples: u32) -> Self {
+        Self {
+            msaa: samples,
+            ..self
+        }
+    }
+
+    /// Record input from the first update. The recording can be retrieved with
+    /// [`InputRecorder::stop_recording`](crate::input::InputRecorder::stop_recording).
+    pub fn with_input_recording(self) -> Self {
+        Self {
+            record_input: true,
+            ..self
+        }
+    }
+
+    /// Replay a recording from the first update instead of using real input.
+    pub fn with_input_replay(self, recording: InputRecording) -> Self {
+        Self {
+            input_replay: Some(recording),
+            ..self
+        }
+    }
+
     #[cfg(feature = "lua")]
     pub fn with_module<M: crate::lua::LuaModule>(self) -> Result<Self, GameError> {
         let module = M::load(&self.lua)?;
@@ -126,8 +217,23 @@ impl GameBuilder {
     /// Run your game.
     pub fn run<G: Game>(self, cfg: G::Config) -> Result<(), GameError> {
         let event_loop = EventLoop::new()?;
-        event_loop.run_app(&mut AppHandler::<G>::new(self, cfg))?;
-        Ok(())
+        let mut app = AppHandler::<G>::new(self, cfg);
+        event_loop.run_app(&mut app)?;
+        app.into_result()
+    }
+
+    /// Create a runner that steps your game one frame at a time without opening a window, with
+    /// simulated time and scripted input. Useful for automated tests.
+    pub fn build_headless<G: Game>(self, cfg: G::Config) -> Result<HeadlessRunner<G>, GameError> {
+        HeadlessRunner::new(self, cfg)
+    }
+
+    /// Run your game without opening a window for the provided number of frames (or until it
+    /// requests to quit), then return it.
+    pub fn run_headless<G: Game>(self, cfg: G::Config, frames: u64) -> Result<G, GameError> {
+        let mut runner = self.build_headless::<G>(cfg)?;
+        runner.run(frames)?;
+        Ok(runner.into_game())
     }
 
     #[cfg(feature = "lua")]
@@ -1,6 +1,12 @@
-use crate::gfx::{DrawError, IndexBufferUploadError, VertexBufferUploadError};
+use crate::audio::AudioError;
+use crate::core::{StorageError, WindowError};
+use crate::gfx::{
+    DrawError, IndexBufferUploadError, TextureDownloadError, TextureUploadError,
+    VertexBufferUploadError,
+};
 use crate::guid::GuidParseError;
 use crate::img::ImageError;
+use crate::input::InputRecordingError;
 use std::error::Error;
 use winit::error::EventLoopError;
 
@@ -13,6 +19,9 @@ pub enum GameError {
     #[error("{0}")]
     EventLoop(#[from] EventLoopError),
 
+    #[error("{0}")]
+    Window(#[from] WindowError),
+
     #[error("{0}")]
     Gamepad(#[from] gilrs::Error),
 
@@ -22,6 +31,12 @@ pub enum GameError {
     #[error("{0}")]
     VertexBufferUpload(#[from] VertexBufferUploadError),
 
+    #[error("{0}")]
+    TextureUpload(#[from] TextureUploadError),
+
+    #[error("{0}")]
+    TextureDownload(#[from] TextureDownloadError),
+
     #[error("{0}")]
     Draw(#[from] DrawError),
 
@@ -31,18 +46,24 @@ pub enum GameError {
     #[error("{0}")]
     Image(#[from] ImageError),
 
+    #[error("{0}")]
+    InputRecording(#[from] InputRecordingError),
+
+    #[error("{0}")]
+    Storage(#[from] StorageError),
+
     #[error("{0}")]
     Io(#[from] std::io::Error),
 
     #[error("{0}")]
     Font(#[from] fey_font::FontError),
 
+    #[error("{0}")]
+    Audio(#[from] AudioError),
+
     #[cfg(feature = "lua")]
     #[error("{0}")]
     Lua(#[from] mlua::prelude::LuaError),
-    //
-    // #[error("{0}")]
-    // TextureUpload(#[from] TextureUploadError),
 }
 
 impl GameError {
@@ -0,0 +1,419 @@
+use crate::core::frame_step::frame_step;
+use crate::core::frame_timer::FrameTimer;
+use crate::core::{Context, Game, GameBuilder, GameError, Window};
+use crate::gfx::{Draw, Graphics};
+use crate::input::{Gamepad, GamepadAxis, GamepadButton, Key, MouseButton};
+use crate::math::{RadiansF, Vec2F};
+use dpi::LogicalPosition;
+use std::fmt::{Debug, Formatter};
+use std::path::PathBuf;
+use winit::event::{MouseScrollDelta, TouchPhase};
+
+/// Runs a game without a window, one frame at a time.
+///
+/// Time is simulated at a fixed rate (the target FPS, or 60 if none is set) and input comes
+/// only from the runner's scripting methods, so runs are deterministic and can be used for
+/// automated tests. Input scripted between steps is visible to the next frame's update and
+/// render, just like real input would be.
+///
+/// Frames are rendered to an offscreen texture (see
+/// [`Graphics::headless_target`](crate::gfx::Graphics::headless_target)). If no graphics
+/// device is available, a no-op device is used instead, so draw calls are still generated but
+/// nothing is actually rendered.
+///
+/// Created with [`GameBuilder::build_headless`].
+pub struct HeadlessRunner<G: Game> {
+    ctx: Context,
+    draw: Draw,
+    timer: FrameTimer,
+    game: G,
+}
+
+impl<G: Game> Debug for HeadlessRunner<G> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("HeadlessRunner").finish_non_exhaustive()
+    }
+}
+
+impl<G: Game> HeadlessRunner<G> {
+    pub(crate) fn new(opts: GameBuilder, cfg: G::Config) -> Result<Self, GameError> {
+        // create a simulated window and render to an offscreen target
+        let window = Window::new_headless(&opts.title, opts.size);
+        let graphics = Graphics::new_headless(window.clone(), &opts);
+
+        // create the drawing context
+        let draw = Draw::new(
+            graphics.device().clone(),
+            graphics.queue().clone(),
+            graphics.default_shader().clone(),
+            graphics.sdf_shader().clone(),
+            graphics.default_texture().clone(),
+            opts.depth_stencil,
+            graphics.window_sample_count(),
+        );
+
+        // create the game context
+        let ctx = Context::new(&opts, window, graphics, true);
+
+        // create the frame timer
+        let timer = FrameTimer::new(ctx.time.0.clone());
+
+        // create the game
+        let game = G::new(&ctx, cfg)?;
+
+        Ok(Self {
+            ctx,
+            draw,
+            timer,
+            game,
+        })
+    }
+
+    /// The game's context.
+    #[inline]
+    pub fn ctx(&self) -> &Context {
+        &self.ctx
+    }
+
+    /// The game being run.
+    #[inline]
+    pub fn game(&self) -> &G {
+        &self.game
+    }
+
+    /// The game being run.
+    #[inline]
+    pub fn game_mut(&mut self) -> &mut G {
+        &mut self.game
+    }
+
+    /// Stop running and return the game.
+    #[inline]
+    pub fn into_game(self) -> G {
+        self.game
+    }
+
+    /// Update and render a single frame.
+    pub fn step(&mut self) -> Result<(), GameError> {
+        frame_step(&self.ctx, &mut self.draw, &mut self.game, &mut self.timer)
+    }
+
+    /// Step through the provided number of frames, stopping early if the game requests to quit.
+    /// Returns how many frames were run.
+    pub fn run(&mut self, frames: u64) -> Result<u64, GameError> {
+        for i in 0..frames {
+            if self.ctx.quit_requested() {
+                return Ok(i);
+            }
+            self.step()?;
+        }
+        Ok(frames)
+    }
+
+    /// Press a key.
+    #[inline]
+    pub fn press_key(&self, key: Key) {
+        self.ctx.keyboard.handle_key(key, true, false);
+    }
+
+    /// Trigger a key repeat, as if the key were being held.
+    #[inline]
+    pub fn repeat_key(&self, key: Key) {
+        self.ctx.keyboard.handle_key(key, true, true);
+    }
+
+    /// Release a key.
+    #[inline]
+    pub fn release_key(&self, key: Key) {
+        self.ctx.keyboard.handle_key(key, false, false);
+    }
+
+    /// Input text, as if it were typed.
+    #[inline]
+    pub fn input_text(&self, text: &str) {
+        self.ctx.keyboard.handle_text(text);
+    }
+
+    /// Set the text being composed by the IME, with an optional cursor byte range.
+    #[inline]
+    pub fn ime_preedit(&self, text: &str, cursor: Option<(usize, usize)>) {
+        self.ctx.keyboard.handle_ime_enabled(true);
+        self.ctx.keyboard.handle_preedit(text, cursor);
+    }
+
+    /// Commit text from the IME, ending the current composition.
+    #[inline]
+    pub fn ime_commit(&self, text: &str) {
+        self.ctx.keyboard.handle_commit(text);
+    }
+
+    /// Move the mouse to a position in the window.
+    #[inline]
+    pub fn move_mouse(&self, pos: impl Into<Vec2F>) {
+        let pos = pos.into();
+        self.ctx
+            .mouse
+            .handle_move(LogicalPosition::new(pos.x, pos.y));
+    }
+
+    /// Press a mouse button.
+    #[inline]
+    pub fn press_mouse(&self, button: MouseButton) {
+        self.ctx.mouse.handle_button(button, true);
+    }
+
+    /// Release a mouse button.
+    #[inline]
+    pub fn release_mouse(&self, button: MouseButton) {
+        self.ctx.mouse.handle_button(button, false);
+    }
+
+    /// Scroll the mouse wheel by a number of lines.
+    #[inline]
+    pub fn scroll_mouse(&self, lines: impl Into<Vec2F>) {
+        let lines = lines.into();
+        self.ctx
+            .mouse
+            .handle_scroll(MouseScrollDelta::LineDelta(lines.x, lines.y));
+    }
+
+    /// Touch the window with a finger.
+    #[inline]
+    pub fn press_touch(&self, id: u64, pos: impl Into<Vec2F>) {
+        self.touch(id, TouchPhase::Started, pos.into());
+    }
+
+    /// Move a finger that is touching the window.
+    #[inline]
+    pub fn move_touch(&self, id: u64, pos: impl Into<Vec2F>) {
+        self.touch(id, TouchPhase::Moved, pos.into());
+    }
+
+    /// Lift a finger from the window.
+    #[inline]
+    pub fn release_touch(&self, id: u64) {
+        if let Some(touch) = self.ctx.touches.get(id) {
+            self.touch(id, TouchPhase::Ended, touch.pos);
+        }
+    }
+
+    #[inline]
+    fn touch(&self, id: u64, phase: TouchPhase, pos: Vec2F) {
+        self.ctx.touches.handle_touch(id, phase, pos, None);
+    }
+
+    /// Pinch the touchpad or screen. Positive values zoom in, negative values zoom out.
+    #[inline]
+    pub fn pinch(&self, delta: f32) {
+        self.ctx.touches.handle_pinch(delta);
+    }
+
+    /// Pan with two fingers.
+    #[inline]
+    pub fn pan(&self, delta: impl Into<Vec2F>) {
+        self.ctx.touches.handle_pan(delta.into());
+    }
+
+    /// Rotate with two fingers. Positive values are counter-clockwise.
+    #[inline]
+    pub fn rotate(&self, delta: RadiansF) {
+        self.ctx.touches.handle_rotation(delta);
+    }
+
+    /// Double-tap the touchpad or screen.
+    #[inline]
+    pub fn double_tap(&self) {
+        self.ctx.touches.handle_double_tap();
+    }
+
+    /// Start dragging a file over the window at the mouse position.
+    #[inline]
+    pub fn hover_file(&self, path: impl Into<PathBuf>) {
+        self.ctx
+            .file_drop
+            .handle_hover(path.into(), self.ctx.mouse.pos());
+    }
+
+    /// Drop a file onto the window at the mouse position.
+    #[inline]
+    pub fn drop_file(&self, path: impl Into<PathBuf>) {
+        self.ctx
+            .file_drop
+            .handle_drop(path.into(), self.ctx.mouse.pos());
+    }
+
+    /// Drag the hovering files away from the window without dropping them.
+    #[inline]
+    pub fn cancel_file_hover(&self) {
+        self.ctx.file_drop.handle_hover_cancelled();
+    }
+
+    /// Connect a simulated gamepad.
+    #[inline]
+    pub fn connect_gamepad(&self, name: &str) -> Gamepad {
+        self.ctx.gamepads.connect_virtual(&self.ctx, name)
+    }
+
+    /// Disconnect a simulated gamepad.
+    #[inline]
+    pub fn disconnect_gamepad(&self, gamepad: &Gamepad) {
+        self.ctx.gamepads.disconnect_virtual(gamepad);
+    }
+
+    /// Press a button on a simulated gamepad.
+    #[inline]
+    pub fn press_gamepad_button(&self, gamepad: &Gamepad, btn: GamepadButton) {
+        gamepad.handle_press(btn);
+        gamepad.handle_button_change(btn, 1.0);
+    }
+
+    /// Release a button on a simulated gamepad.
+    #[inline]
+    pub fn release_gamepad_button(&self, gamepad: &Gamepad, btn: GamepadButton) {
+        gamepad.handle_release(btn);
+        gamepad.handle_button_change(btn, 0.0);
+    }
+
+    /// Set an axis value on a simulated gamepad, from `-1.0` to `1.0`.
+    #[inline]
+    pub fn set_gamepad_axis(&self, gamepad: &Gamepad, axis: GamepadAxis, value: f32) {
+        gamepad.handle_axis_change(axis, value.clamp(-1.0, 1.0));
+    }
+}
+
+/// Run a single headless frame that calls `render`, for testing code that needs a context or
+/// drawing. Use [`assert!`] inside `render`, since its errors fail the test.
+#[cfg(test)]
+pub(crate) fn test_frame(
+    builder: GameBuilder,
+    render: fn(&Context, &mut Draw) -> Result<(), GameError>,
+) {
+    builder
+        .build_headless::<TestRender>(render)
+        .unwrap()
+        .step()
+        .unwrap();
+}
+
+#[cfg(test)]
+struct TestRender(fn(&Context, &mut Draw) -> Result<(), GameError>);
+
+#[cfg(test)]
+impl Game for TestRender {
+    type Config = fn(&Context, &mut Draw) -> Result<(), GameError>;
+
+    fn new(_ctx: &Context, cfg: Self::Config) -> Result<Self, GameError> {
+        Ok(Self(cfg))
+    }
+
+    fn update(&mut self, _ctx: &Context) -> Result<(), GameError> {
+        Ok(())
+    }
+
+    fn render(&mut self, ctx: &Context, draw: &mut Draw) -> Result<(), GameError> {
+        (self.0)(ctx, draw)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::Rgba8;
+
+    struct TestGame {
+        updates: u64,
+        renders: u64,
+        jumps: u64,
+    }
+
+    impl Game for TestGame {
+        type Config = ();
+
+        fn new(_ctx: &Context, _cfg: Self::Config) -> Result<Self, GameError> {
+            Ok(Self {
+                updates: 0,
+                renders: 0,
+                jumps: 0,
+            })
+        }
+
+        fn update(&mut self, ctx: &Context) -> Result<(), GameError> {
+            self.updates += 1;
+            if ctx.keyboard.pressed(Key::Space) {
+                self.jumps += 1;
+            }
+            if self.updates == 10 {
+                ctx.quit();
+            }
+            Ok(())
+        }
+
+        fn render(&mut self, _ctx: &Context, draw: &mut Draw) -> Result<(), GameError> {
+            self.renders += 1;
+            draw.rect((0.0, 0.0, 16.0, 16.0), Rgba8::WHITE);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn headless_steps() {
+        let mut runner = GameBuilder::new()
+            .unwrap()
+            .with_size(64, 64)
+            .build_headless::<TestGame>(())
+            .unwrap();
+
+        runner.run(3).unwrap();
+        runner.press_key(Key::Space);
+        runner.step().unwrap();
+        runner.step().unwrap();
+        assert_eq!(runner.game().jumps, 1);
+        assert!(runner.ctx().keyboard.down(Key::Space));
+        assert_eq!(runner.ctx().time.frame(), 5);
+
+        // the game quits after 10 updates
+        assert_eq!(runner.run(100).unwrap(), 5);
+        let game = runner.into_game();
+        assert_eq!(game.updates, 10);
+        assert_eq!(game.renders, 10);
+    }
+
+    struct FailingGame {
+        handled: u32,
+    }
+
+    impl Game for FailingGame {
+        type Config = ();
+
+        fn new(_ctx: &Context, _cfg: Self::Config) -> Result<Self, GameError> {
+            Ok(Self { handled: 0 })
+        }
+
+        fn update(&mut self, _ctx: &Context) -> Result<(), GameError> {
+            Err(GameError::custom("update failed"))
+        }
+
+        fn render(&mut self, _ctx: &Context, _draw: &mut Draw) -> Result<(), GameError> {
+            Ok(())
+        }
+
+        fn on_error(&mut self, _ctx: &Context, err: GameError) -> Result<(), GameError> {
+            // recover from the first error only
+            self.handled += 1;
+            if self.handled == 1 { Ok(()) } else { Err(err) }
+        }
+    }
+
+    #[test]
+    fn headless_errors() {
+        let mut runner = GameBuilder::new()
+            .unwrap()
+            .build_headless::<FailingGame>(())
+            .unwrap();
+
+        assert!(runner.step().is_ok());
+        let err = runner.step().unwrap_err();
+        assert_eq!(err.to_string(), "update failed");
+        assert_eq!(runner.game().handled, 2);
+    }
+}
@@ -1,8 +1,9 @@
 use super::{Context, Game, GameError};
 use crate::gfx::Draw;
+use crate::math::Vec2U;
 use fey_lua::TempTypes;
 use mlua::prelude::LuaResult;
-use mlua::{Function, Lua, Table, Value};
+use mlua::{FromLuaMulti, Function, IntoLuaMulti, Lua, Table, Value};
 use std::collections::HashSet;
 use std::ffi::OsStr;
 use std::path::PathBuf;
@@ -164,6 +165,72 @@ impl<G: Game> Game for LuaGame<G> {
 
         Ok(())
     }
+
+    fn on_focus_changed(&mut self, ctx: &Context, focused: bool) -> Result<(), GameError> {
+        self.game.on_focus_changed(ctx, focused)?;
+        self.call_hook::<()>("on_focus_changed", focused);
+        Ok(())
+    }
+
+    fn on_resized(&mut self, ctx: &Context, size: Vec2U) -> Result<(), GameError> {
+        self.game.on_resized(ctx, size)?;
+        self.call_hook::<()>("on_resized", size);
+        Ok(())
+    }
+
+    fn on_occluded(&mut self, ctx: &Context, occluded: bool) -> Result<(), GameError> {
+        self.game.on_occluded(ctx, occluded)?;
+        self.call_hook::<()>("on_occluded", occluded);
+        Ok(())
+    }
+
+    fn on_close_requested(&mut self, ctx: &Context) -> Result<bool, GameError> {
+        if !self.game.on_close_requested(ctx)? {
+            return Ok(false);
+        }
+
+        // Main:on_close_requested() can return false to cancel closing
+        Ok(self
+            .call_hook::<Option<bool>>("on_close_requested", ())
+            .flatten()
+            .unwrap_or(true))
+    }
+
+    fn on_suspend(&mut self, ctx: &Context) -> Result<(), GameError> {
+        self.game.on_suspend(ctx)?;
+        self.call_hook::<()>("on_suspend", ());
+        Ok(())
+    }
+
+    fn on_resume(&mut self, ctx: &Context) -> Result<(), GameError> {
+        self.game.on_resume(ctx)?;
+        self.call_hook::<()>("on_resume", ());
+        Ok(())
+    }
+
+    #[inline]
+    fn on_error(&mut self, ctx: &Context, err: GameError) -> Result<(), GameError> {
+        self.game.on_error(ctx, err)
+    }
+}
+
+impl<G: Game> LuaGame<G> {
+    /// Call an optional hook on `Main`, returning `None` if it isn't defined or failed.
+    fn call_hook<R: FromLuaMulti>(&mut self, name: &str, args: impl IntoLuaMulti) -> Option<R> {
+        match self
+            .main
+            .as_ref()
+            .map(|main| main.call_hook(&self.lua, name, args))
+        {
+            Ok(Ok(result)) => result,
+            Ok(Err(err)) => {
+                println!("{err}");
+                self.main = Err(err);
+                None
+            }
+            Err(_) => None,
+        }
+    }
 }
 
 struct LuaMain {
@@ -233,6 +300,20 @@ impl LuaMain {
         self.update_fn.call(self.module.clone())
     }
 
+    fn call_hook<R: FromLuaMulti>(
+        &self,
+        lua: &Lua,
+        name: &str,
+        args: impl IntoLuaMulti,
+    ) -> LuaResult<Option<R>> {
+        let Some(hook) = self.module.get::<Option<Function>>(name)? else {
+            return Ok(None);
+        };
+        let mut args = args.into_lua_multi(lua)?;
+        args.push_front(Value::Table(self.module.clone()));
+        hook.call(args).map(Some)
+    }
+
     #[inline]
     fn render(&self, lua: &Lua, draw: &mut Draw) -> LuaResult<()> {
         let draw: *mut Draw = draw;
@@ -2,14 +2,22 @@
 
 mod app_handler;
 mod context;
+mod cursor_grab;
 mod cursor_icon;
 mod display_mode;
+mod frame_step;
 mod frame_timer;
 mod game;
 mod game_builder;
 mod game_error;
+mod headless_runner;
 mod monitor;
+mod scene;
+mod scene_stack;
+mod scene_transition;
+mod storage;
 mod time;
+mod timestep;
 mod video_mode;
 mod window;
 
@@ -17,13 +25,20 @@ mod window;
 mod lua_game;
 
 pub use context::*;
+pub use cursor_grab::*;
 pub use cursor_icon::*;
 pub use display_mode::*;
 pub use game::*;
 pub use game_builder::*;
 pub use game_error::*;
+pub use headless_runner::*;
 pub use monitor::*;
+pub use scene::*;
+pub use scene_stack::*;
+pub use scene_transition::*;
+pub use storage::*;
 pub use time::*;
+pub use timestep::*;
 pub use video_mode::*;
 pub use window::*;
 
@@ -0,0 +1,51 @@
+use crate::core::{Context, GameError, Scenes};
+use crate::gfx::Draw;
+
+/// A scene managed by a [`SceneStack`](super::SceneStack), such as a title screen, a level, or
+/// a pause menu.
+pub trait Scene: 'static {
+    /// Called every update while the scene isn't paused. Scenes can be pushed, popped, or
+    /// replaced with `scenes`, and the changes will be applied after all scenes are updated.
+    fn update(&mut self, ctx: &Context, scenes: &Scenes) -> Result<(), GameError>;
+
+    /// Called every frame refresh while the scene is visible.
+    fn render(&mut self, ctx: &Context, draw: &mut Draw) -> Result<(), GameError>;
+
+    /// If the scene is an overlay, such as a pause menu or dialog box, the scenes below it will
+    /// still be rendered, so it is drawn over them. Otherwise, the scenes below it are hidden.
+    #[inline]
+    fn is_overlay(&self) -> bool {
+        false
+    }
+
+    /// If this overlay pauses the scenes below it. Scenes below a scene that isn't an overlay
+    /// are always paused.
+    #[inline]
+    fn pauses_below(&self) -> bool {
+        true
+    }
+
+    /// Called when the scene is added to the stack.
+    #[inline]
+    fn on_enter(&mut self, _ctx: &Context) -> Result<(), GameError> {
+        Ok(())
+    }
+
+    /// Called when the scene is removed from the stack.
+    #[inline]
+    fn on_exit(&mut self, _ctx: &Context) -> Result<(), GameError> {
+        Ok(())
+    }
+
+    /// Called when another scene is pushed on top of this one.
+    #[inline]
+    fn on_cover(&mut self, _ctx: &Context) -> Result<(), GameError> {
+        Ok(())
+    }
+
+    /// Called when the scene on top of this one is popped, making this the top scene again.
+    #[inline]
+    fn on_uncover(&mut self, _ctx: &Context) -> Result<(), GameError> {
+        Ok(())
+    }
+}
@@ -0,0 +1,538 @@
+use crate::color::Rgba8;
+use crate::core::{Context, Game, GameError, Scene, SceneTransition};
+use crate::gfx::{Draw, Surface, TextureFormat};
+use crate::math::Vec2U;
+use std::cell::{Cell, RefCell};
+use std::fmt::{Debug, Formatter};
+use std::mem::take;
+use std::rc::Rc;
+
+#[cfg(feature = "lua")]
+pub type SceneStackObj = fey_lua::UserDataOf<SceneStack>;
+#[cfg(feature = "lua")]
+pub type SceneStackRef = mlua::UserDataRef<SceneStack>;
+#[cfg(feature = "lua")]
+pub type SceneStackMut = mlua::UserDataRefMut<SceneStack>;
+#[cfg(feature = "lua")]
+pub type ScenesRef = mlua::UserDataRef<Scenes>;
+
+/// Creates the first scene of a [`SceneStack`] when it is run as a [`Game`].
+pub type SceneFn = Box<dyn FnOnce(&Context) -> Result<Box<dyn Scene>, GameError>>;
+
+/// A stack of [`Scene`]s, which can be run as a [`Game`] or used inside of one.
+///
+/// The top scene is updated and rendered. Overlay scenes are rendered over the scenes below
+/// them, and can optionally let them keep updating. Scenes are switched with the [`Scenes`]
+/// handle passed to [`Scene::update`], and switches can play a [`SceneTransition`].
+///
+/// ```no_run
+/// use kero::prelude::*;
+///
+/// fn main() -> Result<(), GameError> {
+///     kero::new_game()
+///         .run::<SceneStack>(SceneStack::starting_with(|_ctx| Ok(Title)))
+/// }
+///
+/// struct Title;
+///
+/// impl Scene for Title {
+///     fn update(&mut self, ctx: &Context, scenes: &Scenes) -> Result<(), GameError> {
+///         if ctx.keyboard.pressed(Key::Enter) {
+///             scenes.replace_with(Title, SceneTransition::fade(0.5, Rgba8::BLACK));
+///         }
+///         Ok(())
+///     }
+///
+///     fn render(&mut self, _ctx: &Context, _draw: &mut Draw) -> Result<(), GameError> {
+///         Ok(())
+///     }
+/// }
+/// ```
+pub struct SceneStack {
+    scenes: Vec<Box<dyn Scene>>,
+    handle: Scenes,
+    pending: Option<(Vec<SceneCommand>, SceneTransition)>,
+    transition: Option<(SceneTransition, f32)>,
+    surfaces: Option<(Surface, Surface)>,
+}
+
+/// Handle used to switch the scenes of a [`SceneStack`].
+///
+/// This handle can be cloned and passed around freely to give objects the ability to switch
+/// scenes. Switches are queued and applied after all scenes have been updated.
+#[derive(Clone)]
+pub struct Scenes(Rc<ScenesInner>);
+
+#[derive(Default)]
+struct ScenesInner {
+    commands: RefCell<Vec<SceneCommand>>,
+    transition: Cell<Option<SceneTransition>>,
+    len: Cell<usize>,
+}
+
+pub(crate) enum SceneCommand {
+    Push(Box<dyn Scene>),
+    Pop,
+    Replace(Box<dyn Scene>),
+    ReplaceAll(Box<dyn Scene>),
+}
+
+impl Debug for SceneStack {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("SceneStack").finish_non_exhaustive()
+    }
+}
+
+impl Debug for Scenes {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Scenes").finish_non_exhaustive()
+    }
+}
+
+impl PartialEq for Scenes {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Scenes {
+    /// How many scenes are on the stack, not counting queued switches.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.0.len.get()
+    }
+
+    /// If there are no scenes on the stack, not counting queued switches.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Push a scene on top of the stack.
+    #[inline]
+    pub fn push(&self, scene: impl Scene) {
+        self.queue(SceneCommand::Push(Box::new(scene)), None);
+    }
+
+    /// Push a scene on top of the stack with a transition.
+    #[inline]
+    pub fn push_with(&self, scene: impl Scene, transition: SceneTransition) {
+        self.queue(SceneCommand::Push(Box::new(scene)), Some(transition));
+    }
+
+    /// Pop the top scene off the stack. If the stack becomes empty, the game will quit.
+    #[inline]
+    pub fn pop(&self) {
+        self.queue(SceneCommand::Pop, None);
+    }
+
+    /// Pop the top scene off the stack with a transition.
+    #[inline]
+    pub fn pop_with(&self, transition: SceneTransition) {
+        self.queue(SceneCommand::Pop, Some(transition));
+    }
+
+    /// Replace the top scene of the stack.
+    #[inline]
+    pub fn replace(&self, scene: impl Scene) {
+        self.queue(SceneCommand::Replace(Box::new(scene)), None);
+    }
+
+    /// Replace the top scene of the stack with a transition.
+    #[inline]
+    pub fn replace_with(&self, scene: impl Scene, transition: SceneTransition) {
+        self.queue(SceneCommand::Replace(Box::new(scene)), Some(transition));
+    }
+
+    /// Replace all the scenes on the stack with a single scene, such as when returning to the
+    /// title screen.
+    #[inline]
+    pub fn replace_all(&self, scene: impl Scene) {
+        self.queue(SceneCommand::ReplaceAll(Box::new(scene)), None);
+    }
+
+    /// Replace all the scenes on the stack with a single scene with a transition.
+    #[inline]
+    pub fn replace_all_with(&self, scene: impl Scene, transition: SceneTransition) {
+        self.queue(SceneCommand::ReplaceAll(Box::new(scene)), Some(transition));
+    }
+
+    /// Queue a switch. If several switches are queued at once, they are applied together using
+    /// the last transition given.
+    pub(crate) fn queue(&self, command: SceneCommand, transition: Option<SceneTransition>) {
+        self.0.commands.borrow_mut().push(command);
+        if transition.is_some() {
+            self.0.transition.set(transition);
+        }
+    }
+}
+
+impl SceneStack {
+    /// Create a scene stack with the scene on it.
+    pub fn new(ctx: &Context, scene: impl Scene) -> Result<Self, GameError> {
+        Self::new_boxed(ctx, Box::new(scene))
+    }
+
+    pub(crate) fn new_boxed(ctx: &Context, mut scene: Box<dyn Scene>) -> Result<Self, GameError> {
+        let handle = Scenes(Rc::default());
+        scene.on_enter(ctx)?;
+        handle.0.len.set(1);
+        Ok(Self {
+            scenes: vec![scene],
+            handle,
+            pending: None,
+            transition: None,
+            surfaces: None,
+        })
+    }
+
+    /// Create the config to run a scene stack as a [`Game`], starting with the scene.
+    pub fn starting_with<S: Scene>(
+        new: impl FnOnce(&Context) -> Result<S, GameError> + 'static,
+    ) -> SceneFn {
+        Box::new(|ctx| Ok(Box::new(new(ctx)?)))
+    }
+
+    /// Handle used to switch scenes.
+    #[inline]
+    pub fn scenes(&self) -> &Scenes {
+        &self.handle
+    }
+
+    /// How many scenes are on the stack.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.scenes.len()
+    }
+
+    /// If there are no scenes on the stack.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.scenes.is_empty()
+    }
+
+    /// If a transition is playing, or waiting to start.
+    #[inline]
+    pub fn is_transitioning(&self) -> bool {
+        self.pending.is_some() || self.transition.is_some()
+    }
+
+    /// Update the scenes from the top down, stopping at the first one that pauses the scenes
+    /// below it. Then apply any switches that were queued.
+    pub fn update(&mut self, ctx: &Context) -> Result<(), GameError> {
+        // advance the current transition
+        if let Some((transition, elapsed)) = &mut self.transition {
+            *elapsed += ctx.time.delta();
+            if *elapsed >= transition.duration() {
+                self.transition = None;
+            }
+        }
+
+        // update the unpaused scenes
+        for scene in self.scenes.iter_mut().rev() {
+            scene.update(ctx, &self.handle)?;
+            if !scene.is_overlay() || scene.pauses_below() {
+                break;
+            }
+        }
+
+        // apply the switches, unless the old scenes need to be captured for a transition first
+        let commands = take(&mut *self.handle.0.commands.borrow_mut());
+        let transition = self.handle.0.transition.take();
+        if let Some((pending, pending_transition)) = &mut self.pending {
+            pending.extend(commands);
+            if let Some(transition) = transition {
+                *pending_transition = transition;
+            }
+        } else if let Some(transition) = transition.filter(|t| t.duration() > 0.0) {
+            self.pending = Some((commands, transition));
+        } else {
+            self.apply(ctx, commands)?;
+        }
+        Ok(())
+    }
+
+    /// Render the visible scenes from the bottom up, along with the current transition.
+    ///
+    /// Transitions are drawn over the current target without clearing it, so the stack can be
+    /// rendered on top of other drawing.
+    pub fn render(&mut self, ctx: &Context, draw: &mut Draw) -> Result<(), GameError> {
+        // the scenes are drawn to the transition surfaces, so remember where to draw them after
+        let target = draw.surface().cloned();
+
+        // capture the old scenes, then switch to the new ones and start the transition
+        if let Some((commands, transition)) = self.pending.take() {
+            let (from, _) = self.surfaces(ctx).clone();
+            let prev = draw.redirect_window(from);
+            draw.set_surface(None, Rgba8::BLACK);
+            let result = self.render_scenes(ctx, draw);
+            draw.redirect_window(prev);
+            draw.set_surface(target.clone(), None);
+            result?;
+            self.apply(ctx, commands)?;
+            self.transition = Some((transition, 0.0));
+        }
+
+        let Some((transition, elapsed)) = self.transition else {
+            return self.render_scenes(ctx, draw);
+        };
+
+        // render the new scenes, then draw the transition to the target
+        let (from, to) = self.surfaces(ctx).clone();
+        let prev = draw.redirect_window(to.clone());
+        draw.set_surface(None, Rgba8::BLACK);
+        let result = self.render_scenes(ctx, draw);
+        draw.redirect_window(prev);
+        draw.set_surface(target, None);
+        result?;
+        transition.draw(draw, &from, &to, elapsed / transition.duration());
+        Ok(())
+    }
+
+    fn render_scenes(&mut self, ctx: &Context, draw: &mut Draw) -> Result<(), GameError> {
+        let start = self
+            .scenes
+            .iter()
+            .rposition(|scene| !scene.is_overlay())
+            .unwrap_or(0);
+        for scene in &mut self.scenes[start..] {
+            scene.render(ctx, draw)?;
+        }
+        Ok(())
+    }
+
+    /// The surfaces the old and new scenes are rendered into during transitions, resized to
+    /// match the window and created with the same sample count and depth-stencil.
+    fn surfaces(&mut self, ctx: &Context) -> &(Surface, Surface) {
+        let size = ctx.window.size().max(Vec2U::ONE);
+        if self
+            .surfaces
+            .as_ref()
+            .is_none_or(|(from, _)| from.size() != size)
+        {
+            // match the window, so scenes draw the same as they would to it
+            let create = || {
+                ctx.graphics.create_surface_ext(
+                    size,
+                    TextureFormat::Rgba8,
+                    ctx.graphics.window_sample_count(),
+                    ctx.graphics.window_has_depth_stencil(),
+                )
+            };
+            self.surfaces = Some((create(), create()));
+        }
+        self.surfaces.as_ref().unwrap()
+    }
+
+    fn apply(&mut self, ctx: &Context, commands: Vec<SceneCommand>) -> Result<(), GameError> {
+        if commands.is_empty() {
+            return Ok(());
+        }
+        for command in commands {
+            match command {
+                SceneCommand::Push(mut scene) => {
+                    if let Some(top) = self.scenes.last_mut() {
+                        top.on_cover(ctx)?;
+                    }
+                    scene.on_enter(ctx)?;
+                    self.scenes.push(scene);
+                }
+                SceneCommand::Pop => {
+                    if let Some(mut scene) = self.scenes.pop() {
+                        scene.on_exit(ctx)?;
+                    }
+                    if let Some(top) = self.scenes.last_mut() {
+                        top.on_uncover(ctx)?;
+                    }
+                }
+                SceneCommand::Replace(mut scene) => {
+                    if let Some(mut top) = self.scenes.pop() {
+                        top.on_exit(ctx)?;
+                    }
+                    scene.on_enter(ctx)?;
+                    self.scenes.push(scene);
+                }
+                SceneCommand::ReplaceAll(mut scene) => {
+                    while let Some(mut top) = self.scenes.pop() {
+                        top.on_exit(ctx)?;
+                    }
+                    scene.on_enter(ctx)?;
+                    self.scenes.push(scene);
+                }
+            }
+        }
+        self.handle.0.len.set(self.scenes.len());
+
+        // there is nothing left to run
+        if self.scenes.is_empty() {
+            ctx.quit();
+        }
+        Ok(())
+    }
+}
+
+impl Game for SceneStack {
+    type Config = SceneFn;
+
+    fn new(ctx: &Context, cfg: Self::Config) -> Result<Self, GameError>
+    where
+        Self: Sized,
+    {
+        Self::new_boxed(ctx, cfg(ctx)?)
+    }
+
+    #[inline]
+    fn update(&mut self, ctx: &Context) -> Result<(), GameError> {
+        SceneStack::update(self, ctx)
+    }
+
+    #[inline]
+    fn render(&mut self, ctx: &Context, draw: &mut Draw) -> Result<(), GameError> {
+        SceneStack::render(self, ctx, draw)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{GameBuilder, test_frame};
+    use crate::input::Key;
+
+    /// Logs the scene events, and pushes an overlay when space is pressed.
+    struct Level {
+        name: &'static str,
+        overlay: bool,
+        log: Rc<RefCell<Vec<String>>>,
+    }
+
+    impl Level {
+        fn new(name: &'static str, overlay: bool, log: &Rc<RefCell<Vec<String>>>) -> Self {
+            Self {
+                name,
+                overlay,
+                log: log.clone(),
+            }
+        }
+
+        fn log(&self, event: &str) {
+            self.log.borrow_mut().push(format!("{} {event}", self.name));
+        }
+    }
+
+    impl Scene for Level {
+        fn update(&mut self, ctx: &Context, scenes: &Scenes) -> Result<(), GameError> {
+            self.log("update");
+            if ctx.keyboard.pressed(Key::Space) {
+                let menu = Level::new("menu", true, &self.log);
+                scenes.push_with(menu, SceneTransition::cross_fade(0.25));
+            } else if ctx.keyboard.pressed(Key::Escape) {
+                scenes.pop();
+            }
+            Ok(())
+        }
+
+        fn render(&mut self, _ctx: &Context, draw: &mut Draw) -> Result<(), GameError> {
+            self.log("render");
+            draw.rect((0.0, 0.0, 8.0, 8.0), Rgba8::WHITE);
+            Ok(())
+        }
+
+        fn is_overlay(&self) -> bool {
+            self.overlay
+        }
+
+        fn on_enter(&mut self, _ctx: &Context) -> Result<(), GameError> {
+            self.log("enter");
+            Ok(())
+        }
+
+        fn on_exit(&mut self, _ctx: &Context) -> Result<(), GameError> {
+            self.log("exit");
+            Ok(())
+        }
+
+        fn on_cover(&mut self, _ctx: &Context) -> Result<(), GameError> {
+            self.log("cover");
+            Ok(())
+        }
+
+        fn on_uncover(&mut self, _ctx: &Context) -> Result<(), GameError> {
+            self.log("uncover");
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn scene_switching() {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let level_log = log.clone();
+        let mut runner = GameBuilder::new()
+            .unwrap()
+            .with_size(64, 64)
+            .build_headless::<SceneStack>(SceneStack::starting_with(move |_| {
+                Ok(Level::new("level", false, &level_log))
+            }))
+            .unwrap();
+        let take_log = || take(&mut *log.borrow_mut());
+
+        runner.step().unwrap();
+        assert_eq!(take_log(), ["level enter", "level update", "level render"]);
+
+        // the old scenes are captured before the menu is pushed, and both are rendered
+        runner.press_key(Key::Space);
+        runner.step().unwrap();
+        assert_eq!(
+            take_log(),
+            [
+                "level update",
+                "level render",
+                "level cover",
+                "menu enter",
+                "level render",
+                "menu render"
+            ]
+        );
+        assert!(runner.game().is_transitioning());
+        assert_eq!(runner.game().scenes().len(), 2);
+
+        // the menu pauses the level below it
+        runner.release_key(Key::Space);
+        runner.press_key(Key::Escape);
+        runner.step().unwrap();
+        assert_eq!(
+            take_log(),
+            ["menu update", "menu exit", "level uncover", "level render"]
+        );
+        assert_eq!(runner.game().len(), 1);
+
+        // popping the last scene quits the game
+        runner.release_key(Key::Escape);
+        runner.step().unwrap();
+        runner.press_key(Key::Escape);
+        runner.step().unwrap();
+        assert!(runner.game().is_empty());
+        assert!(runner.ctx().quit_requested());
+    }
+
+    #[test]
+    fn nested_transition() {
+        test_frame(GameBuilder::new().unwrap(), |ctx, draw| {
+            let log = Rc::new(RefCell::new(Vec::new()));
+            let mut stack = SceneStack::new(ctx, Level::new("level", false, &log))?;
+            let menu = Level::new("menu", true, &log);
+            stack
+                .scenes()
+                .push_with(menu, SceneTransition::cross_fade(0.25));
+            stack.update(ctx)?;
+
+            // the transition is drawn to the caller's target, which isn't cleared
+            let surface = ctx.graphics.create_surface((8, 8), TextureFormat::Rgba8);
+            draw.set_surface(surface.clone(), Rgba8::BLACK);
+            stack.render(ctx, draw)?;
+            assert!(stack.is_transitioning());
+            assert_eq!(draw.surface(), Some(&surface));
+            Ok(())
+        });
+    }
+}
@@ -0,0 +1,110 @@
+use crate::color::Rgba8;
+use crate::gfx::{ColorMode, Draw, SubTexture, Surface};
+use crate::math::{Cardinal, Numeric, RectF, Vec2F, vec2};
+
+/// A transition played when a [`SceneStack`](super::SceneStack) switches scenes.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum SceneTransition {
+    /// Fade the old scenes out to a color, then fade the new scenes in from it.
+    Fade { duration: f32, color: Rgba8 },
+
+    /// Fade the new scenes in over the old ones.
+    CrossFade { duration: f32 },
+
+    /// Wipe the new scenes in over the old ones, with the edge moving in the direction.
+    Wipe { duration: f32, dir: Cardinal },
+}
+
+impl SceneTransition {
+    /// Create a new [`Fade`](SceneTransition::Fade) transition.
+    #[inline]
+    pub const fn fade(duration: f32, color: Rgba8) -> Self {
+        Self::Fade { duration, color }
+    }
+
+    /// Create a new [`CrossFade`](SceneTransition::CrossFade) transition.
+    #[inline]
+    pub const fn cross_fade(duration: f32) -> Self {
+        Self::CrossFade { duration }
+    }
+
+    /// Create a new [`Wipe`](SceneTransition::Wipe) transition.
+    #[inline]
+    pub const fn wipe(duration: f32, dir: Cardinal) -> Self {
+        Self::Wipe { duration, dir }
+    }
+
+    /// How long the transition lasts in seconds.
+    #[inline]
+    pub const fn duration(&self) -> f32 {
+        match self {
+            Self::Fade { duration, .. }
+            | Self::CrossFade { duration }
+            | Self::Wipe { duration, .. } => *duration,
+        }
+    }
+
+    /// Draw the transition between the old and new scenes, which have been rendered into
+    /// surfaces the size of the window. The progress goes from `0` to `1`.
+    pub fn draw(&self, draw: &mut Draw, from: &Surface, to: &Surface, progress: f32) {
+        let t = progress.clamp(0.0, 1.0);
+        match *self {
+            Self::Fade { color, .. } => {
+                let (surface, amount) = match t < 0.5 {
+                    true => (from, t * 2.0),
+                    false => (to, (1.0 - t) * 2.0),
+                };
+                draw.texture_at(surface, Vec2F::ZERO);
+                draw.rect(RectF::sized(surface.size().to_f32()), fade(color, amount));
+            }
+            Self::CrossFade { .. } => {
+                draw.texture_at(from, Vec2F::ZERO);
+                let color = fade(Rgba8::WHITE, t);
+                draw.texture_at_ext(to, Vec2F::ZERO, color, ColorMode::MULT);
+            }
+            Self::Wipe { dir, .. } => {
+                draw.texture_at(from, Vec2F::ZERO);
+                let size = to.size().to_f32();
+                let rect = match dir {
+                    Cardinal::East => RectF::new(0.0, 0.0, size.x * t, size.y),
+                    Cardinal::West => RectF::new(size.x * (1.0 - t), 0.0, size.x * t, size.y),
+                    Cardinal::South => RectF::new(0.0, 0.0, size.x, size.y * t),
+                    Cardinal::North => RectF::new(0.0, size.y * (1.0 - t), size.x, size.y * t),
+                };
+                let sub = SubTexture::new(to.texture().clone(), rect);
+                draw.subtexture_at(&sub, vec2(rect.x, rect.y));
+            }
+        }
+    }
+}
+
+/// Fade a color by the amount, since colors are drawn with premultiplied alpha.
+#[inline]
+fn fade(color: Rgba8, amount: f32) -> Rgba8 {
+    color.un_mul((amount.clamp(0.0, 1.0) * 255.0).round() as u8)
+}
+
+#[cfg(feature = "lua")]
+impl mlua::FromLua for SceneTransition {
+    fn from_lua(value: mlua::Value, lua: &mlua::Lua) -> mlua::Result<Self> {
+        let t = mlua::Table::from_lua(value, lua)?;
+        let duration = t.get("duration")?;
+        let kind = t.get::<String>("kind")?;
+        Ok(match kind.as_str() {
+            "fade" => Self::Fade {
+                duration,
+                color: t.get::<Option<Rgba8>>("color")?.unwrap_or(Rgba8::BLACK),
+            },
+            "cross_fade" => Self::CrossFade { duration },
+            "wipe" => Self::Wipe {
+                duration,
+                dir: t.get::<Option<Cardinal>>("dir")?.unwrap_or(Cardinal::East),
+            },
+            s => {
+                return Err(mlua::prelude::LuaError::runtime(format!(
+                    "invalid scene transition {s:?}"
+                )));
+            }
+        })
+    }
+}
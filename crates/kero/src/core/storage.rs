@@ -0,0 +1,473 @@
+use directories::ProjectDirs;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::cell::Cell;
+use std::fmt::{Debug, Formatter};
+use std::fs::{self, File};
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+/// Handle to the game's persistent storage.
+///
+/// This handle can be cloned and passed around freely to give objects access to storage.
+///
+/// Obtained from [`Context`](super::Context). Values are saved by key into one of the game's
+/// system directories, so games don't have to manage the files themselves. Saving is atomic:
+/// the value is written to a temporary file which then replaces the previous one, so a crash
+/// while saving will never leave a half-written file behind. The previous versions of a file are
+/// kept as rotating backups, which can be loaded with [`Storage::load_backup`].
+///
+/// Keys are relative paths using `/` as a separator, such as `"settings"` or `"saves/slot1"`.
+#[derive(Clone)]
+pub struct Storage(Rc<StorageInner>);
+
+struct StorageInner {
+    data: PathBuf,
+    config: PathBuf,
+    preferences: PathBuf,
+    backups: Cell<usize>,
+}
+
+/// The system directory a [`Storage`] value is saved in.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum StorageDir {
+    /// The data directory, for save files and other game data.
+    #[default]
+    Data,
+
+    /// The config directory, for settings.
+    Config,
+
+    /// The preferences directory, for settings on platforms that keep them apart from config.
+    Preferences,
+}
+
+impl Debug for Storage {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Storage").finish_non_exhaustive()
+    }
+}
+
+impl PartialEq for Storage {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Storage {
+    /// The default number of backups kept of each file.
+    pub const DEFAULT_BACKUPS: usize = 3;
+
+    #[inline]
+    pub(crate) fn new(dirs: &ProjectDirs) -> Self {
+        Self(Rc::new(StorageInner {
+            data: dirs.data_dir().to_path_buf(),
+            config: dirs.config_dir().to_path_buf(),
+            preferences: dirs.preference_dir().to_path_buf(),
+            backups: Cell::new(Self::DEFAULT_BACKUPS),
+        }))
+    }
+
+    /// Create storage that keeps all of its directories in a single root directory instead of
+    /// the system directories, which is useful for portable installs and tests.
+    pub fn with_root(root: impl Into<PathBuf>) -> Self {
+        let root = root.into();
+        Self(Rc::new(StorageInner {
+            data: root.join("data"),
+            config: root.join("config"),
+            preferences: root.join("preferences"),
+            backups: Cell::new(Self::DEFAULT_BACKUPS),
+        }))
+    }
+
+    /// How many backups are kept of each file.
+    #[inline]
+    pub fn backups(&self) -> usize {
+        self.0.backups.get()
+    }
+
+    /// Set how many backups are kept of each file. Existing backups past this count are deleted
+    /// the next time their file is saved.
+    #[inline]
+    pub fn set_backups(&self, backups: usize) {
+        self.0.backups.set(backups);
+    }
+
+    /// The path of the directory.
+    #[inline]
+    pub fn dir_path(&self, dir: StorageDir) -> &Path {
+        match dir {
+            StorageDir::Data => &self.0.data,
+            StorageDir::Config => &self.0.config,
+            StorageDir::Preferences => &self.0.preferences,
+        }
+    }
+
+    /// The path of the file a value is saved to.
+    pub fn path(&self, dir: StorageDir, key: &str) -> Result<PathBuf, StorageError> {
+        self.file_path(dir, key, "json")
+    }
+
+    /// If a value has been saved with the key.
+    pub fn exists(&self, dir: StorageDir, key: &str) -> Result<bool, StorageError> {
+        Ok(self.path(dir, key)?.is_file())
+    }
+
+    /// Save a value.
+    pub fn save<T: Serialize + ?Sized>(
+        &self,
+        dir: StorageDir,
+        key: &str,
+        value: &T,
+    ) -> Result<(), StorageError> {
+        let bytes = serde_json::to_vec_pretty(value)?;
+        self.write(&self.path(dir, key)?, &bytes)
+    }
+
+    /// Load a value, or `None` if no value has been saved with the key.
+    pub fn load<T: DeserializeOwned>(
+        &self,
+        dir: StorageDir,
+        key: &str,
+    ) -> Result<Option<T>, StorageError> {
+        Self::read(&self.path(dir, key)?)?
+            .map(|bytes| serde_json::from_slice(&bytes).map_err(StorageError::from))
+            .transpose()
+    }
+
+    /// Load a backup of a value, where `0` is the most recent one, or `None` if there is no
+    /// such backup. This can be used to recover when the latest file fails to load.
+    pub fn load_backup<T: DeserializeOwned>(
+        &self,
+        dir: StorageDir,
+        key: &str,
+        index: usize,
+    ) -> Result<Option<T>, StorageError> {
+        Self::read(&backup_path(&self.path(dir, key)?, index))?
+            .map(|bytes| serde_json::from_slice(&bytes).map_err(StorageError::from))
+            .transpose()
+    }
+
+    /// Delete a value along with all of its backups.
+    pub fn delete(&self, dir: StorageDir, key: &str) -> Result<(), StorageError> {
+        self.delete_file(&self.path(dir, key)?)
+    }
+
+    /// The path of the file a value is saved to in the binary format.
+    #[cfg(feature = "wincode")]
+    pub fn bin_path(&self, dir: StorageDir, key: &str) -> Result<PathBuf, StorageError> {
+        self.file_path(dir, key, "bin")
+    }
+
+    /// Save a value in the compact binary format.
+    #[cfg(feature = "wincode")]
+    pub fn save_bin<T: wincode::SchemaWrite<Src = T>>(
+        &self,
+        dir: StorageDir,
+        key: &str,
+        value: &T,
+    ) -> Result<(), StorageError> {
+        let bytes = wincode::serialize(value)?;
+        self.write(&self.bin_path(dir, key)?, &bytes)
+    }
+
+    /// Load a value saved in the compact binary format, or `None` if no value has been saved
+    /// with the key.
+    #[cfg(feature = "wincode")]
+    pub fn load_bin<T: for<'de> wincode::SchemaRead<'de, Dst = T>>(
+        &self,
+        dir: StorageDir,
+        key: &str,
+    ) -> Result<Option<T>, StorageError> {
+        Self::read(&self.bin_path(dir, key)?)?
+            .map(|bytes| wincode::deserialize(&bytes).map_err(StorageError::from))
+            .transpose()
+    }
+
+    /// Load a backup of a value saved in the compact binary format, where `0` is the most
+    /// recent one, or `None` if there is no such backup.
+    #[cfg(feature = "wincode")]
+    pub fn load_bin_backup<T: for<'de> wincode::SchemaRead<'de, Dst = T>>(
+        &self,
+        dir: StorageDir,
+        key: &str,
+        index: usize,
+    ) -> Result<Option<T>, StorageError> {
+        Self::read(&backup_path(&self.bin_path(dir, key)?, index))?
+            .map(|bytes| wincode::deserialize(&bytes).map_err(StorageError::from))
+            .transpose()
+    }
+
+    /// Delete a value saved in the compact binary format along with all of its backups.
+    #[cfg(feature = "wincode")]
+    pub fn delete_bin(&self, dir: StorageDir, key: &str) -> Result<(), StorageError> {
+        self.delete_file(&self.bin_path(dir, key)?)
+    }
+
+    fn file_path(&self, dir: StorageDir, key: &str, ext: &str) -> Result<PathBuf, StorageError> {
+        let valid = !key.is_empty()
+            && key.split('/').all(|part| {
+                !part.is_empty()
+                    && !part.starts_with('.')
+                    && part
+                        .chars()
+                        .all(|c| c.is_alphanumeric() || matches!(c, '_' | '-' | '.' | ' '))
+            });
+        if !valid {
+            return Err(StorageError::InvalidKey(key.to_string()));
+        }
+        let mut path = self.dir_path(dir).to_path_buf();
+        path.extend(key.split('/'));
+        path.as_mut_os_string().push(".");
+        path.as_mut_os_string().push(ext);
+        Ok(path)
+    }
+
+    fn write(&self, path: &Path, bytes: &[u8]) -> Result<(), StorageError> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        // write the new file next to the old one, so a crash can't corrupt it
+        let tmp = with_suffix(path, "tmp");
+        let mut file = File::create(&tmp)?;
+        file.write_all(bytes)?;
+        file.sync_all()?;
+        drop(file);
+
+        // delete backups past the count, in case it was lowered
+        let backups = self.backups();
+        for (index, backup) in existing_backups(path)? {
+            if index >= backups {
+                fs::remove_file(backup)?;
+            }
+        }
+
+        // shift the backups down and back up the current file
+        if backups > 0 && path.is_file() {
+            for i in (1..backups).rev() {
+                let src = backup_path(path, i - 1);
+                if src.is_file() {
+                    fs::rename(&src, backup_path(path, i))?;
+                }
+            }
+            fs::copy(path, backup_path(path, 0))?;
+        }
+
+        // replace the current file in a single step, then make sure the rename itself is saved
+        fs::rename(&tmp, path)?;
+        sync_dir(path)?;
+        Ok(())
+    }
+
+    fn read(path: &Path) -> Result<Option<Vec<u8>>, StorageError> {
+        match fs::read(path) {
+            Ok(bytes) => Ok(Some(bytes)),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    fn delete_file(&self, path: &Path) -> Result<(), StorageError> {
+        // delete every backup, even those past the current count
+        let backups = existing_backups(path)?
+            .into_iter()
+            .map(|(_, backup)| backup);
+        for path in std::iter::once(path.to_path_buf()).chain(backups) {
+            match fs::remove_file(&path) {
+                Err(err) if err.kind() != ErrorKind::NotFound => return Err(err.into()),
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+#[inline]
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut path = path.as_os_str().to_owned();
+    path.push(".");
+    path.push(suffix);
+    path.into()
+}
+
+#[inline]
+fn backup_path(path: &Path, index: usize) -> PathBuf {
+    with_suffix(path, &(index + 1).to_string())
+}
+
+/// The index and path of every backup of the file that exists on disk, in no particular order.
+fn existing_backups(path: &Path) -> std::io::Result<Vec<(usize, PathBuf)>> {
+    let (Some(parent), Some(name)) = (path.parent(), path.file_name()) else {
+        return Ok(Vec::new());
+    };
+    let entries = match fs::read_dir(parent) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err),
+    };
+    let mut backups = Vec::new();
+    for entry in entries {
+        let entry = entry?;
+        let file_name = entry.file_name();
+        let index = file_name
+            .to_str()
+            .zip(name.to_str())
+            .and_then(|(file_name, name)| file_name.strip_prefix(name)?.strip_prefix('.'))
+            .filter(|num| num.bytes().all(|b| b.is_ascii_digit()))
+            .and_then(|num| num.parse::<usize>().ok())
+            .filter(|&num| num > 0);
+        if let Some(num) = index {
+            backups.push((num - 1, entry.path()));
+        }
+    }
+    Ok(backups)
+}
+
+/// Flush the directory containing the path, so renames and deletions in it survive a crash.
+/// Directories can't be opened as files on Windows, which doesn't need this anyway.
+fn sync_dir(path: &Path) -> std::io::Result<()> {
+    #[cfg(unix)]
+    if let Some(parent) = path.parent() {
+        File::open(parent)?.sync_all()?;
+    }
+    #[cfg(not(unix))]
+    let _ = path;
+    Ok(())
+}
+
+/// An error saving or loading [`Storage`] values.
+#[derive(Debug, thiserror::Error)]
+pub enum StorageError {
+    #[error("{0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("{0}")]
+    Json(#[from] serde_json::Error),
+
+    #[cfg(feature = "wincode")]
+    #[error("{0}")]
+    WincodeWrite(#[from] wincode::WriteError),
+
+    #[cfg(feature = "wincode")]
+    #[error("{0}")]
+    WincodeRead(#[from] wincode::ReadError),
+
+    #[error("invalid storage key [{0}]")]
+    InvalidKey(String),
+}
+
+#[cfg(feature = "lua")]
+impl From<StorageError> for mlua::Error {
+    #[inline]
+    fn from(value: StorageError) -> Self {
+        mlua::Error::external(value)
+    }
+}
+
+#[cfg(feature = "lua")]
+impl mlua::FromLua for StorageDir {
+    fn from_lua(value: mlua::Value, lua: &mlua::Lua) -> mlua::Result<Self> {
+        let s = mlua::BorrowedStr::from_lua(value, lua)?;
+        Ok(match s.as_ref() {
+            "data" => Self::Data,
+            "config" => Self::Config,
+            "preferences" => Self::Preferences,
+            s => {
+                return Err(mlua::prelude::LuaError::runtime(format!(
+                    "invalid storage dir {s:?}"
+                )));
+            }
+        })
+    }
+}
+
+#[cfg(feature = "lua")]
+impl mlua::IntoLua for StorageDir {
+    fn into_lua(self, lua: &mlua::Lua) -> mlua::Result<mlua::Value> {
+        match self {
+            Self::Data => "data",
+            Self::Config => "config",
+            Self::Preferences => "preferences",
+        }
+        .into_lua(lua)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn save_and_backups() {
+        let root = std::env::temp_dir().join(format!("kero_storage_{}", std::process::id()));
+        let storage = Storage::with_root(&root);
+        storage.set_backups(2);
+
+        assert_eq!(
+            storage.load::<u32>(StorageDir::Data, "saves/slot").unwrap(),
+            None
+        );
+        for i in 0..4u32 {
+            storage.save(StorageDir::Data, "saves/slot", &i).unwrap();
+        }
+        assert_eq!(
+            storage.load(StorageDir::Data, "saves/slot").unwrap(),
+            Some(3u32)
+        );
+        assert_eq!(
+            storage
+                .load_backup(StorageDir::Data, "saves/slot", 0)
+                .unwrap(),
+            Some(2u32)
+        );
+        assert_eq!(
+            storage
+                .load_backup(StorageDir::Data, "saves/slot", 1)
+                .unwrap(),
+            Some(1u32)
+        );
+        assert_eq!(
+            storage
+                .load_backup::<u32>(StorageDir::Data, "saves/slot", 2)
+                .unwrap(),
+            None
+        );
+        assert!(storage.exists(StorageDir::Data, "saves/slot").unwrap());
+        assert!(!storage.exists(StorageDir::Config, "saves/slot").unwrap());
+
+        // lowering the backup count deletes the extra backups on the next save
+        storage.set_backups(3);
+        storage.save(StorageDir::Data, "saves/slot", &4u32).unwrap();
+        storage.set_backups(1);
+        storage.save(StorageDir::Data, "saves/slot", &5u32).unwrap();
+        assert_eq!(
+            storage
+                .load_backup::<u32>(StorageDir::Data, "saves/slot", 1)
+                .unwrap(),
+            None
+        );
+
+        // deleting removes backups past the current count too
+        storage.set_backups(3);
+        storage.save(StorageDir::Data, "saves/slot", &6u32).unwrap();
+        storage.save(StorageDir::Data, "saves/slot", &7u32).unwrap();
+        storage.set_backups(0);
+        storage.delete(StorageDir::Data, "saves/slot").unwrap();
+        assert!(!storage.exists(StorageDir::Data, "saves/slot").unwrap());
+        assert_eq!(
+            storage
+                .load_backup::<u32>(StorageDir::Data, "saves/slot", 0)
+                .unwrap(),
+            None
+        );
+
+        assert!(matches!(
+            storage.save(StorageDir::Data, "../escape", &0),
+            Err(StorageError::InvalidKey(_))
+        ));
+        let _ = fs::remove_dir_all(root);
+    }
+}
@@ -1,3 +1,4 @@
+use crate::core::Timestep;
 use crate::math::Float;
 use std::cell::Cell;
 use std::fmt::{Debug, Formatter};
@@ -22,6 +23,8 @@ impl Debug for Time {
 pub(crate) struct TimeState {
     pub target_fps: Cell<Option<f64>>,
     pub max_frame_skip: Cell<u32>,
+    pub timestep: Cell<Timestep>,
+    pub alpha: Cell<f32>,
     pub fps: Cell<u32>,
     pub delta: Cell<f32>,
     pub unfixed_delta: Cell<f32>,
@@ -35,6 +38,8 @@ impl Default for TimeState {
         Self {
             target_fps: Cell::new(Some(60.0)),
             max_frame_skip: Cell::new(0),
+            timestep: Cell::new(Timestep::Fixed),
+            alpha: Cell::new(0.0),
             fps: Cell::new(60),
             delta: Cell::new(1.0 / 60.0),
             unfixed_delta: Cell::new(0.0),
@@ -45,8 +50,11 @@ impl Default for TimeState {
 }
 
 impl Time {
-    pub(crate) fn new() -> Self {
-        Self(Rc::new(TimeState::default()))
+    pub(crate) fn new(timestep: Timestep) -> Self {
+        Self(Rc::new(TimeState {
+            timestep: Cell::new(timestep),
+            ..Default::default()
+        }))
     }
 
     #[inline]
@@ -69,6 +77,29 @@ impl Time {
         self.0.max_frame_skip.set(max);
     }
 
+    /// How the game loop advances time between updates.
+    #[inline]
+    pub fn timestep(&self) -> Timestep {
+        self.0.timestep.get()
+    }
+
+    /// Set how the game loop advances time between updates.
+    #[inline]
+    pub fn set_timestep(&self, timestep: Timestep) {
+        self.0.timestep.set(timestep);
+    }
+
+    /// How far the current frame is between the previous update and the next one, from `0.0`
+    /// to `1.0`. Rendering can interpolate between the previous and current state by this
+    /// amount to keep motion smooth when updates don't line up with frames.
+    ///
+    /// This is only meaningful with a [`Fixed`](Timestep::Fixed) timestep, with other
+    /// timesteps it is always `1.0`.
+    #[inline]
+    pub fn alpha(&self) -> f32 {
+        self.0.alpha.get()
+    }
+
     /// FPS the app is running at.
     #[inline]
     pub fn fps(&self) -> u32 {
@@ -0,0 +1,30 @@
+/// How the game loop advances time between updates.
+///
+/// Set with [`GameBuilder::with_timestep`](super::GameBuilder::with_timestep) or
+/// [`Time::set_timestep`](super::Time::set_timestep).
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+pub enum Timestep {
+    /// Update at the target FPS with a fixed delta, running as many updates per frame as are
+    /// needed to keep up. Leftover time is reported by [`Time::alpha`](super::Time::alpha), so
+    /// rendering can interpolate between the previous and current update.
+    #[default]
+    Fixed,
+
+    /// Update once per frame, using the real time since the last frame as the delta.
+    Variable,
+
+    /// Update once per frame using the real time since the last frame, but split it into
+    /// multiple updates if it is longer than `max_delta` seconds. At most
+    /// [`max_frame_skip`](super::Time::max_frame_skip) `+ 1` updates run per frame, any time
+    /// beyond that is dropped.
+    SemiFixed { max_delta: f32 },
+}
+
+impl Timestep {
+    /// A [`SemiFixed`](Self::SemiFixed) timestep, or `None` if `max_delta` is not a positive
+    /// number of seconds.
+    #[inline]
+    pub fn semi_fixed(max_delta: f32) -> Option<Self> {
+        (max_delta.is_finite() && max_delta > 0.0).then_some(Self::SemiFixed { max_delta })
+    }
+}
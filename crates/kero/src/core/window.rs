@@ -1,19 +1,36 @@
 use dpi::{LogicalPosition, LogicalSize, PhysicalPosition, PhysicalSize};
 use std::fmt::{Debug, Formatter};
-use std::sync::Arc;
-use winit::window::{Cursor, Fullscreen, Window as WinitWindow};
+use std::sync::{Arc, Mutex};
+use winit::error::ExternalError;
+use winit::event_loop::ActiveEventLoop;
+use winit::window::{
+    BadIcon, BadImage, Cursor, CustomCursor, CustomCursorSource, Fullscreen, Icon,
+    Window as WinitWindow,
+};
 
-use crate::math::{Vec2I, Vec2U};
+use crate::grid::Grid;
+use crate::img::ImageRgba8;
+use crate::math::{RectF, Vec2I, Vec2U};
 
-use super::{CursorIcon, DisplayMode, Monitor, VideoMode};
+use super::{CursorGrab, CursorIcon, DisplayMode, Monitor, VideoMode};
 
 /// Handle to the window.
 ///
 /// This handle can be cloned and passed around freely to give objects access to the window.
 ///
-/// Obtained from [`Context`](super::Context).
+/// Obtained from [`Context`](super::Context). When running headless, there is no real window:
+/// the size and title are simulated, and calls that would affect the window do nothing.
 #[derive(Clone)]
-pub struct Window(pub(crate) Arc<WinitWindow>);
+pub struct Window(pub(crate) Arc<WindowInner>);
+
+pub(crate) enum WindowInner {
+    /// The window, and a custom cursor waiting to be created by the event loop.
+    Winit(Arc<WinitWindow>, Mutex<Option<CustomCursorSource>>),
+    Headless {
+        title: Mutex<String>,
+        size: Vec2U,
+    },
+}
 
 impl Debug for Window {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
@@ -22,22 +39,59 @@ impl Debug for Window {
 }
 
 impl Window {
+    #[inline]
+    pub(crate) fn new(window: WinitWindow) -> Self {
+        Self(Arc::new(WindowInner::Winit(
+            Arc::new(window),
+            Mutex::new(None),
+        )))
+    }
+
+    #[inline]
+    pub(crate) fn new_headless(title: &str, size: Vec2U) -> Self {
+        Self(Arc::new(WindowInner::Headless {
+            title: Mutex::new(title.to_string()),
+            size,
+        }))
+    }
+
+    /// The underlying winit window, or `None` if running headless.
+    #[inline]
+    pub(crate) fn winit(&self) -> Option<&Arc<WinitWindow>> {
+        match self.0.as_ref() {
+            WindowInner::Winit(window, _) => Some(window),
+            WindowInner::Headless { .. } => None,
+        }
+    }
+
+    /// If there is no real window because the game is running headless.
+    #[inline]
+    pub fn is_headless(&self) -> bool {
+        self.winit().is_none()
+    }
+
     /// The window title.
     #[inline]
     pub fn title(&self) -> String {
-        self.0.title()
+        match self.0.as_ref() {
+            WindowInner::Winit(window, _) => window.title(),
+            WindowInner::Headless { title, .. } => title.lock().unwrap().clone(),
+        }
     }
 
     /// Set the window title.
     #[inline]
     pub fn set_title(&self, title: &str) {
-        self.0.set_title(title);
+        match self.0.as_ref() {
+            WindowInner::Winit(window, _) => window.set_title(title),
+            WindowInner::Headless { title: dst, .. } => *dst.lock().unwrap() = title.to_string(),
+        }
     }
 
     /// The window scale factor.
     #[inline]
     pub fn scale_factor(&self) -> f32 {
-        self.0.scale_factor() as f32
+        self.winit().map_or(1.0, |w| w.scale_factor() as f32)
     }
 
     /// The inverse window scale factor.
@@ -49,28 +103,34 @@ impl Window {
     /// The monitor the window is on.
     #[inline]
     pub fn monitor(&self) -> Option<Monitor> {
-        self.0.current_monitor().map(Monitor)
+        self.winit()?.current_monitor().map(Monitor)
     }
 
     /// The primary monitor.
     #[inline]
     pub fn primary_monitor(&self) -> Option<Monitor> {
-        self.0.primary_monitor().map(Monitor)
+        self.winit()?.primary_monitor().map(Monitor)
     }
 
     /// All monitors.
     #[inline]
     pub fn monitors(&self) -> impl Iterator<Item = Monitor> {
-        self.0.available_monitors().map(Monitor)
+        self.winit()
+            .into_iter()
+            .flat_map(|w| w.available_monitors())
+            .map(Monitor)
     }
 
     /// Center the window on the selected monitor.
     #[inline]
     pub fn center_on(&self, monitor: &Monitor) {
+        let Some(window) = self.winit() else {
+            return;
+        };
         let mon_pos = monitor.0.position();
         let mon_size = monitor.0.size();
-        let win_size = self.0.outer_size();
-        self.0.set_outer_position(PhysicalPosition::new(
+        let win_size = window.outer_size();
+        window.set_outer_position(PhysicalPosition::new(
             mon_pos.x + ((mon_size.width - win_size.width) / 2) as i32,
             mon_pos.y + ((mon_size.height - win_size.height) / 2) as i32,
         ));
@@ -79,7 +139,7 @@ impl Window {
     /// The window display mode.
     #[inline]
     pub fn display_mode(&self) -> DisplayMode {
-        match self.0.fullscreen() {
+        match self.winit().and_then(|w| w.fullscreen()) {
             Some(Fullscreen::Exclusive(mode)) => DisplayMode::FullscreenExclusive(VideoMode(mode)),
             Some(Fullscreen::Borderless(monitor)) => {
                 DisplayMode::FullscreenBorderless(monitor.map(Monitor))
@@ -91,16 +151,18 @@ impl Window {
     /// Set the window display mode.
     #[inline]
     pub fn set_display_mode(&self, display_mode: DisplayMode) {
+        let Some(window) = self.winit() else {
+            return;
+        };
         match display_mode {
             DisplayMode::FullscreenExclusive(mode) => {
-                self.0.set_fullscreen(Some(Fullscreen::Exclusive(mode.0)));
+                window.set_fullscreen(Some(Fullscreen::Exclusive(mode.0)));
             }
             DisplayMode::FullscreenBorderless(monitor) => {
-                self.0
-                    .set_fullscreen(Some(Fullscreen::Borderless(monitor.map(|m| m.0))));
+                window.set_fullscreen(Some(Fullscreen::Borderless(monitor.map(|m| m.0))));
             }
             DisplayMode::Windowed(monitor) => {
-                self.0.set_fullscreen(None);
+                window.set_fullscreen(None);
                 if let Some(monitor) = monitor {
                     self.center_on(&monitor);
                 }
@@ -111,7 +173,7 @@ impl Window {
     /// If the window is in a fullscreen mode.
     #[inline]
     pub fn is_fullscreen(&self) -> bool {
-        self.0.fullscreen().is_some()
+        self.winit().is_some_and(|w| w.fullscreen().is_some())
     }
 
     /// Set the window to borderless fullscreen mode.
@@ -135,23 +197,24 @@ impl Window {
     /// If the window has focus.
     #[inline]
     pub fn has_focus(&self) -> bool {
-        self.0.has_focus()
+        self.winit().is_none_or(|w| w.has_focus())
     }
 
     /// The window position in pixels.
     #[inline]
     pub fn pixel_pos(&self) -> Option<Vec2I> {
-        self.0.inner_position().ok().map(Vec2I::from)
+        self.winit()?.inner_position().ok().map(Vec2I::from)
     }
 
     /// The DPI-independent window position.
     #[inline]
     pub fn pos(&self) -> Option<Vec2I> {
+        let window = self.winit()?;
         Some(
-            self.0
+            window
                 .inner_position()
                 .ok()?
-                .to_logical(self.0.scale_factor())
+                .to_logical(window.scale_factor())
                 .into(),
         )
     }
@@ -159,17 +222,18 @@ impl Window {
     /// The window outer position in pixels.
     #[inline]
     pub fn outer_pixel_pos(&self) -> Option<Vec2I> {
-        self.0.outer_position().ok().map(Vec2I::from)
+        self.winit()?.outer_position().ok().map(Vec2I::from)
     }
 
     /// The DPI-independent window outer position.
     #[inline]
     pub fn outer_pos(&self) -> Option<Vec2I> {
+        let window = self.winit()?;
         Some(
-            self.0
+            window
                 .outer_position()
                 .ok()?
-                .to_logical(self.0.scale_factor())
+                .to_logical(window.scale_factor())
                 .into(),
         )
     }
@@ -178,58 +242,78 @@ impl Window {
     #[inline]
     pub fn set_outer_pos(&self, pos: impl Into<Vec2I>) {
         let pos = pos.into();
-        self.0
-            .set_outer_position(LogicalPosition::new(pos.x, pos.y));
+        if let Some(window) = self.winit() {
+            window.set_outer_position(LogicalPosition::new(pos.x, pos.y));
+        }
     }
 
     /// Set the window outer pixel position.
     #[inline]
     pub fn set_outer_pixel_pos(&self, pos: impl Into<Vec2I>) {
         let pos = pos.into();
-        self.0
-            .set_outer_position(PhysicalPosition::new(pos.x, pos.y));
+        if let Some(window) = self.winit() {
+            window.set_outer_position(PhysicalPosition::new(pos.x, pos.y));
+        }
     }
 
     /// The window pixel size.
     #[inline]
     pub fn pixel_size(&self) -> Vec2U {
-        self.0.inner_size().into()
+        match self.0.as_ref() {
+            WindowInner::Winit(window, _) => window.inner_size().into(),
+            WindowInner::Headless { size, .. } => *size,
+        }
     }
 
     /// The window DPI-independent size.
     #[inline]
     pub fn size(&self) -> Vec2U {
-        self.0.inner_size().to_logical(self.0.scale_factor()).into()
+        match self.0.as_ref() {
+            WindowInner::Winit(window, _) => {
+                window.inner_size().to_logical(window.scale_factor()).into()
+            }
+            WindowInner::Headless { size, .. } => *size,
+        }
     }
 
     /// Request a DPI-independent size for the window.
     #[inline]
     pub fn request_size(&self, size: impl Into<Vec2U>) -> bool {
         let size = size.into();
-        self.0
-            .request_inner_size(LogicalSize::new(size.x, size.y))
-            .is_none()
+        self.winit().is_some_and(|w| {
+            w.request_inner_size(LogicalSize::new(size.x, size.y))
+                .is_none()
+        })
     }
 
     /// Request a pixel size for the window.
     #[inline]
     pub fn request_pixel_size(&self, size: impl Into<Vec2U>) -> bool {
         let size = size.into();
-        self.0
-            .request_inner_size(PhysicalSize::new(size.x, size.y))
-            .is_none()
+        self.winit().is_some_and(|w| {
+            w.request_inner_size(PhysicalSize::new(size.x, size.y))
+                .is_none()
+        })
     }
 
     /// Outer pixel size of the window.
     #[inline]
     pub fn outer_pixel_size(&self) -> Vec2U {
-        self.0.outer_size().into()
+        match self.0.as_ref() {
+            WindowInner::Winit(window, _) => window.outer_size().into(),
+            WindowInner::Headless { size, .. } => *size,
+        }
     }
 
     /// Outer DPI-independent size of the window.
     #[inline]
     pub fn outer_size(&self) -> Vec2U {
-        self.0.outer_size().to_logical(self.0.scale_factor()).into()
+        match self.0.as_ref() {
+            WindowInner::Winit(window, _) => {
+                window.outer_size().to_logical(window.scale_factor()).into()
+            }
+            WindowInner::Headless { size, .. } => *size,
+        }
     }
 
     /// DPI-independent center of the window.
@@ -247,70 +331,203 @@ impl Window {
     /// If the window can be resized by the user.
     #[inline]
     pub fn resizable(&self) -> bool {
-        self.0.is_resizable()
+        self.winit().is_some_and(|w| w.is_resizable())
     }
 
     /// Set if the window can be resized by the user.
     #[inline]
     pub fn set_resizable(&self, resizable: bool) {
-        self.0.set_resizable(resizable);
+        if let Some(window) = self.winit() {
+            window.set_resizable(resizable);
+        }
     }
 
     /// If the window is maximized.
     #[inline]
     pub fn maximized(&self) -> bool {
-        self.0.is_maximized()
+        self.winit().is_some_and(|w| w.is_maximized())
     }
 
     /// Set if the window is maximized.
     #[inline]
     pub fn set_maximized(&self, maximized: bool) {
-        self.0.set_maximized(maximized);
+        if let Some(window) = self.winit() {
+            window.set_maximized(maximized);
+        }
     }
 
     /// If the window is minimized.
     #[inline]
     pub fn minimized(&self) -> Option<bool> {
-        self.0.is_minimized()
+        self.winit()?.is_minimized()
     }
 
     /// Set if the window is minimized.
     #[inline]
     pub fn set_minimized(&self, minimized: bool) {
-        self.0.set_minimized(minimized);
+        if let Some(window) = self.winit() {
+            window.set_minimized(minimized);
+        }
     }
 
     /// Set the window's minimum inner DPI-independent size.
     #[inline]
     pub fn set_min_inner_size(&self, size: impl Into<Option<Vec2U>>) {
-        self.0
-            .set_min_inner_size(size.into().map(|s| LogicalSize::new(s.x, s.y)));
+        if let Some(window) = self.winit() {
+            window.set_min_inner_size(size.into().map(|s| LogicalSize::new(s.x, s.y)));
+        }
     }
 
     /// Set the window's minimum inner pixel size.
     #[inline]
     pub fn set_min_inner_pixel_size(&self, size: impl Into<Option<Vec2U>>) {
-        self.0
-            .set_min_inner_size(size.into().map(|s| LogicalSize::new(s.x, s.y)));
+        if let Some(window) = self.winit() {
+            window.set_min_inner_size(size.into().map(|s| LogicalSize::new(s.x, s.y)));
+        }
     }
 
     /// Set the window's maximum inner DPI-independent size.
     #[inline]
     pub fn set_max_inner_size(&self, size: impl Into<Option<Vec2U>>) {
-        self.0
-            .set_max_inner_size(size.into().map(|s| LogicalSize::new(s.x, s.y)));
+        if let Some(window) = self.winit() {
+            window.set_max_inner_size(size.into().map(|s| LogicalSize::new(s.x, s.y)));
+        }
     }
 
     /// Set the window's maximum inner pixel size.
     #[inline]
     pub fn set_max_inner_pixel_size(&self, size: impl Into<Option<Vec2U>>) {
-        self.0
-            .set_max_inner_size(size.into().map(|s| LogicalSize::new(s.x, s.y)));
+        if let Some(window) = self.winit() {
+            window.set_max_inner_size(size.into().map(|s| LogicalSize::new(s.x, s.y)));
+        }
+    }
+
+    /// Set whether the window accepts input from an IME (input method editor), which is needed
+    /// to type languages like Japanese, Chinese, or Korean. This should be enabled while a text
+    /// field has focus and disabled otherwise, since an active IME can swallow key presses.
+    ///
+    /// Composition and committed text are available from [`Keyboard`](crate::input::Keyboard).
+    #[inline]
+    pub fn set_ime_allowed(&self, allowed: bool) {
+        if let Some(window) = self.winit() {
+            window.set_ime_allowed(allowed);
+        }
+    }
+
+    /// Set the DPI-independent area of the window where text is being edited, so the IME can
+    /// place its candidate window next to it without covering it.
+    #[inline]
+    pub fn set_ime_cursor_area(&self, area: impl Into<RectF>) {
+        let area = area.into();
+        if let Some(window) = self.winit() {
+            window.set_ime_cursor_area(
+                LogicalPosition::new(area.x, area.y),
+                LogicalSize::new(area.w, area.h),
+            );
+        }
     }
 
     /// Set the cursor to display when the mouse is over the window.
     #[inline]
     pub fn set_cursor(&self, icon: CursorIcon) {
-        self.0.set_cursor(Cursor::Icon(icon.into()));
+        if let Some(window) = self.winit() {
+            window.set_cursor(Cursor::Icon(icon.into()));
+        }
+    }
+
+    /// Set the window icon. This is shown in the title bar or taskbar on some platforms.
+    pub fn set_icon(&self, icon: &ImageRgba8) -> Result<(), WindowError> {
+        let icon = image_to_icon(icon)?;
+        if let Some(window) = self.winit() {
+            window.set_window_icon(Some(icon));
+        }
+        Ok(())
+    }
+
+    /// Set a custom image to use as the cursor when the mouse is over the window. The hotspot is
+    /// the pixel in the image that is positioned on the mouse.
+    ///
+    /// Cursor images can be at most 2048×2048 pixels, and the hotspot must be inside the image.
+    pub fn set_custom_cursor(
+        &self,
+        image: &ImageRgba8,
+        hotspot: impl Into<Vec2U>,
+    ) -> Result<(), WindowError> {
+        let size = image.size().map(|n| u16::try_from(n).unwrap_or(u16::MAX));
+        let hotspot = hotspot.into().map(|n| u16::try_from(n).unwrap_or(u16::MAX));
+        let source = CustomCursor::from_rgba(image.bytes(), size.x, size.y, hotspot.x, hotspot.y)?;
+
+        // custom cursors can only be created by the event loop, so the cursor is applied when
+        // the next frame is rendered
+        if let WindowInner::Winit(_, cursor) = self.0.as_ref() {
+            *cursor.lock().unwrap() = Some(source);
+        }
+        Ok(())
+    }
+
+    /// Create and apply the custom cursor set since the last frame, if any.
+    pub(crate) fn apply_custom_cursor(&self, event_loop: &ActiveEventLoop) {
+        if let WindowInner::Winit(window, cursor) = self.0.as_ref()
+            && let Some(source) = cursor.lock().unwrap().take()
+        {
+            window.set_cursor(event_loop.create_custom_cursor(source));
+        }
+    }
+
+    /// Set whether the cursor is visible when the mouse is over the window.
+    #[inline]
+    pub fn set_cursor_visible(&self, visible: bool) {
+        if let Some(window) = self.winit() {
+            window.set_cursor_visible(visible);
+        }
+    }
+
+    /// Set how the cursor is grabbed by the window.
+    ///
+    /// Not every platform supports both grab modes, so if the requested mode isn't supported,
+    /// the other one will be used instead: a locked cursor falls back to being confined, and a
+    /// confined cursor falls back to being locked.
+    pub fn set_cursor_grab(&self, grab: CursorGrab) -> Result<(), WindowError> {
+        let Some(window) = self.winit() else {
+            return Ok(());
+        };
+        let fallback = match grab {
+            CursorGrab::None => None,
+            CursorGrab::Confined => Some(CursorGrab::Locked),
+            CursorGrab::Locked => Some(CursorGrab::Confined),
+        };
+        match (window.set_cursor_grab(grab.into()), fallback) {
+            (Err(ExternalError::NotSupported(_)), Some(fallback)) => {
+                Ok(window.set_cursor_grab(fallback.into())?)
+            }
+            (result, _) => Ok(result?),
+        }
+    }
+}
+
+/// Convert an image into a window icon.
+pub(crate) fn image_to_icon(image: &ImageRgba8) -> Result<Icon, WindowError> {
+    let size = image.size();
+    Ok(Icon::from_rgba(image.bytes().to_vec(), size.x, size.y)?)
+}
+
+/// An error changing the window.
+#[derive(Debug, thiserror::Error)]
+pub enum WindowError {
+    #[error("{0}")]
+    Icon(#[from] BadIcon),
+
+    #[error("{0}")]
+    Cursor(#[from] BadImage),
+
+    #[error("{0}")]
+    CursorGrab(#[from] ExternalError),
+}
+
+#[cfg(feature = "lua")]
+impl From<WindowError> for mlua::Error {
+    #[inline]
+    fn from(value: WindowError) -> Self {
+        mlua::Error::external(value)
     }
 }
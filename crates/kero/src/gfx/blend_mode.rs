@@ -1,23 +1,17 @@
 use serde::{Deserialize, Serialize};
-use strum::{EnumCount, FromRepr, VariantArray};
-use wgpu::{BlendComponent, BlendFactor, BlendOperation, BlendState};
+use strum::{EnumCount, VariantArray};
+use wgpu::{BlendState, ColorWrites};
 
 /// Different blend mode types.
+///
+/// All drawing colors are premultiplied, which the presets expect, except for
+/// [`Straight`](Self::Straight).
+///
+/// [`COUNT`](EnumCount::COUNT), [`VARIANTS`](VariantArray::VARIANTS) and
+/// [`from_repr`](Self::from_repr) only cover the [`PRESETS`](Self::PRESETS), not
+/// [`Custom`](Self::Custom).
 #[derive(
-    Default,
-    Debug,
-    Copy,
-    Clone,
-    Eq,
-    PartialEq,
-    Ord,
-    PartialOrd,
-    Hash,
-    Serialize,
-    Deserialize,
-    EnumCount,
-    FromRepr,
-    VariantArray,
+    Default, Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize,
 )]
 pub enum BlendMode {
     /// Pixels are alpha-composited into the backdrop.
@@ -32,59 +26,367 @@ pub enum BlendMode {
 
     /// Pixels are multiplicatively blended into the backdrop.
     Multiply,
+
+    /// Pixels brighten the backdrop, the inverse of multiplying.
+    Screen,
+
+    /// Keeps the brightest of each color channel. Transparent pixels have a color of zero, so
+    /// they leave the backdrop unchanged.
+    Lighten,
+
+    /// Keeps the darkest of each color channel. Transparent pixels have a color of zero, so they
+    /// darken the backdrop too, which makes this best suited to opaque drawing.
+    Darken,
+
+    /// Pixels are alpha-composited into the backdrop, for colors that aren't premultiplied.
+    Straight,
+
+    /// Pixels erase the backdrop by their alpha, punching holes into it.
+    Erase,
+
+    /// Pixels replace the backdrop, including its alpha.
+    Replace,
+
+    /// Pixels replace only the backdrop's alpha, leaving its color channels unchanged.
+    AlphaOnly,
+
+    /// Color and alpha are blended separately with custom factors and operations, and only the
+    /// channels in `mask` are written.
+    Custom {
+        color: BlendComponent,
+        alpha: BlendComponent,
+        mask: ColorMask,
+    },
+}
+
+impl EnumCount for BlendMode {
+    const COUNT: usize = Self::PRESETS.len();
+}
+
+impl VariantArray for BlendMode {
+    const VARIANTS: &'static [Self] = &Self::PRESETS;
 }
 
-impl Into<BlendState> for BlendMode {
-    fn into(self) -> BlendState {
+impl BlendMode {
+    /// Every preset blend mode, in declaration order.
+    pub const PRESETS: [Self; 11] = [
+        Self::Normal,
+        Self::Add,
+        Self::Subtract,
+        Self::Multiply,
+        Self::Screen,
+        Self::Lighten,
+        Self::Darken,
+        Self::Straight,
+        Self::Erase,
+        Self::Replace,
+        Self::AlphaOnly,
+    ];
+
+    /// The preset at the index, or `None` if there isn't one.
+    #[inline]
+    pub const fn from_repr(discriminant: usize) -> Option<Self> {
+        if discriminant < Self::PRESETS.len() {
+            Some(Self::PRESETS[discriminant])
+        } else {
+            None
+        }
+    }
+
+    /// The color and alpha components of the blend mode.
+    pub const fn components(self) -> (BlendComponent, BlendComponent) {
+        use BlendFactor::*;
+        use BlendOp::*;
+        let over = BlendComponent::new(One, OneMinusSrcAlpha, Add);
+        match self {
+            Self::Normal => (over, over),
+            Self::Add => {
+                let add = BlendComponent::new(SrcAlpha, One, Add);
+                (add, add)
+            }
+            Self::Subtract => (
+                BlendComponent::new(One, One, ReverseSubtract),
+                BlendComponent::new(One, One, Add),
+            ),
+            Self::Multiply => {
+                let mul = BlendComponent::new(Dst, OneMinusSrcAlpha, Add);
+                (mul, mul)
+            }
+            Self::Screen => (BlendComponent::new(One, OneMinusSrc, Add), over),
+            Self::Lighten => (BlendComponent::new(One, One, Max), over),
+            Self::Darken => (BlendComponent::new(One, One, Min), over),
+            Self::Straight => (BlendComponent::new(SrcAlpha, OneMinusSrcAlpha, Add), over),
+            Self::Erase => {
+                let erase = BlendComponent::new(Zero, OneMinusSrcAlpha, Add);
+                (erase, erase)
+            }
+            Self::Replace | Self::AlphaOnly => {
+                let replace = BlendComponent::new(One, Zero, Add);
+                (replace, replace)
+            }
+            Self::Custom { color, alpha, .. } => (color, alpha),
+        }
+    }
+
+    /// Which channels the blend mode writes to.
+    pub const fn mask(self) -> ColorMask {
         match self {
-            Self::Normal => BlendState {
-                color: BlendComponent {
-                    src_factor: BlendFactor::One,
-                    dst_factor: BlendFactor::OneMinusSrcAlpha,
-                    operation: BlendOperation::Add,
-                },
-                alpha: BlendComponent {
-                    src_factor: BlendFactor::One,
-                    dst_factor: BlendFactor::OneMinusSrcAlpha,
-                    operation: BlendOperation::Add,
-                },
-            },
-            Self::Add => BlendState {
-                color: BlendComponent {
-                    src_factor: BlendFactor::SrcAlpha,
-                    dst_factor: BlendFactor::One,
-                    operation: BlendOperation::Add,
-                },
-                alpha: BlendComponent {
-                    src_factor: BlendFactor::SrcAlpha,
-                    dst_factor: BlendFactor::One,
-                    operation: BlendOperation::Add,
-                },
-            },
-            Self::Subtract => BlendState {
-                color: BlendComponent {
-                    src_factor: BlendFactor::One,
-                    dst_factor: BlendFactor::One,
-                    operation: BlendOperation::ReverseSubtract,
-                },
-                alpha: BlendComponent {
-                    src_factor: BlendFactor::One,
-                    dst_factor: BlendFactor::One,
-                    operation: BlendOperation::Add,
-                },
-            },
-            Self::Multiply => BlendState {
-                color: BlendComponent {
-                    src_factor: BlendFactor::Dst,
-                    dst_factor: BlendFactor::OneMinusSrcAlpha,
-                    operation: BlendOperation::Add,
-                },
-                alpha: BlendComponent {
-                    src_factor: BlendFactor::Dst,
-                    dst_factor: BlendFactor::OneMinusSrcAlpha,
-                    operation: BlendOperation::Add,
-                },
-            },
+            Self::AlphaOnly => ColorMask::ALPHA,
+            Self::Custom { mask, .. } => mask,
+            _ => ColorMask::ALL,
+        }
+    }
+}
+
+impl From<BlendMode> for BlendState {
+    fn from(value: BlendMode) -> Self {
+        let (color, alpha) = value.components();
+        BlendState {
+            color: color.into(),
+            alpha: alpha.into(),
+        }
+    }
+}
+
+/// How the source and destination are combined for either the color or alpha channels.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
+pub struct BlendComponent {
+    /// Factor the source (the pixel being drawn) is multiplied by.
+    pub src: BlendFactor,
+
+    /// Factor the destination (the backdrop) is multiplied by.
+    pub dst: BlendFactor,
+
+    /// Operation that combines the source and destination.
+    pub op: BlendOp,
+}
+
+impl BlendComponent {
+    /// Create a new blend component.
+    #[inline]
+    pub const fn new(src: BlendFactor, dst: BlendFactor, op: BlendOp) -> Self {
+        Self { src, dst, op }
+    }
+}
+
+impl From<BlendComponent> for wgpu::BlendComponent {
+    #[inline]
+    fn from(value: BlendComponent) -> Self {
+        // the factors of min and max must be one, since they are ignored anyway
+        let (src, dst) = match value.op {
+            BlendOp::Min | BlendOp::Max => (BlendFactor::One, BlendFactor::One),
+            _ => (value.src, value.dst),
+        };
+        wgpu::BlendComponent {
+            src_factor: src.into(),
+            dst_factor: dst.into(),
+            operation: value.op.into(),
+        }
+    }
+}
+
+/// What the source or destination is multiplied by when blending.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
+pub enum BlendFactor {
+    /// `0`
+    Zero,
+
+    /// `1`
+    One,
+
+    /// The source color.
+    Src,
+
+    /// `1 - src`
+    OneMinusSrc,
+
+    /// The source alpha.
+    SrcAlpha,
+
+    /// `1 - src.a`
+    OneMinusSrcAlpha,
+
+    /// The destination color.
+    Dst,
+
+    /// `1 - dst`
+    OneMinusDst,
+
+    /// The destination alpha.
+    DstAlpha,
+
+    /// `1 - dst.a`
+    OneMinusDstAlpha,
+
+    /// `min(src.a, 1 - dst.a)`, or `1` for alpha.
+    SrcAlphaSaturated,
+}
+
+impl From<BlendFactor> for wgpu::BlendFactor {
+    #[inline]
+    fn from(value: BlendFactor) -> Self {
+        match value {
+            BlendFactor::Zero => wgpu::BlendFactor::Zero,
+            BlendFactor::One => wgpu::BlendFactor::One,
+            BlendFactor::Src => wgpu::BlendFactor::Src,
+            BlendFactor::OneMinusSrc => wgpu::BlendFactor::OneMinusSrc,
+            BlendFactor::SrcAlpha => wgpu::BlendFactor::SrcAlpha,
+            BlendFactor::OneMinusSrcAlpha => wgpu::BlendFactor::OneMinusSrcAlpha,
+            BlendFactor::Dst => wgpu::BlendFactor::Dst,
+            BlendFactor::OneMinusDst => wgpu::BlendFactor::OneMinusDst,
+            BlendFactor::DstAlpha => wgpu::BlendFactor::DstAlpha,
+            BlendFactor::OneMinusDstAlpha => wgpu::BlendFactor::OneMinusDstAlpha,
+            BlendFactor::SrcAlphaSaturated => wgpu::BlendFactor::SrcAlphaSaturated,
+        }
+    }
+}
+
+impl BlendFactor {
+    /// Every blend factor, in declaration order.
+    pub const VARIANTS: [Self; 11] = [
+        Self::Zero,
+        Self::One,
+        Self::Src,
+        Self::OneMinusSrc,
+        Self::SrcAlpha,
+        Self::OneMinusSrcAlpha,
+        Self::Dst,
+        Self::OneMinusDst,
+        Self::DstAlpha,
+        Self::OneMinusDstAlpha,
+        Self::SrcAlphaSaturated,
+    ];
+}
+
+/// How the multiplied source and destination are combined when blending.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
+pub enum BlendOp {
+    /// `src + dst`
+    Add,
+
+    /// `src - dst`
+    Subtract,
+
+    /// `dst - src`
+    ReverseSubtract,
+
+    /// `min(src, dst)`, ignoring both factors.
+    Min,
+
+    /// `max(src, dst)`, ignoring both factors.
+    Max,
+}
+
+impl From<BlendOp> for wgpu::BlendOperation {
+    #[inline]
+    fn from(value: BlendOp) -> Self {
+        match value {
+            BlendOp::Add => wgpu::BlendOperation::Add,
+            BlendOp::Subtract => wgpu::BlendOperation::Subtract,
+            BlendOp::ReverseSubtract => wgpu::BlendOperation::ReverseSubtract,
+            BlendOp::Min => wgpu::BlendOperation::Min,
+            BlendOp::Max => wgpu::BlendOperation::Max,
+        }
+    }
+}
+
+impl BlendOp {
+    /// Every blend operation, in declaration order.
+    pub const VARIANTS: [Self; 5] = [
+        Self::Add,
+        Self::Subtract,
+        Self::ReverseSubtract,
+        Self::Min,
+        Self::Max,
+    ];
+}
+
+/// Which color channels are written to when drawing.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
+pub struct ColorMask {
+    /// If the red channel is written.
+    pub r: bool,
+
+    /// If the green channel is written.
+    pub g: bool,
+
+    /// If the blue channel is written.
+    pub b: bool,
+
+    /// If the alpha channel is written.
+    pub a: bool,
+}
+
+impl Default for ColorMask {
+    #[inline]
+    fn default() -> Self {
+        Self::ALL
+    }
+}
+
+impl ColorMask {
+    /// Write every channel.
+    pub const ALL: Self = Self::new(true, true, true, true);
+
+    /// Write no channels.
+    pub const NONE: Self = Self::new(false, false, false, false);
+
+    /// Write only the color channels.
+    pub const RGB: Self = Self::new(true, true, true, false);
+
+    /// Write only the alpha channel.
+    pub const ALPHA: Self = Self::new(false, false, false, true);
+
+    /// Create a new color mask.
+    #[inline]
+    pub const fn new(r: bool, g: bool, b: bool, a: bool) -> Self {
+        Self { r, g, b, a }
+    }
+}
+
+impl From<ColorMask> for ColorWrites {
+    #[inline]
+    fn from(value: ColorMask) -> Self {
+        let mut writes = ColorWrites::empty();
+        writes.set(ColorWrites::RED, value.r);
+        writes.set(ColorWrites::GREEN, value.g);
+        writes.set(ColorWrites::BLUE, value.b);
+        writes.set(ColorWrites::ALPHA, value.a);
+        writes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn min_max_factors_are_one() {
+        let mode = BlendMode::Custom {
+            color: BlendComponent::new(BlendFactor::SrcAlpha, BlendFactor::Zero, BlendOp::Max),
+            alpha: BlendComponent::new(BlendFactor::One, BlendFactor::Zero, BlendOp::Add),
+            mask: ColorMask::RGB,
+        };
+        let state: BlendState = mode.into();
+        assert_eq!(state.color.src_factor, wgpu::BlendFactor::One);
+        assert_eq!(state.color.dst_factor, wgpu::BlendFactor::One);
+        assert_eq!(state.alpha.dst_factor, wgpu::BlendFactor::Zero);
+
+        let writes: ColorWrites = mode.mask().into();
+        assert_eq!(writes, ColorWrites::COLOR);
+    }
+
+    #[test]
+    fn presets() {
+        assert_eq!(BlendMode::COUNT, BlendMode::VARIANTS.len());
+        assert!(
+            BlendMode::VARIANTS
+                .iter()
+                .all(|mode| !matches!(mode, BlendMode::Custom { .. }))
+        );
+        for (i, &mode) in BlendMode::VARIANTS.iter().enumerate() {
+            assert_eq!(BlendMode::from_repr(i), Some(mode));
         }
+        assert_eq!(BlendMode::from_repr(BlendMode::COUNT), None);
     }
 }
@@ -0,0 +1,99 @@
+use crate::math::Vec2U;
+use wgpu::{
+    CompareFunction, DepthBiasState, DepthStencilState, Device, Extent3d, StencilFaceState,
+    StencilOperation, StencilState, TextureDescriptor, TextureDimension, TextureFormat,
+    TextureUsages,
+};
+
+/// Format of all depth-stencil attachments.
+pub(crate) const DEPTH_STENCIL_FORMAT: TextureFormat = TextureFormat::Depth24PlusStencil8;
+
+/// Create a depth-stencil attachment for a render target of the size and sample count.
+pub(crate) fn create_depth_stencil(
+    device: &Device,
+    size: Vec2U,
+    sample_count: u32,
+) -> wgpu::Texture {
+    device.create_texture(&TextureDescriptor {
+        label: None,
+        size: Extent3d {
+            width: size.x.max(1),
+            height: size.y.max(1),
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count,
+        dimension: TextureDimension::D2,
+        format: DEPTH_STENCIL_FORMAT,
+        usage: TextureUsages::RENDER_ATTACHMENT,
+        view_formats: &[],
+    })
+}
+
+/// How a draw call interacts with the stencil buffer. The reference value it is compared against
+/// is stored on the draw call, so it doesn't require a separate pipeline.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub(crate) enum StencilMode {
+    /// The stencil is not tested or written.
+    Ignore,
+    /// Increment the stencil where it equals the reference, without writing any color.
+    Write,
+    /// Decrement the stencil where it equals the reference, without writing any color.
+    Erase,
+    /// Only draw where the stencil equals the reference.
+    Test,
+}
+
+/// Depth and stencil state of a draw call, used as part of its pipeline key.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct DepthStencil {
+    pub stencil: StencilMode,
+    pub depth_test: bool,
+}
+
+impl DepthStencil {
+    pub const NONE: Self = Self {
+        stencil: StencilMode::Ignore,
+        depth_test: false,
+    };
+
+    /// If the draw call writes only to the stencil buffer.
+    #[inline]
+    pub fn is_mask(&self) -> bool {
+        matches!(self.stencil, StencilMode::Write | StencilMode::Erase)
+    }
+
+    pub fn state(&self) -> DepthStencilState {
+        let (compare, pass_op) = match self.stencil {
+            StencilMode::Ignore => (CompareFunction::Always, StencilOperation::Keep),
+            StencilMode::Write => (CompareFunction::Equal, StencilOperation::IncrementClamp),
+            StencilMode::Erase => (CompareFunction::Equal, StencilOperation::DecrementClamp),
+            StencilMode::Test => (CompareFunction::Equal, StencilOperation::Keep),
+        };
+        let face = StencilFaceState {
+            compare,
+            fail_op: StencilOperation::Keep,
+            depth_fail_op: StencilOperation::Keep,
+            pass_op,
+        };
+
+        // masks are never depth tested, so they can't be hidden by what was drawn before them
+        let depth_test = self.depth_test && !self.is_mask();
+        DepthStencilState {
+            format: DEPTH_STENCIL_FORMAT,
+            depth_write_enabled: depth_test,
+            depth_compare: if depth_test {
+                CompareFunction::LessEqual
+            } else {
+                CompareFunction::Always
+            },
+            stencil: StencilState {
+                front: face,
+                back: face,
+                read_mask: 0xff,
+                write_mask: 0xff,
+            },
+            bias: DepthBiasState::default(),
+        }
+    }
+}
@@ -1,10 +1,10 @@
 use crate::color::{Rgba8, Rgba64F, ToRgba};
-use crate::core::Window;
 use crate::gfx::buffer_cache::BufferCache;
 use crate::gfx::{
     BindingValue, BlendMode, ColorMode, DrawCall, FilterMode, Font, IndexBuffer, RenderData,
-    RenderLayer, RenderPass, Sampler, Shader, SubTexture, Surface, Texture, Topology, UniformValue,
-    Vertex, VertexBuffer,
+    RenderLayer, RenderPass, Sampler, Shader, StencilMode, SubTexture, Surface, TextLayout,
+    TextOptions, Texture, Topology, UniformValue, Vertex, VertexBuffer, create_depth_stencil,
+    create_multisampled,
 };
 use crate::math::{
     Affine2F, Angle, CircleF, LineF, Mat2F, Mat3F, Mat4F, Numeric, PolygonF, QuadF, RadiansF,
@@ -16,7 +16,8 @@ use std::fmt::{Debug, Formatter};
 use std::mem::{replace, swap};
 use wgpu::{
     Color, CommandEncoderDescriptor, Device, IndexFormat, LoadOp, Operations, Queue,
-    RenderPassColorAttachment, RenderPassDescriptor, StoreOp, TextureViewDescriptor,
+    RenderPassColorAttachment, RenderPassDepthStencilAttachment, RenderPassDescriptor, StoreOp,
+    TextureViewDescriptor,
 };
 
 /// Rendering API.
@@ -24,10 +25,14 @@ pub struct Draw {
     cache: DrawCache,
     data: RenderData,
     pass: RenderPass,
+    window_redirect: Option<Surface>,
     layer: usize,
     matrix: Affine2F,
     matrix_stack: Vec<Affine2F>,
     clip_rect: Option<RectU>,
+    mask_stack: Vec<Mask>,
+    mask_rejected: bool,
+    frame: u64,
 }
 
 impl Debug for Draw {
@@ -42,14 +47,22 @@ impl Draw {
         device: Device,
         queue: Queue,
         default_shader: Shader,
+        sdf_shader: Shader,
         default_texture: Texture,
+        depth_stencil: bool,
+        window_sample_count: u32,
     ) -> Self {
         Self {
             cache: DrawCache {
                 device,
                 queue,
                 default_shader,
+                sdf_shader,
                 default_texture,
+                depth_stencil,
+                window_depth_stencil: None,
+                window_sample_count,
+                window_multisampled: None,
                 samplers: HashMap::new(),
                 buffer_cache: BufferCache::default(),
                 render_layer_vecs: Vec::new(),
@@ -60,15 +73,20 @@ impl Draw {
             },
             data: RenderData::new(),
             pass: RenderPass::new(None, None, Vec::new()),
+            window_redirect: None,
             layer: 0,
             matrix: Affine2F::IDENTITY,
             matrix_stack: Vec::new(),
             clip_rect: None,
+            mask_stack: Vec::new(),
+            mask_rejected: false,
+            frame: 0,
         }
     }
 
     pub(crate) fn begin_frame(&mut self, window_size: Vec2U) {
         self.cache.window_size = window_size;
+        self.frame += 1;
 
         // reset the buffer cache so the buffers can be reused
         self.cache.buffer_cache.reset();
@@ -91,6 +109,7 @@ impl Draw {
         // clear the data from the previous frame
         self.data.clear();
 
+        self.window_redirect = None;
         self.pass = RenderPass::new(
             None,
             Some(Rgba8::BLACK),
@@ -102,25 +121,17 @@ impl Draw {
         self.matrix = Affine2F::IDENTITY;
         self.matrix_stack.clear();
         self.clip_rect = None;
+        self.mask_stack.clear();
+        self.mask_rejected = false;
     }
 
-    pub(crate) fn end_frame(
-        &mut self,
-        frame: u64,
-        surface: &wgpu::Surface<'static>,
-        window: &Window,
-    ) {
+    pub(crate) fn end_frame(&mut self, frame: u64, target: &wgpu::Texture) {
         // if the current render pass has anything in it, finish and submit it
         let mut pass = replace(&mut self.pass, RenderPass::new(None, None, Vec::new()));
         if pass.finish(&mut self.cache) {
             self.data.passes.push(pass);
         }
 
-        // get the window surface
-        let window_surface = surface
-            .get_current_texture()
-            .expect("failed to acquire surface texture");
-
         // create the command encoder
         let mut encoder = self
             .cache
@@ -132,9 +143,7 @@ impl Draw {
             _ = encoder.begin_render_pass(&RenderPassDescriptor {
                 label: None,
                 color_attachments: &[Some(RenderPassColorAttachment {
-                    view: &window_surface
-                        .texture
-                        .create_view(&TextureViewDescriptor::default()),
+                    view: &target.create_view(&TextureViewDescriptor::default()),
                     depth_slice: None,
                     resolve_target: None,
                     ops: Operations {
@@ -148,32 +157,62 @@ impl Draw {
             });
         }
 
+        // make sure the window's attachments match its size
+        self.update_window_attachments(target);
+
         // perform the rest of our render passes
         for pass in &self.data.passes {
-            let surface_tex = if let Some(surface) = pass.surface.as_ref() {
-                surface.texture().0.texture.clone()
-            } else {
-                window_surface.texture.clone()
+            let (surface_tex, multisampled, depth_stencil) = match pass.surface.as_ref() {
+                Some(surface) => (
+                    surface.texture().0.texture.clone(),
+                    surface.multisampled(),
+                    surface.depth_stencil(),
+                ),
+                None => (
+                    target.clone(),
+                    self.cache.window_multisampled.as_ref(),
+                    self.cache.window_depth_stencil.as_ref(),
+                ),
             };
             let surface_format = surface_tex.format();
+            let sample_count = multisampled.map_or(1, |tex| tex.sample_count());
             let load = if let Some(clear_color) = pass.clear_color {
                 let Rgba64F { r, g, b, a } = clear_color.to_rgba();
                 LoadOp::Clear(Color { r, g, b, a })
             } else {
                 LoadOp::Load
             };
+
+            // multisampled drawing is resolved into the surface at the end of every pass
+            let view = surface_tex.create_view(&TextureViewDescriptor::default());
+            let multisampled =
+                multisampled.map(|tex| tex.create_view(&TextureViewDescriptor::default()));
+            let depth_stencil =
+                depth_stencil.map(|tex| tex.create_view(&TextureViewDescriptor::default()));
             let mut wgpu_pass = encoder.begin_render_pass(&RenderPassDescriptor {
                 label: None,
                 color_attachments: &[Some(RenderPassColorAttachment {
-                    view: &surface_tex.create_view(&TextureViewDescriptor::default()),
+                    view: multisampled.as_ref().unwrap_or(&view),
                     depth_slice: None,
-                    resolve_target: None,
+                    resolve_target: multisampled.as_ref().map(|_| &view),
                     ops: Operations {
                         load,
                         store: StoreOp::Store,
                     },
                 })],
-                depth_stencil_attachment: None,
+                depth_stencil_attachment: depth_stencil.as_ref().map(|view| {
+                    RenderPassDepthStencilAttachment {
+                        view,
+                        depth_ops: Some(Operations {
+                            load: LoadOp::Clear(1.0),
+                            store: StoreOp::Discard,
+                        }),
+                        stencil_ops: Some(Operations {
+                            load: LoadOp::Clear(0),
+                            store: StoreOp::Discard,
+                        }),
+                    }
+                }),
                 timestamp_writes: None,
                 occlusion_query_set: None,
             });
@@ -182,13 +221,23 @@ impl Draw {
             for layer in pass.layers.iter() {
                 // perform all the draw calls
                 for call in layer.calls.iter() {
+                    // masks can't be drawn without a stencil buffer
+                    if depth_stencil.is_none() && call.depth_stencil.is_mask() {
+                        continue;
+                    }
+
                     // set the render pipeline
                     wgpu_pass.set_pipeline(&call.shader.request_pipeline(
                         &self.cache.device,
                         call.topology,
                         surface_format,
                         call.blend_mode,
+                        depth_stencil.is_some().then_some(call.depth_stencil),
+                        sample_count,
                     ));
+                    if depth_stencil.is_some() {
+                        wgpu_pass.set_stencil_reference(call.stencil_ref);
+                    }
 
                     if let Some(RectU { x, y, w, h }) = call.clip_rect {
                         wgpu_pass.set_scissor_rect(x, y, w, h);
@@ -231,21 +280,45 @@ impl Draw {
         }
 
         self.cache.queue.submit([encoder.finish()]);
-        window.0.pre_present_notify();
-        window_surface.present();
-        window.0.request_redraw();
+    }
+
+    fn update_window_attachments(&mut self, target: &wgpu::Texture) {
+        let size = Vec2U::new(target.width(), target.height());
+        let fits = |tex: &Option<wgpu::Texture>| {
+            tex.as_ref()
+                .is_some_and(|tex| tex.width() == size.x && tex.height() == size.y)
+        };
+        let sample_count = self.cache.window_sample_count;
+        if sample_count > 1 && !fits(&self.cache.window_multisampled) {
+            self.cache.window_multisampled = Some(create_multisampled(
+                &self.cache.device,
+                size,
+                target.format(),
+                sample_count,
+            ));
+        }
+        if self.cache.depth_stencil && !fits(&self.cache.window_depth_stencil) {
+            self.cache.window_depth_stencil =
+                Some(create_depth_stencil(&self.cache.device, size, sample_count));
+        }
     }
 
     /// Set the target surface and optionally clear it with a single color. If `None` is passed
     /// as the surface, the window will be drawn to. If `None` is passed as the clear color, then
     /// the surface will not be cleared, drawing will instead be appended to its current pixels.
+    ///
+    /// The surface can't be changed while a mask is being written, see
+    /// [`push_mask`](Self::push_mask).
     #[inline]
     pub fn set_surface(
         &mut self,
         surface: impl Into<Option<Surface>>,
         clear_color: impl Into<Option<Rgba8>>,
     ) {
-        let surface = surface.into();
+        if self.reject_while_writing_mask() {
+            return;
+        }
+        let surface = surface.into().or_else(|| self.window_redirect.clone());
         let clear_color = clear_color.into();
         let mut prev = replace(
             &mut self.pass,
@@ -256,16 +329,37 @@ impl Draw {
             ),
         );
         self.pass.ensure_layer(self.layer, &mut self.cache);
+        self.mask_stack.clear();
         if prev.finish(&mut self.cache) {
             self.data.passes.push(prev);
         }
     }
 
+    /// The surface being drawn to, or `None` if drawing to the window.
+    #[inline]
+    pub fn surface(&self) -> Option<&Surface> {
+        self.pass.surface.as_ref()
+    }
+
+    /// Redirect drawing that targets the window into a surface, or pass `None` to draw to the
+    /// window again. While redirected, passing `None` to [`set_surface`](Self::set_surface) will
+    /// target the surface instead, so code that draws to the window can be captured without
+    /// changing it. Returns the previous redirect, so it can be restored afterwards.
+    ///
+    /// This does not change the current target, call [`set_surface`](Self::set_surface) after.
+    #[inline]
+    pub fn redirect_window(&mut self, surface: impl Into<Option<Surface>>) -> Option<Surface> {
+        replace(&mut self.window_redirect, surface.into())
+    }
+
     /// Set the target layer. For the most part you will be rendering to the default layer `0`,
     /// but in rare cases you may want to use layers to improve render batching.
+    ///
+    /// The layer can't be changed while a mask is being written, see
+    /// [`push_mask`](Self::push_mask).
     #[inline]
     pub fn set_layer(&mut self, layer: usize) {
-        if self.layer == layer {
+        if self.layer == layer || self.reject_while_writing_mask() {
             return;
         }
         self.layer = layer;
@@ -448,6 +542,150 @@ impl Draw {
             .set_scissor_rect(self.clip_rect, &mut self.cache);
     }
 
+    /// If the current surface has a depth-stencil buffer, which is required for masking and depth
+    /// testing. Surfaces get one when created with
+    /// [`create_depth_stencil_surface`](crate::gfx::Graphics::create_depth_stencil_surface), and
+    /// the window when the game is built
+    /// [`with_depth_stencil`](crate::core::GameBuilder::with_depth_stencil).
+    #[inline]
+    pub fn has_depth_stencil(&self) -> bool {
+        match self.pass.surface.as_ref() {
+            Some(surface) => surface.has_depth_stencil(),
+            None => self.cache.depth_stencil,
+        }
+    }
+
+    /// If depth testing is enabled.
+    #[inline]
+    pub fn depth_test(&mut self) -> bool {
+        self.pass.layer(self.layer).depth_stencil.depth_test
+    }
+
+    /// Enable or disable depth testing. While enabled, drawing only appears where its
+    /// [depth](Self::set_depth) is less than or equal to what was drawn there before, so it can
+    /// be sorted regardless of the order it was submitted in. Depth is written even where the
+    /// drawing is transparent, so this is best suited to opaque sprites.
+    ///
+    /// This has no effect if the surface has no depth-stencil buffer.
+    #[inline]
+    pub fn set_depth_test(&mut self, enabled: bool) {
+        self.pass
+            .layer(self.layer)
+            .set_depth_test(enabled, &mut self.cache);
+    }
+
+    /// The current depth.
+    #[inline]
+    pub fn depth(&mut self) -> f32 {
+        self.pass.layer(self.layer).depth
+    }
+
+    /// Set the depth of future drawing, from `0.0` (nearest) to `1.0` (farthest). This is only
+    /// used when [depth testing](Self::set_depth_test) is enabled.
+    #[inline]
+    pub fn set_depth(&mut self, value: f32) {
+        self.pass
+            .layer(self.layer)
+            .set_depth(value.clamp(0.0, 1.0), &mut self.cache);
+    }
+
+    /// Size of the mask stack.
+    #[inline]
+    pub fn mask_count(&self) -> usize {
+        self.mask_stack.len()
+    }
+
+    /// Push a mask to the top of the stack. Everything drawn inside `f` is written to the mask
+    /// instead of the surface, and until the mask is popped, future drawing will only appear
+    /// where it overlaps the mask. Nested masks are intersected with the masks beneath them.
+    ///
+    /// Masks belong to the current surface and layer, and the stack is cleared when the surface
+    /// changes. This fails if the surface has no [depth-stencil buffer](Self::has_depth_stencil).
+    ///
+    /// While the mask is being written, changing the surface or layer and popping the mask are
+    /// ignored, and this returns [`DrawError::WritingMask`] once the mask is finished.
+    pub fn push_mask(&mut self, f: impl FnOnce(&mut Draw)) -> Result<(), DrawError> {
+        self.begin_mask()?;
+        f(self);
+        self.end_mask()
+    }
+
+    pub(crate) fn begin_mask(&mut self) -> Result<(), DrawError> {
+        if !self.has_depth_stencil() {
+            return Err(DrawError::NoDepthStencil);
+        }
+        if self.mask_stack.len() >= MAX_MASKS {
+            return Err(DrawError::TooManyMasks);
+        }
+
+        // increment the stencil wherever the previous masks passed
+        let level = self.mask_stack.len().to_u32();
+        let layer = self.pass.layer(self.layer);
+        layer.set_stencil(StencilMode::Write, level, &mut self.cache);
+        self.mask_stack.push(Mask {
+            layer: self.layer,
+            start: layer.calls.len(),
+            calls: Vec::new(),
+            writing: true,
+        });
+        Ok(())
+    }
+
+    pub(crate) fn end_mask(&mut self) -> Result<(), DrawError> {
+        // the surface and layer can't change while writing, so the mask is still on top
+        let level = self.mask_stack.len().to_u32();
+        let mask = self.mask_stack.last_mut().unwrap();
+        let layer = self.pass.layer(mask.layer);
+
+        // test against the new mask, then keep its draw calls around so it can be erased
+        layer.set_stencil(StencilMode::Test, level, &mut self.cache);
+        mask.calls.extend_from_slice(&layer.calls[mask.start..]);
+        mask.writing = false;
+
+        if replace(&mut self.mask_rejected, false) {
+            return Err(DrawError::WritingMask);
+        }
+        Ok(())
+    }
+
+    /// If a mask is being written, record that a change to the target was rejected, so it can be
+    /// reported when the mask is finished.
+    fn reject_while_writing_mask(&mut self) -> bool {
+        if !self.mask_stack.iter().any(|mask| mask.writing) {
+            return false;
+        }
+        self.mask_rejected = true;
+        true
+    }
+
+    /// Pop the mask off the top of the stack, so drawing is only limited by the masks beneath it.
+    pub fn pop_mask(&mut self) -> Result<(), DrawError> {
+        // masks that are still being written can't be popped
+        if self.mask_stack.last().is_some_and(|mask| mask.writing) {
+            self.mask_rejected = true;
+            return Err(DrawError::WritingMask);
+        }
+        let mask = self.mask_stack.pop().ok_or(DrawError::NoMaskToPop)?;
+        let level = self.mask_stack.len().to_u32();
+        let layer = self.pass.layer(mask.layer);
+
+        // redraw the mask, decrementing the stencil back to where it was before
+        layer.set_stencil(StencilMode::Erase, level + 1, &mut self.cache);
+        for mut call in mask.calls {
+            call.depth_stencil.stencil = StencilMode::Erase;
+            call.stencil_ref = level + 1;
+            layer.calls.push(call);
+        }
+
+        let mode = if level == 0 {
+            StencilMode::Ignore
+        } else {
+            StencilMode::Test
+        };
+        layer.set_stencil(mode, level, &mut self.cache);
+        Ok(())
+    }
+
     /// Size of the transform stack.
     #[inline]
     pub fn transform_count(&self) -> usize {
@@ -987,7 +1225,8 @@ impl Draw {
         self.subtexture_at_ext(sub, pos, Rgba8::WHITE, ColorMode::MULT);
     }
 
-    /// Draw text with the provided font and size.
+    /// Draw text with the provided font and size. The position is the start of the first
+    /// line's baseline, and lines are separated by newlines.
     #[inline]
     pub fn text(
         &mut self,
@@ -996,6 +1235,42 @@ impl Draw {
         font: &Font,
         color: Rgba8,
         size: impl Into<Option<f32>>,
+    ) {
+        self.text_ext(text, pos, font, color, size, TextOptions::default());
+    }
+
+    /// Draw text with the provided font and size, wrapped and aligned by the options. The max
+    /// width is at the size the text is drawn.
+    pub fn text_ext(
+        &mut self,
+        text: &str,
+        pos: Vec2F,
+        font: &Font,
+        color: Rgba8,
+        size: impl Into<Option<f32>>,
+        options: TextOptions,
+    ) {
+        let size = size.into().unwrap_or(font.size());
+        let scale = size / font.size();
+        let options = TextOptions {
+            max_width: options.max_width.map(|w| w / scale),
+            ..options
+        };
+        let layout = TextLayout::new(font, text, options);
+        self.text_layout(&layout, pos, font, color, size);
+    }
+
+    /// Draw text that was laid out with the provided font, at the provided size.
+    ///
+    /// Fonts with [signed distance field](Font::sdf_from_ttf_file) glyphs are drawn with the
+    /// [`sdf_shader`](crate::gfx::Graphics::sdf_shader) while the default shader is set.
+    pub fn text_layout(
+        &mut self,
+        layout: &TextLayout,
+        pos: Vec2F,
+        font: &Font,
+        color: Rgba8,
+        size: impl Into<Option<f32>>,
     ) {
         let size = size.into().unwrap_or(font.size());
         let prev_sampler = self.main_sampler();
@@ -1010,20 +1285,55 @@ impl Draw {
             });
         }
 
+        // distance field fonts need their shader, unless a custom one has been set
+        let use_sdf = font.sdf_spread().is_some()
+            && self.pass.layer(self.layer).shader == self.cache.default_shader;
+        if use_sdf {
+            self.set_shader(self.cache.sdf_shader.clone());
+        }
+
         self.push_translation(pos);
-        self.push_scale_of(size / font.size());
 
-        let mut cursor = Vec2F::ZERO;
-        for chr in text.chars() {
-            if let Some(g) = font.glyph(chr) {
-                if let Some(sub) = g.sub.as_ref() {
-                    self.subtexture_at_ext(sub, cursor, color, ColorMode::MULT);
+        match font.cache() {
+            // cached glyphs are rasterized at the drawn size, so only their positions scale
+            Some(cache) => {
+                let scale = size / font.size();
+                for g in layout.glyphs() {
+                    let pos = g.pos * scale;
+                    match font.glyph(g.chr) {
+                        // glyphs set manually are still scaled like uncached ones
+                        Some(manual) => {
+                            if let Some(sub) = manual.sub.as_ref() {
+                                self.push_translation(pos);
+                                self.push_scale_of(scale);
+                                self.subtexture_at_ext(sub, Vec2F::ZERO, color, ColorMode::MULT);
+                                self.pop_transforms(2).unwrap();
+                            }
+                        }
+                        None => {
+                            if let Some(sub) = cache.glyph(g.chr, size, self.frame) {
+                                self.subtexture_at_ext(&sub, pos, color, ColorMode::MULT);
+                            }
+                        }
+                    }
+                }
+            }
+            None => {
+                self.push_scale_of(size / font.size());
+                for g in layout.glyphs() {
+                    if let Some(sub) = font.glyph(g.chr).and_then(|g| g.sub.as_ref()) {
+                        self.subtexture_at_ext(sub, g.pos, color, ColorMode::MULT);
+                    }
                 }
-                cursor.x += g.adv;
+                self.pop_transform().unwrap();
             }
         }
 
-        self.pop_transforms(2).unwrap();
+        self.pop_transform().unwrap();
+
+        if use_sdf {
+            self.set_shader(None);
+        }
 
         if prev_sampler.mag_filter != mag_filter {
             self.set_main_sampler(prev_sampler);
@@ -1080,7 +1390,12 @@ pub(crate) struct DrawCache {
     pub device: Device,
     pub queue: Queue,
     pub default_shader: Shader,
+    pub sdf_shader: Shader,
     pub default_texture: Texture,
+    pub depth_stencil: bool,
+    pub window_depth_stencil: Option<wgpu::Texture>,
+    pub window_sample_count: u32,
+    pub window_multisampled: Option<wgpu::Texture>,
     pub samplers: HashMap<Sampler, wgpu::Sampler>,
     pub buffer_cache: BufferCache,
     pub render_layer_vecs: Vec<Vec<RenderLayer>>,
@@ -1090,11 +1405,30 @@ pub(crate) struct DrawCache {
     pub window_size: Vec2U,
 }
 
+/// The stencil buffer holds 8 bits, so only this many masks can be stacked.
+const MAX_MASKS: usize = u8::MAX as usize;
+
+#[derive(Debug)]
+struct Mask {
+    layer: usize,
+    start: usize,
+    calls: Vec<DrawCall>,
+    writing: bool,
+}
+
 /// A drawing error.
 #[derive(Debug, Clone, thiserror::Error)]
 pub enum DrawError {
     #[error("no transform to pop")]
     NoTransformToPop,
+    #[error("no mask to pop")]
+    NoMaskToPop,
+    #[error("too many masks pushed")]
+    TooManyMasks,
+    #[error("the surface has no depth-stencil buffer")]
+    NoDepthStencil,
+    #[error("the surface, layer or mask stack was changed while writing a mask")]
+    WritingMask,
 }
 
 #[cfg(feature = "lua")]
@@ -1104,3 +1438,50 @@ impl From<DrawError> for mlua::Error {
         mlua::Error::external(value)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{GameBuilder, test_frame};
+    use crate::gfx::TextureFormat;
+
+    #[test]
+    fn masks() {
+        let builder = GameBuilder::new().unwrap().with_depth_stencil();
+        test_frame(builder, |ctx, draw| {
+            assert!(matches!(draw.pop_mask(), Err(DrawError::NoMaskToPop)));
+
+            // masks stack and pop
+            draw.push_mask(|draw| draw.rect((4.0, 4.0, 8.0, 8.0), Rgba8::WHITE))?;
+            draw.push_mask(|draw| draw.rect((0.0, 0.0, 8.0, 8.0), Rgba8::WHITE))?;
+            assert_eq!(draw.mask_count(), 2);
+            draw.rect((0.0, 0.0, 16.0, 16.0), Rgba8::RED);
+            draw.pop_mask()?;
+            assert_eq!(draw.mask_count(), 1);
+            draw.pop_mask()?;
+            assert_eq!(draw.mask_count(), 0);
+
+            // changing the target or popping while writing is rejected
+            let surface = ctx.graphics.create_surface((8, 8), TextureFormat::Rgba8);
+            let err = draw.push_mask(|draw| draw.set_surface(surface, None));
+            assert!(matches!(err, Err(DrawError::WritingMask)));
+            assert_eq!(draw.mask_count(), 1);
+            let err = draw.push_mask(|draw| draw.set_layer(1));
+            assert!(matches!(err, Err(DrawError::WritingMask)));
+            let err = draw.push_mask(|draw| assert!(draw.pop_mask().is_err()));
+            assert!(matches!(err, Err(DrawError::WritingMask)));
+            assert_eq!(draw.mask_count(), 3);
+            draw.pop_mask()?;
+            draw.pop_mask()?;
+            draw.pop_mask()?;
+
+            // surfaces without a depth-stencil buffer can't be masked
+            let surface = ctx.graphics.create_surface((8, 8), TextureFormat::Rgba8);
+            draw.set_surface(surface, Rgba8::BLACK);
+            let err = draw.push_mask(|_| {});
+            assert!(matches!(err, Err(DrawError::NoDepthStencil)));
+            assert_eq!(draw.mask_count(), 0);
+            Ok(())
+        });
+    }
+}
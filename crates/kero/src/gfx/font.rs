@@ -1,6 +1,6 @@
-use crate::gfx::{Graphics, Texture, TexturePacker};
+use crate::gfx::{FontMetrics, GlyphCache, Graphics, Texture, TexturePacker, TexturePixel};
 use crate::prelude::SubTexture;
-use fey_font::Font as FeyFont;
+use fey_font::{Font as FeyFont, Glyph as FeyGlyph, RasterizedGlyph};
 use fey_math::Vec2F;
 use fnv::FnvHashMap;
 use std::fmt::{Debug, Formatter};
@@ -17,8 +17,13 @@ pub type FontMut = mlua::UserDataRefMut<Font>;
 pub struct Font {
     size: f32,
     pixelated: bool,
+    sdf_spread: Option<f32>,
+    ascent: f32,
+    descent: f32,
+    line_gap: f32,
     glyphs: FnvHashMap<char, Glyph>,
     kerning: FnvHashMap<(char, char), f32>,
+    cache: Option<GlyphCache>,
 }
 
 impl Debug for Font {
@@ -36,12 +41,18 @@ pub(crate) struct Glyph {
 }
 
 impl Font {
+    /// Create a new empty font. Its ascent is the size until its metrics are set.
     pub fn new(size: f32, pixelated: bool) -> Self {
         Self {
             size,
             pixelated,
+            sdf_spread: None,
+            ascent: size,
+            descent: 0.0,
+            line_gap: 0.0,
             glyphs: FnvHashMap::default(),
             kerning: FnvHashMap::default(),
+            cache: None,
         }
     }
 
@@ -53,7 +64,7 @@ impl Font {
         chars: impl IntoIterator<Item = char>,
     ) -> Result<Option<(Self, Texture)>, fey_font::FontError> {
         let font = FeyFont::from_slice(font, size)?;
-        Ok(Self::pack(gfx, font, pixelated, chars))
+        Ok(Self::pack_coverage(gfx, font, pixelated, chars))
     }
 
     pub fn from_ttf_file(
@@ -64,16 +75,80 @@ impl Font {
         chars: impl IntoIterator<Item = char>,
     ) -> Result<Option<(Self, Texture)>, fey_font::FontError> {
         let font = FeyFont::from_file(path, size)?;
-        Ok(Self::pack(gfx, font, pixelated, chars))
+        Ok(Self::pack_coverage(gfx, font, pixelated, chars))
     }
 
-    fn pack(
+    /// Load a font whose glyphs are rasterized as signed distance fields, which stay sharp at
+    /// any size when drawn. The field reaches `spread` pixels past each glyph's outline, which
+    /// limits how far outlines, shadows, and glows can go. Multi-channel fields keep corners
+    /// sharp, but use twice the memory.
+    ///
+    /// Drawing the font will use the [`sdf_shader`](Graphics::sdf_shader) unless another
+    /// shader is set.
+    pub fn sdf_from_ttf_bytes(
+        gfx: &Graphics,
+        font: &[u8],
+        size: f32,
+        spread: f32,
+        multi_channel: bool,
+        chars: impl IntoIterator<Item = char>,
+    ) -> Result<Option<(Self, Texture)>, fey_font::FontError> {
+        let font = FeyFont::from_slice(font, size)?;
+        Ok(Self::pack_sdf(gfx, font, spread, multi_channel, chars))
+    }
+
+    /// Load a font whose glyphs are rasterized as signed distance fields. See
+    /// [`sdf_from_ttf_bytes`](Self::sdf_from_ttf_bytes).
+    pub fn sdf_from_ttf_file(
+        gfx: &Graphics,
+        path: impl AsRef<Path>,
+        size: f32,
+        spread: f32,
+        multi_channel: bool,
+        chars: impl IntoIterator<Item = char>,
+    ) -> Result<Option<(Self, Texture)>, fey_font::FontError> {
+        let font = FeyFont::from_file(path, size)?;
+        Ok(Self::pack_sdf(gfx, font, spread, multi_channel, chars))
+    }
+
+    fn pack_coverage(
+        gfx: &Graphics,
+        font: FeyFont<'_>,
+        pixelated: bool,
+        chars: impl IntoIterator<Item = char>,
+    ) -> Option<(Self, Texture)> {
+        Self::pack(gfx, font, pixelated, None, chars, |g| match pixelated {
+            true => g.rasterize_pixelated(),
+            false => g.rasterize_smooth(),
+        })
+    }
+
+    fn pack_sdf(
+        gfx: &Graphics,
+        font: FeyFont<'_>,
+        spread: f32,
+        multi_channel: bool,
+        chars: impl IntoIterator<Item = char>,
+    ) -> Option<(Self, Texture)> {
+        match multi_channel {
+            true => Self::pack(gfx, font, false, Some(spread), chars, |g| {
+                g.rasterize_msdf(spread)
+            }),
+            false => Self::pack(gfx, font, false, Some(spread), chars, |g| {
+                g.rasterize_sdf(spread)
+            }),
+        }
+    }
+
+    fn pack<P: TexturePixel>(
         gfx: &Graphics,
         font: FeyFont<'_>,
         pixelated: bool,
+        sdf_spread: Option<f32>,
         chars: impl IntoIterator<Item = char>,
+        rasterize: impl Fn(&FeyGlyph) -> Option<RasterizedGlyph<P>>,
     ) -> Option<(Self, Texture)> {
-        let mut packer = TexturePacker::new();
+        let mut packer = TexturePacker::<usize, P>::new();
 
         // rasterize and pack all glyphs, collect their char/advance/offset
         let chars: Vec<(char, f32, Vec2F)> = chars
@@ -81,11 +156,7 @@ impl Font {
             .enumerate()
             .map(|(i, chr)| {
                 let g = font.char_glyph(chr);
-                let raster = match pixelated {
-                    true => g.rasterize_pixelated(),
-                    false => g.rasterize_smooth(),
-                };
-                let off = match raster {
+                let off = match rasterize(&g) {
                     Some(raster) => {
                         packer.add_image(i, raster.image, None, None);
                         raster.offset
@@ -129,8 +200,13 @@ impl Font {
             Self {
                 size: font.size(),
                 pixelated,
+                sdf_spread,
+                ascent: font.ascent(),
+                descent: font.descent(),
+                line_gap: font.line_gap(),
                 glyphs,
                 kerning,
+                cache: None,
             },
             tex,
         ))
@@ -146,6 +222,50 @@ impl Font {
         self.pixelated
     }
 
+    /// If the glyphs are signed distance fields, how many pixels the fields reach past their
+    /// outlines.
+    #[inline]
+    pub fn sdf_spread(&self) -> Option<f32> {
+        self.sdf_spread
+    }
+
+    /// How far the font extends above the baseline.
+    #[inline]
+    pub fn ascent(&self) -> f32 {
+        self.ascent
+    }
+
+    /// How far the font extends below the baseline, which is usually negative.
+    #[inline]
+    pub fn descent(&self) -> f32 {
+        self.descent
+    }
+
+    /// Extra space between the bottom of one line and the top of the next.
+    #[inline]
+    pub fn line_gap(&self) -> f32 {
+        self.line_gap
+    }
+
+    /// The font's height (`ascent - descent`).
+    #[inline]
+    pub fn height(&self) -> f32 {
+        self.ascent - self.descent
+    }
+
+    /// The distance between the baselines of two lines.
+    #[inline]
+    pub fn line_height(&self) -> f32 {
+        self.height() + self.line_gap
+    }
+
+    #[inline]
+    pub fn set_metrics(&mut self, ascent: f32, descent: f32, line_gap: f32) {
+        self.ascent = ascent;
+        self.descent = descent;
+        self.line_gap = line_gap;
+    }
+
     #[inline]
     pub fn set_glyph(&mut self, chr: char, sub: Option<SubTexture>, adv: f32) {
         self.glyphs.insert(chr, Glyph { sub, adv });
@@ -163,6 +283,55 @@ impl Font {
 
     #[inline]
     pub fn kerning(&self, left: char, right: char) -> Option<f32> {
-        self.kerning.get(&(left, right)).copied()
+        FontMetrics::kerning(self, left, right)
+    }
+
+    /// The glyph cache this font draws from, if it was created by one.
+    #[inline]
+    pub fn cache(&self) -> Option<&GlyphCache> {
+        self.cache.as_ref()
+    }
+
+    /// Draw glyphs from the cache, which is used for any glyphs that weren't set manually.
+    #[inline]
+    pub fn set_cache(&mut self, cache: impl Into<Option<GlyphCache>>) {
+        self.cache = cache.into();
+    }
+}
+
+impl FontMetrics for Font {
+    #[inline]
+    fn ascent(&self) -> f32 {
+        self.ascent
+    }
+
+    #[inline]
+    fn descent(&self) -> f32 {
+        self.descent
+    }
+
+    #[inline]
+    fn line_gap(&self) -> f32 {
+        self.line_gap
+    }
+
+    #[inline]
+    fn advance(&self, chr: char) -> Option<f32> {
+        match self.glyphs.get(&chr) {
+            Some(g) => Some(g.adv),
+            None => self.cache.as_ref().map(|c| c.advance(chr, self.size)),
+        }
+    }
+
+    #[inline]
+    fn kerning(&self, left: char, right: char) -> Option<f32> {
+        match self.kerning.get(&(left, right)) {
+            Some(&kern) => Some(kern),
+            None => self
+                .cache
+                .as_ref()
+                .map(|c| c.kerning(left, right, self.size))
+                .filter(|&kern| kern != 0.0),
+        }
     }
 }
@@ -0,0 +1,385 @@
+use crate::gfx::{Font, Graphics, SubTexture, Texture};
+use crate::grid::Grid;
+use crate::math::{Numeric, RectU, Vec2U, vec2};
+use fey_color::GreyAlpha8;
+use fey_font::{Font as FeyFont, FontError};
+use fnv::FnvHashMap;
+use std::cell::RefCell;
+use std::fmt::{Debug, Formatter};
+use std::path::Path;
+use std::rc::Rc;
+
+#[cfg(feature = "lua")]
+pub type GlyphCacheObj = fey_lua::UserDataOf<GlyphCache>;
+#[cfg(feature = "lua")]
+pub type GlyphCacheRef = mlua::UserDataRef<GlyphCache>;
+
+/// Rasterizes glyphs from a TTF font the first time they are drawn.
+///
+/// Glyphs are packed into atlas pages, and new pages are added as they fill up. Once the
+/// cache reaches its page limit, the page that has gone the longest without being drawn is
+/// cleared to make room. A page that was drawn from this frame is never cleared, so if every
+/// page is in use the cache grows past its limit instead.
+///
+/// One cache can serve several sizes of the same face, each used through a [`Font`] created
+/// with [`font`](GlyphCache::font). Cached fonts draw their glyphs at the size they are
+/// drawn at, so text stays crisp when scaled with the `size` parameter of [`Draw::text`].
+///
+/// [`Draw::text`]: crate::gfx::Draw::text
+#[derive(Clone)]
+pub struct GlyphCache(Rc<RefCell<Inner>>);
+
+impl Debug for GlyphCache {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("GlyphCache").finish_non_exhaustive()
+    }
+}
+
+impl PartialEq for GlyphCache {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+struct Inner {
+    gfx: Graphics,
+    font: FeyFont<'static>,
+    pixelated: bool,
+    page_size: u32,
+    max_pages: usize,
+    pages: Vec<Page>,
+    glyphs: FnvHashMap<(char, u32), Entry>,
+}
+
+struct Entry {
+    sub: Option<SubTexture>,
+    page: usize,
+}
+
+struct Page {
+    texture: Texture,
+    shelves: Shelves,
+    last_used: u64,
+}
+
+/// Padding between glyphs, so they don't bleed into each other when filtered.
+const PADDING: u32 = 1;
+
+impl GlyphCache {
+    /// The default width and height of each atlas page.
+    pub const DEFAULT_PAGE_SIZE: u32 = 1024;
+
+    /// The default number of pages the cache can have before it starts clearing them.
+    pub const DEFAULT_MAX_PAGES: usize = 4;
+
+    /// Create a glyph cache for the font. Pixelated glyphs are either fully transparent or
+    /// fully opaque.
+    pub fn new(gfx: &Graphics, font: FeyFont<'static>, pixelated: bool) -> Self {
+        Self(Rc::new(RefCell::new(Inner {
+            gfx: gfx.clone(),
+            font,
+            pixelated,
+            page_size: Self::DEFAULT_PAGE_SIZE.min(gfx.max_texture_size()),
+            max_pages: Self::DEFAULT_MAX_PAGES,
+            pages: Vec::new(),
+            glyphs: FnvHashMap::default(),
+        })))
+    }
+
+    pub fn from_ttf_bytes(gfx: &Graphics, font: &[u8], pixelated: bool) -> Result<Self, FontError> {
+        let font = FeyFont::from_vec(font.to_vec(), 1.0)?;
+        Ok(Self::new(gfx, font, pixelated))
+    }
+
+    pub fn from_ttf_file(
+        gfx: &Graphics,
+        path: impl AsRef<Path>,
+        pixelated: bool,
+    ) -> Result<Self, FontError> {
+        let font = FeyFont::from_file(path, 1.0)?;
+        Ok(Self::new(gfx, font, pixelated))
+    }
+
+    /// Create a font of the provided size that draws its glyphs from this cache.
+    pub fn font(&self, size: f32) -> Font {
+        let mut inner = self.0.borrow_mut();
+        inner.font.set_size(size);
+        let mut font = Font::new(size, inner.pixelated);
+        font.set_metrics(
+            inner.font.ascent(),
+            inner.font.descent(),
+            inner.font.line_gap(),
+        );
+        font.set_cache(self.clone());
+        font
+    }
+
+    #[inline]
+    pub fn pixelated(&self) -> bool {
+        self.0.borrow().pixelated
+    }
+
+    /// The width and height of each atlas page.
+    #[inline]
+    pub fn page_size(&self) -> u32 {
+        self.0.borrow().page_size
+    }
+
+    /// Set the width and height of each atlas page. This clears the cache.
+    pub fn set_page_size(&self, size: u32) {
+        let mut inner = self.0.borrow_mut();
+        inner.page_size = size.clamp(1, inner.gfx.max_texture_size());
+        inner.pages.clear();
+        inner.glyphs.clear();
+    }
+
+    /// How many pages the cache can have before it starts clearing them.
+    #[inline]
+    pub fn max_pages(&self) -> usize {
+        self.0.borrow().max_pages
+    }
+
+    /// Set how many pages the cache can have before it starts clearing them.
+    #[inline]
+    pub fn set_max_pages(&self, max_pages: usize) {
+        self.0.borrow_mut().max_pages = max_pages.max(1);
+    }
+
+    /// How many atlas pages the cache currently has.
+    #[inline]
+    pub fn page_count(&self) -> usize {
+        self.0.borrow().pages.len()
+    }
+
+    /// The texture of each atlas page.
+    #[inline]
+    pub fn pages(&self) -> Vec<Texture> {
+        self.0
+            .borrow()
+            .pages
+            .iter()
+            .map(|page| page.texture.clone())
+            .collect()
+    }
+
+    /// How many glyphs are currently cached, across all sizes.
+    #[inline]
+    pub fn glyph_count(&self) -> usize {
+        self.0.borrow().glyphs.len()
+    }
+
+    /// Remove all glyphs and pages from the cache.
+    #[inline]
+    pub fn clear(&self) {
+        let mut inner = self.0.borrow_mut();
+        inner.pages.clear();
+        inner.glyphs.clear();
+    }
+
+    /// Rasterize the characters at the provided size ahead of time, so drawing them later
+    /// doesn't have to.
+    pub fn prepare(&self, size: f32, chars: impl IntoIterator<Item = char>) {
+        let mut inner = self.0.borrow_mut();
+        for chr in chars {
+            inner.glyph(chr, size, 0);
+        }
+    }
+
+    /// Get the glyph of the character at the provided size, rasterizing it if it isn't cached
+    /// yet. The frame is used to know which pages have been drawn from recently.
+    #[inline]
+    pub(crate) fn glyph(&self, chr: char, size: f32, frame: u64) -> Option<SubTexture> {
+        self.0.borrow_mut().glyph(chr, size, frame)
+    }
+
+    #[inline]
+    pub(crate) fn advance(&self, chr: char, size: f32) -> f32 {
+        let mut inner = self.0.borrow_mut();
+        inner.font.set_size(size);
+        inner.font.char_glyph(chr).advance()
+    }
+
+    #[inline]
+    pub(crate) fn kerning(&self, left: char, right: char, size: f32) -> f32 {
+        let mut inner = self.0.borrow_mut();
+        inner.font.set_size(size);
+        inner.font.char_kerning(left, right)
+    }
+}
+
+impl Inner {
+    fn glyph(&mut self, chr: char, size: f32, frame: u64) -> Option<SubTexture> {
+        let key = (chr, size.to_bits());
+        if let Some(entry) = self.glyphs.get(&key) {
+            if entry.sub.is_some() {
+                let page = &mut self.pages[entry.page];
+                page.last_used = page.last_used.max(frame);
+            }
+            return entry.sub.clone();
+        }
+
+        // rasterize the glyph, glyphs without an image (like spaces) are cached as empty
+        self.font.set_size(size);
+        let glyph = self.font.char_glyph(chr);
+        let raster = match self.pixelated {
+            true => glyph.rasterize_pixelated(),
+            false => glyph.rasterize_smooth(),
+        };
+        let Some(raster) = raster else {
+            self.glyphs.insert(key, Entry { sub: None, page: 0 });
+            return None;
+        };
+
+        // find a spot for the glyph, or skip it if it could never fit in a page
+        let size = raster.image.size();
+        let (page, pos) = self.allocate(size + Vec2U::splat(PADDING * 2), frame)?;
+        let rect = RectU::pos_size(pos + Vec2U::splat(PADDING), size);
+        let texture = self.pages[page].texture.clone();
+        texture.upload_img_region(rect, &raster.image).unwrap();
+
+        let offset = vec2(raster.offset.x, -raster.offset.y);
+        let sub = SubTexture::new_ext(texture, rect.to_f32(), offset, size.to_f32());
+        self.pages[page].last_used = self.pages[page].last_used.max(frame);
+        self.glyphs.insert(
+            key,
+            Entry {
+                sub: Some(sub.clone()),
+                page,
+            },
+        );
+        Some(sub)
+    }
+
+    fn allocate(&mut self, size: Vec2U, frame: u64) -> Option<(usize, Vec2U)> {
+        if size.x > self.page_size || size.y > self.page_size {
+            return None;
+        }
+
+        // try to fit it in one of the existing pages
+        for (i, page) in self.pages.iter_mut().enumerate() {
+            if let Some(pos) = page.shelves.allocate(size) {
+                return Some((i, pos));
+            }
+        }
+
+        // when at the limit, clear the page that has gone the longest without being drawn,
+        // unless it was drawn this frame, in which case its glyphs are still needed
+        if self.pages.len() >= self.max_pages {
+            let oldest = self
+                .pages
+                .iter()
+                .enumerate()
+                .filter(|(_, page)| page.last_used < frame)
+                .min_by_key(|(_, page)| page.last_used)
+                .map(|(i, _)| i);
+            if let Some(i) = oldest {
+                self.clear_page(i);
+                let pos = self.pages[i].shelves.allocate(size)?;
+                return Some((i, pos));
+            }
+        }
+
+        // otherwise add a new page
+        let page_size = Vec2U::splat(self.page_size);
+        let pixels = vec![GreyAlpha8::TRANSPARENT; (self.page_size * self.page_size) as usize];
+        self.pages.push(Page {
+            texture: self.gfx.create_texture(page_size, &pixels),
+            shelves: Shelves::new(page_size),
+            last_used: frame,
+        });
+        let i = self.pages.len() - 1;
+        let pos = self.pages[i].shelves.allocate(size)?;
+        Some((i, pos))
+    }
+
+    fn clear_page(&mut self, i: usize) {
+        let page = &mut self.pages[i];
+        page.shelves.clear();
+
+        // wipe the old glyphs so they can't bleed into the padding of new ones
+        let pixels = vec![GreyAlpha8::TRANSPARENT; (self.page_size * self.page_size) as usize];
+        page.texture.upload_pixels(&pixels).unwrap();
+
+        self.glyphs
+            .retain(|_, entry| entry.sub.is_none() || entry.page != i);
+    }
+}
+
+/// Packs rectangles into rows of increasing height, which suits glyphs because glyphs of the
+/// same size tend to have similar heights.
+#[derive(Debug, Clone)]
+struct Shelves {
+    size: Vec2U,
+    shelves: Vec<Shelf>,
+    bottom: u32,
+}
+
+#[derive(Debug, Clone)]
+struct Shelf {
+    y: u32,
+    height: u32,
+    width: u32,
+}
+
+impl Shelves {
+    fn new(size: Vec2U) -> Self {
+        Self {
+            size,
+            shelves: Vec::new(),
+            bottom: 0,
+        }
+    }
+
+    fn clear(&mut self) {
+        self.shelves.clear();
+        self.bottom = 0;
+    }
+
+    fn allocate(&mut self, size: Vec2U) -> Option<Vec2U> {
+        // use the shortest shelf that can fit it, so tall shelves aren't wasted on short glyphs
+        let best = self
+            .shelves
+            .iter_mut()
+            .filter(|shelf| shelf.height >= size.y && self.size.x - shelf.width >= size.x)
+            .min_by_key(|shelf| shelf.height);
+        if let Some(shelf) = best {
+            let pos = Vec2U::new(shelf.width, shelf.y);
+            shelf.width += size.x;
+            return Some(pos);
+        }
+
+        // otherwise start a new shelf below the others
+        if size.x > self.size.x || self.size.y - self.bottom < size.y {
+            return None;
+        }
+        let pos = Vec2U::new(0, self.bottom);
+        self.shelves.push(Shelf {
+            y: self.bottom,
+            height: size.y,
+            width: size.x,
+        });
+        self.bottom += size.y;
+        Some(pos)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shelves_pack_and_fill() {
+        let mut shelves = Shelves::new(Vec2U::new(10, 10));
+        assert_eq!(shelves.allocate(Vec2U::new(4, 5)), Some(Vec2U::new(0, 0)));
+        assert_eq!(shelves.allocate(Vec2U::new(4, 3)), Some(Vec2U::new(4, 0)));
+        assert_eq!(shelves.allocate(Vec2U::new(4, 3)), Some(Vec2U::new(0, 5)));
+        assert_eq!(shelves.allocate(Vec2U::new(6, 2)), Some(Vec2U::new(4, 5)));
+        assert_eq!(shelves.allocate(Vec2U::new(3, 3)), None);
+        assert_eq!(shelves.allocate(Vec2U::new(2, 2)), Some(Vec2U::new(8, 0)));
+        assert_eq!(shelves.allocate(Vec2U::new(11, 1)), None);
+        shelves.clear();
+        assert_eq!(shelves.allocate(Vec2U::new(10, 10)), Some(Vec2U::new(0, 0)));
+    }
+}
@@ -1,7 +1,8 @@
 use crate::color::{FromRgb, Rgba8, Rgba16, Rgba32F};
 use crate::core::{GameBuilder, Window};
 use crate::gfx::{
-    IndexBuffer, Shader, Surface, Texture, TextureFormat, TexturePixel, Vertex, VertexBuffer,
+    IndexBuffer, Shader, Surface, Texture, TextureDownload, TextureDownloadError, TextureFormat,
+    TexturePixel, Vertex, VertexBuffer, supported_sample_count,
 };
 use crate::grid::Grid;
 use crate::img::{DynImage, Image, ImageError, ImageRgba8};
@@ -10,12 +11,12 @@ use dpi::PhysicalSize;
 use pollster::FutureExt;
 use std::fmt::{Debug, Formatter};
 use std::path::Path;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use wgpu::{
     Adapter, BackendOptions, Backends, Device, DeviceDescriptor, ExperimentalFeatures, Features,
     Instance, InstanceDescriptor, InstanceFlags, Limits, MemoryBudgetThresholds, MemoryHints,
-    PowerPreference, PresentMode, Queue, RequestAdapterOptions, SurfaceCapabilities,
-    SurfaceConfiguration, TextureUsages, Trace,
+    NoopBackendOptions, PollType, PowerPreference, PresentMode, Queue, RequestAdapterOptions,
+    SurfaceCapabilities, SurfaceConfiguration, TextureUsages, Trace,
 };
 
 /// Handle to the graphics state, used to create surfaces, textures, shaders, etc.
@@ -34,14 +35,17 @@ impl Debug for Graphics {
 struct GraphicsInner {
     window: Window,
     _instance: Instance,
-    surface_caps: SurfaceCapabilities,
-    pub(crate) surface: wgpu::Surface<'static>,
+    target: Target,
     _adapter: Adapter,
     device: Device,
     queue: Queue,
     limits: Limits,
+    window_sample_count: u32,
+    window_depth_stencil: bool,
     default_texture: Texture,
     default_shader: Shader,
+    sdf_shader: Shader,
+    screenshots: Mutex<Vec<TextureDownload>>,
 
     #[cfg(feature = "lua")]
     default_texture_userdata: mlua::AnyUserData,
@@ -49,14 +53,54 @@ struct GraphicsInner {
     #[cfg(feature = "lua")]
     default_shader_userdata: mlua::AnyUserData,
 
+    #[cfg(feature = "lua")]
+    sdf_shader_userdata: mlua::AnyUserData,
+
     #[cfg(feature = "lua")]
     lua: mlua::WeakLua,
 }
 
+/// What the window's frames are rendered to.
+enum Target {
+    Window {
+        surface: wgpu::Surface<'static>,
+        caps: SurfaceCapabilities,
+    },
+    Offscreen(Texture),
+}
+
+impl Target {
+    fn format(&self) -> wgpu::TextureFormat {
+        match self {
+            Self::Window { .. } => WINDOW_FORMAT,
+            Self::Offscreen(texture) => texture.0.texture.format(),
+        }
+    }
+}
+
+/// A frame acquired from the graphics target, ready to be rendered to.
+pub(crate) enum Frame {
+    Window(wgpu::SurfaceTexture),
+    Offscreen(wgpu::Texture),
+}
+
+impl Frame {
+    #[inline]
+    pub fn texture(&self) -> &wgpu::Texture {
+        match self {
+            Self::Window(frame) => &frame.texture,
+            Self::Offscreen(texture) => texture,
+        }
+    }
+}
+
+const WINDOW_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Bgra8Unorm;
+
 fn config(size: PhysicalSize<u32>, caps: &SurfaceCapabilities) -> SurfaceConfiguration {
     SurfaceConfiguration {
-        usage: TextureUsages::RENDER_ATTACHMENT,
-        format: wgpu::TextureFormat::Bgra8Unorm,
+        // allow frames to be copied from if possible, so screenshots can be taken
+        usage: TextureUsages::RENDER_ATTACHMENT | (caps.usages & TextureUsages::COPY_SRC),
+        format: WINDOW_FORMAT,
         width: size.width,
         height: size.height,
         present_mode: PresentMode::AutoVsync,
@@ -66,43 +110,110 @@ fn config(size: PhysicalSize<u32>, caps: &SurfaceCapabilities) -> SurfaceConfigu
     }
 }
 
+fn create_instance(backends: Backends, backend_options: BackendOptions) -> Instance {
+    Instance::new(&InstanceDescriptor {
+        backends,
+        flags: InstanceFlags::DEBUG | InstanceFlags::VALIDATION,
+        memory_budget_thresholds: MemoryBudgetThresholds::default(),
+        backend_options,
+    })
+}
+
+fn default_backends() -> Backends {
+    if cfg!(target_os = "windows") {
+        Backends::DX12
+    } else if cfg!(target_os = "macos") {
+        Backends::METAL
+    } else {
+        Backends::VULKAN
+    }
+}
+
+fn adapter_options<'a>(
+    surface: Option<&'a wgpu::Surface<'static>>,
+) -> RequestAdapterOptions<'a, 'static> {
+    RequestAdapterOptions {
+        power_preference: PowerPreference::HighPerformance,
+        force_fallback_adapter: false,
+        compatible_surface: surface,
+    }
+}
+
 impl Graphics {
-    #[allow(unused_variables)]
     pub(crate) fn new(window: Window, opts: &GameBuilder) -> Self {
+        let winit = window
+            .winit()
+            .expect("cannot create a window surface for a headless window")
+            .clone();
+
         // create the instance
-        let instance = {
-            let backends = if cfg!(target_os = "windows") {
-                Backends::DX12
-            } else if cfg!(target_os = "macos") {
-                Backends::METAL
-            } else {
-                Backends::VULKAN
-            };
-            Instance::new(&InstanceDescriptor {
-                backends,
-                flags: InstanceFlags::DEBUG | InstanceFlags::VALIDATION,
-                memory_budget_thresholds: MemoryBudgetThresholds::default(),
-
-                // TODO: ship with (or detect) DX12 compiler DLL?
-                backend_options: BackendOptions::default(),
-            })
-        };
+        // TODO: ship with (or detect) DX12 compiler DLL?
+        let instance = create_instance(default_backends(), BackendOptions::default());
 
         // create the window surface
         let surface = instance
-            .create_surface(window.0.clone())
+            .create_surface(winit.clone())
             .expect("failed to create window surface");
 
         // request an adapter to a graphics device
         let adapter = instance
-            .request_adapter(&RequestAdapterOptions {
-                power_preference: PowerPreference::HighPerformance,
-                force_fallback_adapter: false,
-                compatible_surface: Some(&surface),
-            })
+            .request_adapter(&adapter_options(Some(&surface)))
             .block_on()
             .expect("failed to find a suitable graphics device");
 
+        let size = winit.inner_size();
+        Self::create(window, opts, instance, adapter, |adapter, device, _| {
+            // create the surface configuration and configure the surface
+            let caps = surface.get_capabilities(adapter);
+            surface.configure(device, &config(size, &caps));
+            Target::Window { surface, caps }
+        })
+    }
+
+    /// Create graphics that render the window's frames to an offscreen texture. If no graphics
+    /// device is available, the no-op backend is used, so draw calls are still generated and
+    /// submitted but nothing is actually rendered.
+    pub(crate) fn new_headless(window: Window, opts: &GameBuilder) -> Self {
+        let instance = create_instance(default_backends(), BackendOptions::default());
+        let (instance, adapter) = match instance.request_adapter(&adapter_options(None)).block_on()
+        {
+            Ok(adapter) => (instance, adapter),
+            Err(_) => {
+                let instance = create_instance(
+                    Backends::NOOP,
+                    BackendOptions {
+                        noop: NoopBackendOptions { enable: true },
+                        ..Default::default()
+                    },
+                );
+                let adapter = instance
+                    .request_adapter(&adapter_options(None))
+                    .block_on()
+                    .expect("failed to create a no-op graphics device");
+                (instance, adapter)
+            }
+        };
+
+        let size = window.pixel_size();
+        Self::create(window, opts, instance, adapter, |_, device, queue| {
+            Target::Offscreen(Texture::new(
+                device,
+                queue.clone(),
+                size,
+                TextureFormat::Rgba8,
+                true,
+            ))
+        })
+    }
+
+    #[allow(unused_variables)]
+    fn create(
+        window: Window,
+        opts: &GameBuilder,
+        instance: Instance,
+        adapter: Adapter,
+        target: impl FnOnce(&Adapter, &Device, &Queue) -> Target,
+    ) -> Self {
         // request a graphics device and queue for it
         let (device, queue) = adapter
             .request_device(&DeviceDescriptor {
@@ -120,13 +231,16 @@ impl Graphics {
             ));
         let limits = device.limits();
 
-        // create the surface configuration and configure the surface
-        let surface_caps = surface.get_capabilities(&adapter);
-        surface.configure(&device, &config(window.0.inner_size(), &surface_caps));
+        // create the target that frames are rendered to
+        let target = target(&adapter, &device, &queue);
+        let window_sample_count = supported_sample_count(&device, target.format(), opts.msaa);
 
         // create the default shader
         let default_shader = Shader::new(&device, include_str!("shader_default.wgsl"));
 
+        // create the signed distance field shader
+        let sdf_shader = Shader::new(&device, include_str!("shader_sdf.wgsl"));
+
         // create the default texture
         let default_texture = Texture::new(
             &device,
@@ -140,16 +254,20 @@ impl Graphics {
         Self(Arc::new(GraphicsInner {
             window,
             _instance: instance,
-            surface_caps,
-            surface,
+            target,
             _adapter: adapter,
             device,
             queue,
             limits,
+            window_sample_count,
+            window_depth_stencil: opts.depth_stencil,
 
             #[cfg(feature = "lua")]
             default_shader_userdata: opts.lua.create_userdata(default_shader.clone()).unwrap(),
 
+            #[cfg(feature = "lua")]
+            sdf_shader_userdata: opts.lua.create_userdata(sdf_shader.clone()).unwrap(),
+
             #[cfg(feature = "lua")]
             default_texture_userdata: opts.lua.create_userdata(default_texture.clone()).unwrap(),
 
@@ -157,7 +275,9 @@ impl Graphics {
             lua: opts.lua.weak(),
 
             default_shader,
+            sdf_shader,
             default_texture,
+            screenshots: Mutex::new(Vec::new()),
         }))
     }
 
@@ -172,9 +292,76 @@ impl Graphics {
         &self.0.window
     }
 
+    /// The offscreen texture the window is rendered to when running headless.
     #[inline]
-    pub(crate) fn surface(&self) -> &wgpu::Surface<'static> {
-        &self.0.surface
+    pub fn headless_target(&self) -> Option<&Texture> {
+        match &self.0.target {
+            Target::Window { .. } => None,
+            Target::Offscreen(texture) => Some(texture),
+        }
+    }
+
+    /// Acquire the next frame to render the window to.
+    pub(crate) fn acquire_frame(&self) -> Frame {
+        match &self.0.target {
+            Target::Window { surface, .. } => Frame::Window(
+                surface
+                    .get_current_texture()
+                    .expect("failed to acquire surface texture"),
+            ),
+            Target::Offscreen(texture) => Frame::Offscreen(texture.0.texture.clone()),
+        }
+    }
+
+    /// Capture the window's contents at the end of the current frame.
+    ///
+    /// The screenshot includes everything drawn to the window this frame, and completes after
+    /// the frame has been rendered. If the window's frames can't be read back on this platform,
+    /// the download will fail with [`TextureDownloadError::Unsupported`].
+    pub fn screenshot(&self) -> TextureDownload {
+        let download = TextureDownload::new(self.0.device.clone());
+        self.0.screenshots.lock().unwrap().push(download.handle());
+        download
+    }
+
+    /// Copy the rendered frame into any screenshots that were requested this frame.
+    fn capture_screenshots(&self, frame: &Frame) {
+        let mut screenshots = self.0.screenshots.lock().unwrap();
+        if screenshots.is_empty() {
+            return;
+        }
+        let texture = frame.texture();
+        let bgra = match texture.format() {
+            wgpu::TextureFormat::Rgba8Unorm | wgpu::TextureFormat::Rgba8UnormSrgb => Some(false),
+            wgpu::TextureFormat::Bgra8Unorm | wgpu::TextureFormat::Bgra8UnormSrgb => Some(true),
+            _ => None,
+        }
+        .filter(|_| texture.usage().contains(TextureUsages::COPY_SRC));
+        for download in screenshots.drain(..) {
+            match bgra {
+                Some(bgra) => download.start(&self.0.queue, texture, TextureFormat::Rgba8, bgra),
+                None => download.fail(TextureDownloadError::Unsupported),
+            }
+        }
+    }
+
+    /// Present a rendered frame to the window.
+    pub(crate) fn present_frame(&self, frame: Frame) {
+        self.capture_screenshots(&frame);
+
+        // complete any texture downloads that have finished
+        _ = self.0.device.poll(PollType::Poll);
+
+        if let Frame::Window(frame) = frame {
+            let window = self.0.window.winit();
+            if let Some(window) = window {
+                window.pre_present_notify();
+            }
+            frame.present();
+            if let Some(window) = window {
+                window.request_redraw();
+            }
+        }
     }
 
     #[inline]
@@ -192,6 +379,20 @@ impl Graphics {
         self.0.limits.max_texture_dimension_2d
     }
 
+    /// How many samples are taken per pixel when drawing to the window, which is set with
+    /// [`GameBuilder::with_msaa`](crate::core::GameBuilder::with_msaa).
+    #[inline]
+    pub fn window_sample_count(&self) -> u32 {
+        self.0.window_sample_count
+    }
+
+    /// If the window has a depth-stencil buffer, which is set with
+    /// [`GameBuilder::with_depth_stencil`](crate::core::GameBuilder::with_depth_stencil).
+    #[inline]
+    pub fn window_has_depth_stencil(&self) -> bool {
+        self.0.window_depth_stencil
+    }
+
     /// Shader that is used by default, which is:
     ///
     /// ```wgsl
@@ -210,6 +411,33 @@ impl Graphics {
         &self.0.default_shader
     }
 
+    /// Shader for drawing signed distance fields, such as fonts created with
+    /// [`Font::sdf_from_ttf_file`](super::Font::sdf_from_ttf_file), which stay sharp at any
+    /// scale. It works with both single and multi-channel fields, where the outline is at `0.5`
+    /// in the texture. Any texture in that format can be drawn with it, so it also works for
+    /// shapes.
+    ///
+    /// Effects are off by default, and can be set with [`Draw`](super::Draw)'s `set_param_*`
+    /// methods. Widths and softness are measured in the field's spread, so `1.0` reaches as far
+    /// out as the field does, and colors are premultiplied like all other drawing colors:
+    ///
+    /// | Parameter         | Type    | Description                                       |
+    /// |-------------------|---------|---------------------------------------------------|
+    /// | `outline_width`   | `f32`   | How far the outline extends past the fill.        |
+    /// | `outline_color`   | `vec4f` | Color of the outline.                             |
+    /// | `shadow_offset`   | `vec2f` | Offset of the shadow, in texels of the field.     |
+    /// | `shadow_softness` | `f32`   | How far the shadow's edge fades out.              |
+    /// | `shadow_color`    | `vec4f` | Color of the shadow.                              |
+    /// | `glow_width`      | `f32`   | How far the glow fades out past the outline.      |
+    /// | `glow_color`      | `vec4f` | Color of the glow.                                |
+    ///
+    /// Shadows and glows can't reach past the padding around each glyph, which is as wide as
+    /// the spread they were generated with.
+    #[inline]
+    pub fn sdf_shader(&self) -> &Shader {
+        &self.0.sdf_shader
+    }
+
     /// Texture that is used by default (a single `Rgba8::FUCHSIA` pixel).
     #[inline]
     pub fn default_texture(&self) -> &Texture {
@@ -222,6 +450,12 @@ impl Graphics {
         &self.0.default_shader_userdata
     }
 
+    #[cfg(feature = "lua")]
+    #[inline]
+    pub fn sdf_shader_userdata(&self) -> &mlua::AnyUserData {
+        &self.0.sdf_shader_userdata
+    }
+
     #[cfg(feature = "lua")]
     #[inline]
     pub fn default_texture_userdata(&self) -> &mlua::AnyUserData {
@@ -245,16 +479,57 @@ impl Graphics {
 
     /// Create a new surface that can be rendered to.
     pub fn create_surface(&self, size: impl Into<Vec2U>, format: TextureFormat) -> Surface {
-        Surface::new(
-            self,
-            Texture::new(
-                &self.0.device,
-                self.0.queue.clone(),
-                size.into(),
-                format,
-                true,
-            ),
-        )
+        self.create_surface_ext(size, format, 1, false)
+    }
+
+    /// Create a new multisampled surface, which smooths the edges of shapes drawn to it. Drawing
+    /// is resolved into the surface's texture after every render pass, so it can be drawn like
+    /// any other texture.
+    ///
+    /// If `samples` isn't supported by the graphics device, the highest supported count below
+    /// it is used instead, which can be checked with [`Surface::sample_count`].
+    ///
+    /// Drawing starts from the multisampled pixels rather than the texture's, so pixels uploaded
+    /// directly to the texture are overwritten the next time the surface is drawn to.
+    pub fn create_surface_msaa(
+        &self,
+        size: impl Into<Vec2U>,
+        format: TextureFormat,
+        samples: u32,
+    ) -> Surface {
+        self.create_surface_ext(size, format, samples, false)
+    }
+
+    /// Create a new surface with a depth-stencil buffer, which allows masking and depth testing
+    /// when drawing to it.
+    pub fn create_depth_stencil_surface(
+        &self,
+        size: impl Into<Vec2U>,
+        format: TextureFormat,
+    ) -> Surface {
+        self.create_surface_ext(size, format, 1, true)
+    }
+
+    /// Create a new surface, which can be both multisampled and have a depth-stencil buffer.
+    /// See [`create_surface_msaa`](Self::create_surface_msaa) and
+    /// [`create_depth_stencil_surface`](Self::create_depth_stencil_surface).
+    pub fn create_surface_ext(
+        &self,
+        size: impl Into<Vec2U>,
+        format: TextureFormat,
+        samples: u32,
+        depth_stencil: bool,
+    ) -> Surface {
+        let texture = Texture::new(
+            &self.0.device,
+            self.0.queue.clone(),
+            size.into(),
+            format,
+            true,
+        );
+        let sample_count =
+            supported_sample_count(&self.0.device, texture.0.texture.format(), samples);
+        Surface::new(self, texture, sample_count, depth_stencil)
     }
 
     /// Create a new [`Rgba8`](TextureFormat::Rgba8) surface.
@@ -365,9 +640,11 @@ impl Graphics {
 
     pub(crate) fn resized(&self, new_size: PhysicalSize<u32>) {
         // only configure surface if the window has an actual size
-        if new_size.width > 0 && new_size.height > 0 {
-            let config = config(new_size, &self.0.surface_caps);
-            self.0.surface.configure(&self.0.device, &config);
+        if let Target::Window { surface, caps } = &self.0.target
+            && new_size.width > 0
+            && new_size.height > 0
+        {
+            surface.configure(&self.0.device, &config(new_size, caps));
         }
     }
 }
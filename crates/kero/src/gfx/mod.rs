@@ -4,10 +4,13 @@ mod bindings;
 mod blend_mode;
 mod buffer_cache;
 mod color_mode;
+mod depth_stencil;
 mod draw;
 mod font;
+mod glyph_cache;
 mod graphics;
 mod index_buffer;
+mod multisample;
 mod params;
 mod render_data;
 mod sampler;
@@ -15,7 +18,9 @@ mod screen;
 mod shader;
 mod sub_texture;
 mod surface;
+mod text_layout;
 mod texture;
+mod texture_download;
 mod texture_format;
 mod texture_packer;
 mod texture_pixel;
@@ -26,10 +31,13 @@ mod vertex_buffer;
 pub use bindings::*;
 pub use blend_mode::*;
 pub use color_mode::*;
+pub(crate) use depth_stencil::*;
 pub use draw::*;
 pub use font::*;
+pub use glyph_cache::*;
 pub use graphics::*;
 pub use index_buffer::*;
+pub(crate) use multisample::*;
 pub use params::*;
 pub(crate) use render_data::*;
 pub use sampler::*;
@@ -37,7 +45,9 @@ pub use screen::*;
 pub use shader::*;
 pub use sub_texture::*;
 pub use surface::*;
+pub use text_layout::*;
 pub use texture::*;
+pub use texture_download::*;
 pub use texture_format::*;
 pub use texture_packer::*;
 pub use texture_pixel::*;
@@ -0,0 +1,42 @@
+use crate::gfx::DEPTH_STENCIL_FORMAT;
+use crate::math::Vec2U;
+use wgpu::{Device, Extent3d, TextureDescriptor, TextureDimension, TextureFormat, TextureUsages};
+
+/// The highest sample count no greater than `samples` that can be rendered to with the format,
+/// and with a depth-stencil buffer alongside it. Only the counts guaranteed by WebGPU are used,
+/// so this works the same on every graphics device.
+pub(crate) fn supported_sample_count(device: &Device, format: TextureFormat, samples: u32) -> u32 {
+    let features = device.features();
+    let flags = format.guaranteed_format_features(features).flags
+        & DEPTH_STENCIL_FORMAT
+            .guaranteed_format_features(features)
+            .flags;
+    [16, 8, 4, 2]
+        .into_iter()
+        .find(|&count| count <= samples && flags.sample_count_supported(count))
+        .unwrap_or(1)
+}
+
+/// Create a multisampled color attachment, which is resolved into a regular texture of the same
+/// size and format at the end of each render pass.
+pub(crate) fn create_multisampled(
+    device: &Device,
+    size: Vec2U,
+    format: TextureFormat,
+    sample_count: u32,
+) -> wgpu::Texture {
+    device.create_texture(&TextureDescriptor {
+        label: None,
+        size: Extent3d {
+            width: size.x.max(1),
+            height: size.y.max(1),
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count,
+        dimension: TextureDimension::D2,
+        format,
+        usage: TextureUsages::RENDER_ATTACHMENT,
+        view_formats: &[],
+    })
+}
@@ -1,8 +1,8 @@
 use crate::color::Rgba8;
 use crate::gfx::draw::DrawCache;
 use crate::gfx::{
-    BindingValue, Bindings, BlendMode, IndexBuffer, Sampler, Shader, Surface, Texture, Topology,
-    UniformValue, Vertex, VertexBuffer,
+    BindingValue, Bindings, BlendMode, DepthStencil, IndexBuffer, Sampler, Shader, StencilMode,
+    Surface, Texture, Topology, UniformValue, Vertex, VertexBuffer,
 };
 use crate::math::{Mat4, Numeric, Rect, Vec2};
 
@@ -80,6 +80,9 @@ pub struct RenderLayer {
     pub main_texture: Texture,
     pub main_sampler: Sampler,
     pub ortho: Mat4<f32>,
+    pub depth_stencil: DepthStencil,
+    pub stencil_ref: u32,
+    pub depth: f32,
 }
 
 impl RenderLayer {
@@ -97,6 +100,9 @@ impl RenderLayer {
             main_texture: cache.default_texture.clone(),
             main_sampler: Sampler::default(),
             ortho: Mat4::ortho(0.0, size.x, size.y, 0.0, 0.0, 1.0),
+            depth_stencil: DepthStencil::NONE,
+            stencil_ref: 0,
+            depth: 0.0,
         }
     }
 
@@ -118,11 +124,13 @@ impl RenderLayer {
         self.vertices.clear();
         self.indices.clear();
 
-        // update the binding values
+        // update the binding values, flattening all vertices to the layer's depth
+        let mut view_matrix = self.ortho * self.view_matrix;
+        view_matrix.w_axis.z = self.depth;
         self.bindings.set(
             &self.shader,
             "view_matrix",
-            BindingValue::Uniform(UniformValue::Mat4(self.ortho * self.view_matrix)),
+            BindingValue::Uniform(UniformValue::Mat4(view_matrix)),
         );
         self.bindings.set(
             &self.shader,
@@ -141,6 +149,8 @@ impl RenderLayer {
             bindings: self.bindings.clone(),
             blend_mode: self.blend_mode,
             clip_rect: self.scissor_rect,
+            depth_stencil: self.depth_stencil,
+            stencil_ref: self.stencil_ref,
             vertices,
             indices,
             topology: self.topology,
@@ -196,6 +206,28 @@ impl RenderLayer {
         }
     }
 
+    pub fn set_stencil(&mut self, mode: StencilMode, reference: u32, cache: &mut DrawCache) {
+        if self.depth_stencil.stencil != mode || self.stencil_ref != reference {
+            self.flush(cache);
+            self.depth_stencil.stencil = mode;
+            self.stencil_ref = reference;
+        }
+    }
+
+    pub fn set_depth_test(&mut self, enabled: bool, cache: &mut DrawCache) {
+        if self.depth_stencil.depth_test != enabled {
+            self.flush(cache);
+            self.depth_stencil.depth_test = enabled;
+        }
+    }
+
+    pub fn set_depth(&mut self, depth: f32, cache: &mut DrawCache) {
+        if self.depth != depth {
+            self.flush(cache);
+            self.depth = depth;
+        }
+    }
+
     pub fn set_topology(&mut self, topology: Topology, cache: &mut DrawCache) {
         if self.topology != topology {
             self.flush(cache);
@@ -226,6 +258,8 @@ impl RenderLayer {
             bindings: self.bindings.clone(),
             blend_mode: self.blend_mode,
             clip_rect: self.scissor_rect,
+            depth_stencil: self.depth_stencil,
+            stencil_ref: self.stencil_ref,
             vertices,
             indices,
             topology,
@@ -239,6 +273,8 @@ pub struct DrawCall {
     pub bindings: Bindings,
     pub blend_mode: BlendMode,
     pub clip_rect: Option<Rect<u32>>,
+    pub depth_stencil: DepthStencil,
+    pub stencil_ref: u32,
     pub vertices: VertexBuffer,
     pub indices: IndexBuffer,
     pub topology: Topology,
@@ -1,6 +1,7 @@
 use crate::color::Rgba8;
 use crate::core::{Context, Window};
 use crate::gfx::{Draw, Surface, TextureFormat};
+use crate::input::Touch;
 use crate::math::{Numeric, RectF, Vec2F, Vec2U};
 
 #[cfg(feature = "lua")]
@@ -54,6 +55,9 @@ pub struct Screen {
     win_rect: RectF,
     scale: f32,
     mouse_pos: Vec2F,
+    hover_pos: Option<Vec2F>,
+    drop_pos: Option<Vec2F>,
+    touches: Vec<Touch>,
 }
 
 fn surface_size(window: &Window, scale: f32) -> Vec2U {
@@ -87,6 +91,9 @@ impl Screen {
             win_rect: RectF::ZERO,
             scale: 0.0,
             mouse_pos: Vec2F::ZERO,
+            hover_pos: None,
+            drop_pos: None,
+            touches: Vec::new(),
         };
         screen.update(ctx);
         screen
@@ -167,6 +174,26 @@ impl Screen {
         self.mouse_pos.y
     }
 
+    /// Position on screen where files are being dragged over the window, accounting for
+    /// scaling/framing.
+    #[inline]
+    pub fn hover_pos(&self) -> Option<Vec2F> {
+        self.hover_pos
+    }
+
+    /// Position on screen where files were dropped onto the window this frame, accounting for
+    /// scaling/framing.
+    #[inline]
+    pub fn drop_pos(&self) -> Option<Vec2F> {
+        self.drop_pos
+    }
+
+    /// All touches this frame, with their positions on screen, accounting for scaling/framing.
+    #[inline]
+    pub fn touches(&self) -> &[Touch] {
+        &self.touches
+    }
+
     /// Update the screen, which will update the mouse position. If the screen needs to update its
     /// surface, it will replace its existing surface with a new resized one.
     #[inline]
@@ -189,12 +216,20 @@ impl Screen {
         self.scale = scale;
 
         self.mouse_pos = win_rect.map_pos(ctx.mouse.pos(), &self.scr_rect).round();
+        self.hover_pos = ctx.file_drop.hover_pos().map(|p| self.map_pos(p).round());
+        self.drop_pos = ctx.file_drop.drop_pos().map(|p| self.map_pos(p).round());
+
+        let mut touches = ctx.touches.all();
+        for touch in &mut touches {
+            touch.pos = self.map_pos(touch.pos).round();
+        }
+        self.touches = touches;
     }
 
-    /// Map a window position to an on-screen position.
+    /// Map a window position (or the position of a [`Touch`]) to an on-screen position.
     #[inline]
-    pub fn map_pos(&self, pos: Vec2F) -> Vec2F {
-        self.win_rect.map_pos(pos, &self.scr_rect)
+    pub fn map_pos(&self, pos: impl Into<Vec2F>) -> Vec2F {
+        self.win_rect.map_pos(pos.into(), &self.scr_rect)
     }
 
     /// Make this screen the drawing surface.
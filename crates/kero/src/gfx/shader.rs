@@ -1,5 +1,6 @@
 use crate::gfx::{
-    BindingValue, Bindings, BlendMode, ParamDefs, ParamType, Sampler, Texture, Topology, Vertex,
+    BindingValue, Bindings, BlendMode, DepthStencil, ParamDefs, ParamType, Sampler, Texture,
+    Topology, Vertex,
 };
 use naga::valid::{Capabilities, ValidationFlags, Validator};
 use naga::{FunctionResult, Scalar, ScalarKind, ShaderStage, TypeInner, VectorSize};
@@ -246,12 +247,24 @@ impl Shader {
         topology: Topology,
         format: wgpu::TextureFormat,
         blend_mode: BlendMode,
+        depth_stencil: Option<DepthStencil>,
+        sample_count: u32,
     ) -> RenderPipeline {
         self.0
             .pipeline_cache
             .write()
             .unwrap()
-            .request(device, &self.0.shader, topology, format, blend_mode)
+            .request(
+                device,
+                &self.0.shader,
+                PipelineKey {
+                    topology,
+                    format,
+                    blend_mode,
+                    depth_stencil,
+                    sample_count,
+                },
+            )
             .clone()
     }
 
@@ -303,63 +316,67 @@ impl PipelineCache {
         &mut self,
         device: &Device,
         shader: &ShaderModule,
-        topology: Topology,
-        format: wgpu::TextureFormat,
-        blend_mode: BlendMode,
+        key: PipelineKey,
     ) -> &RenderPipeline {
-        self.cache
-            .entry(PipelineKey {
-                topology,
-                format,
-                blend_mode,
-            })
-            .or_insert_with(|| {
-                device.create_render_pipeline(&RenderPipelineDescriptor {
-                    label: None,
-                    layout: Some(&self.layout),
-                    vertex: VertexState {
-                        module: shader,
-                        entry_point: None,
-                        compilation_options: Default::default(),
-                        buffers: &[Vertex::LAYOUT],
-                    },
-                    primitive: PrimitiveState {
-                        topology: topology.into(),
-                        strip_index_format: None,
-                        front_face: FrontFace::Cw,
-                        cull_mode: None,
-                        unclipped_depth: false,
-                        polygon_mode: PolygonMode::Fill,
-                        conservative: false,
-                    },
-                    depth_stencil: None,
-                    multisample: MultisampleState {
-                        count: 1,
-                        mask: !0,
-                        alpha_to_coverage_enabled: false,
-                    },
-                    fragment: Some(FragmentState {
-                        module: shader,
-                        entry_point: None,
-                        compilation_options: Default::default(),
-                        targets: &[Some(ColorTargetState {
-                            format,
-                            blend: Some(blend_mode.into()),
-                            write_mask: ColorWrites::ALL,
-                        })],
-                    }),
-                    multiview: None,
-                    cache: None,
-                })
+        let PipelineKey {
+            topology,
+            format,
+            blend_mode,
+            depth_stencil,
+            sample_count,
+        } = key;
+        self.cache.entry(key).or_insert_with(|| {
+            device.create_render_pipeline(&RenderPipelineDescriptor {
+                label: None,
+                layout: Some(&self.layout),
+                vertex: VertexState {
+                    module: shader,
+                    entry_point: None,
+                    compilation_options: Default::default(),
+                    buffers: &[Vertex::LAYOUT],
+                },
+                primitive: PrimitiveState {
+                    topology: topology.into(),
+                    strip_index_format: None,
+                    front_face: FrontFace::Cw,
+                    cull_mode: None,
+                    unclipped_depth: false,
+                    polygon_mode: PolygonMode::Fill,
+                    conservative: false,
+                },
+                depth_stencil: depth_stencil.map(|ds| ds.state()),
+                multisample: MultisampleState {
+                    count: sample_count,
+                    mask: !0,
+                    alpha_to_coverage_enabled: false,
+                },
+                fragment: Some(FragmentState {
+                    module: shader,
+                    entry_point: None,
+                    compilation_options: Default::default(),
+                    targets: &[Some(ColorTargetState {
+                        format,
+                        blend: Some(blend_mode.into()),
+                        write_mask: match depth_stencil {
+                            Some(ds) if ds.is_mask() => ColorWrites::empty(),
+                            _ => blend_mode.mask().into(),
+                        },
+                    })],
+                }),
+                multiview: None,
+                cache: None,
             })
+        })
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Hash)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 struct PipelineKey {
     topology: Topology,
     format: wgpu::TextureFormat,
     blend_mode: BlendMode,
+    depth_stencil: Option<DepthStencil>,
+    sample_count: u32,
 }
 
 #[derive(Debug, Default)]
@@ -0,0 +1,63 @@
+// widths and softness are measured in the field's spread, so 1 reaches as far as it does
+@group(0) @binding(0)
+var<uniform> outline_width: f32;
+
+@group(0) @binding(1)
+var<uniform> outline_color: vec4f;
+
+@group(0) @binding(2)
+var<uniform> shadow_offset: vec2f;
+
+@group(0) @binding(3)
+var<uniform> shadow_softness: f32;
+
+@group(0) @binding(4)
+var<uniform> shadow_color: vec4f;
+
+@group(0) @binding(5)
+var<uniform> glow_width: f32;
+
+@group(0) @binding(6)
+var<uniform> glow_color: vec4f;
+
+@vertex
+fn vert_main(vert: Vertex) -> Fragment {
+    return vert_default(vert);
+}
+
+@fragment
+fn frag_main(frag: Fragment) -> @location(0) vec4f {
+    // sample both distances up front, the offset is in texels
+    let dist = sdf_distance(frag.tex);
+    let texel = 1.0 / vec2f(textureDimensions(main_texture));
+    let shadow_dist = sdf_distance(frag.tex - shadow_offset * texel);
+
+    // how much the distance changes per screen pixel, for antialiasing at any scale
+    let aa = max(fwidth(dist), 0.0001);
+
+    // the fill, with the outline around it
+    let fill = clamp(dist / aa + 0.5, 0.0, 1.0);
+    let edge = dist + outline_width;
+    let outer = clamp(edge / aa + 0.5, 0.0, 1.0);
+    var color = frag.col * fill + outline_color * (outer - fill);
+
+    // glow fades out from the outer edge
+    var glow = clamp(1.0 + edge / max(glow_width, 0.0001), 0.0, 1.0);
+    glow = select(0.0, glow * glow, glow_width > 0.0);
+    color += glow_color * glow * (1.0 - color.a);
+
+    // shadow goes behind everything
+    let softness = max(shadow_softness, aa);
+    let shadow = clamp((shadow_dist + outline_width) / softness + 0.5, 0.0, 1.0);
+    color += shadow_color * shadow * (1.0 - color.a);
+
+    return color;
+}
+
+// the median of the color channels is the distance for both single and multi-channel fields,
+// single-channel fields store it in two channels and leave the third at zero
+fn sdf_distance(tex: vec2f) -> f32 {
+    let s = textureSample(main_texture, main_sampler, tex);
+    let median = max(min(s.r, s.g), min(max(s.r, s.g), s.b));
+    return (median - 0.5) * 2.0;
+}
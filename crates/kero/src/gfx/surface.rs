@@ -1,4 +1,4 @@
-use crate::gfx::{Graphics, Texture};
+use crate::gfx::{Graphics, Texture, create_depth_stencil, create_multisampled};
 use std::borrow::Borrow;
 use std::cmp::Ordering;
 use std::fmt::{Debug, Formatter};
@@ -20,6 +20,8 @@ pub struct Surface(Rc<Inner>);
 
 struct Inner {
     texture: Texture,
+    multisampled: Option<wgpu::Texture>,
+    depth_stencil: Option<wgpu::Texture>,
 
     #[cfg(feature = "lua")]
     texture_userdata: mlua::AnyUserData,
@@ -46,9 +48,20 @@ impl PartialOrd for Surface {
 }
 
 impl Surface {
-    #[allow(unused_variables)]
-    pub(crate) fn new(gfx: &Graphics, texture: Texture) -> Self {
+    pub(crate) fn new(
+        gfx: &Graphics,
+        texture: Texture,
+        sample_count: u32,
+        depth_stencil: bool,
+    ) -> Self {
+        let device = gfx.device();
+        let size = texture.size();
         Self(Rc::new(Inner {
+            multisampled: (sample_count > 1).then(|| {
+                create_multisampled(device, size, texture.0.texture.format(), sample_count)
+            }),
+            depth_stencil: depth_stencil.then(|| create_depth_stencil(device, size, sample_count)),
+
             #[cfg(feature = "lua")]
             texture_userdata: gfx
                 .lua()
@@ -66,6 +79,33 @@ impl Surface {
         &self.0.texture
     }
 
+    /// How many samples are taken per pixel when drawing to the surface. If this is more than
+    /// `1`, drawing is multisampled and then resolved into the surface's texture.
+    #[inline]
+    pub fn sample_count(&self) -> u32 {
+        self.0
+            .multisampled
+            .as_ref()
+            .map_or(1, |tex| tex.sample_count())
+    }
+
+    #[inline]
+    pub(crate) fn multisampled(&self) -> Option<&wgpu::Texture> {
+        self.0.multisampled.as_ref()
+    }
+
+    /// If the surface has a depth-stencil buffer, which is required for masking and depth
+    /// testing when drawing to it.
+    #[inline]
+    pub fn has_depth_stencil(&self) -> bool {
+        self.0.depth_stencil.is_some()
+    }
+
+    #[inline]
+    pub(crate) fn depth_stencil(&self) -> Option<&wgpu::Texture> {
+        self.0.depth_stencil.as_ref()
+    }
+
     #[cfg(feature = "lua")]
     pub fn texture_userdata(&self) -> &mlua::AnyUserData {
         &self.0.texture_userdata
@@ -94,3 +134,37 @@ impl Borrow<Texture> for Surface {
         &self.0.texture
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::color::Rgba8;
+    use crate::core::{GameBuilder, test_frame};
+    use crate::gfx::TextureFormat;
+
+    #[test]
+    fn msaa_surfaces() {
+        test_frame(GameBuilder::new().unwrap(), |ctx, draw| {
+            // unsupported sample counts are clamped to the highest supported one
+            let gfx = &ctx.graphics;
+            let surface = gfx.create_surface_msaa((8, 8), TextureFormat::Rgba8, 64);
+            assert!([4, 8, 16].contains(&surface.sample_count()));
+            let surface = gfx.create_surface_msaa((8, 8), TextureFormat::Rgba8, 1);
+            assert_eq!(surface.sample_count(), 1);
+
+            // multisampled surfaces can be drawn to with and without a depth-stencil buffer
+            for depth_stencil in [false, true] {
+                let surface =
+                    gfx.create_surface_ext((16, 16), TextureFormat::Rgba8, 4, depth_stencil);
+                assert_eq!(surface.sample_count(), 4);
+                draw.set_surface(surface.clone(), Rgba8::BLACK);
+                if depth_stencil {
+                    draw.push_mask(|draw| draw.rect((4.0, 4.0, 8.0, 8.0), Rgba8::WHITE))?;
+                }
+                draw.rect((0.0, 0.0, 16.0, 16.0), Rgba8::RED);
+                draw.set_surface(None, None);
+                draw.texture_at(&surface, (0.0, 0.0));
+            }
+            Ok(())
+        });
+    }
+}
@@ -0,0 +1,573 @@
+use crate::math::{RectF, Vec2F, vec2};
+use std::ops::Range;
+
+#[cfg(feature = "lua")]
+pub type TextLayoutObj = fey_lua::UserDataOf<TextLayout>;
+#[cfg(feature = "lua")]
+pub type TextLayoutRef = mlua::UserDataRef<TextLayout>;
+#[cfg(feature = "lua")]
+pub type TextLayoutMut = mlua::UserDataRefMut<TextLayout>;
+
+/// The metrics of a font that are needed to lay out text with it.
+pub trait FontMetrics {
+    /// How far the font extends above the baseline.
+    fn ascent(&self) -> f32;
+
+    /// How far the font extends below the baseline, which is usually negative.
+    fn descent(&self) -> f32;
+
+    /// Extra space between the bottom of one line and the top of the next.
+    fn line_gap(&self) -> f32;
+
+    /// How far to move the cursor after the char, or `None` if the font has no glyph for it.
+    fn advance(&self, chr: char) -> Option<f32>;
+
+    /// Extra horizontal space between two chars.
+    fn kerning(&self, left: char, right: char) -> Option<f32>;
+}
+
+/// How lines of text are aligned horizontally.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum TextAlign {
+    /// Lines start at the position.
+    #[default]
+    Left,
+
+    /// Lines are centered on the position.
+    Center,
+
+    /// Lines end at the position.
+    Right,
+
+    /// Lines start at the position, and lines that were wrapped have their spaces stretched so
+    /// they fill the max width.
+    Justify,
+}
+
+/// Which part of the text is placed at the position vertically.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum TextAnchor {
+    /// The top of the first line.
+    Top,
+
+    /// The middle of all the lines.
+    Middle,
+
+    /// The baseline of the first line.
+    #[default]
+    Baseline,
+
+    /// The bottom of the last line.
+    Bottom,
+}
+
+/// Options for laying out text.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct TextOptions {
+    /// If set, lines longer than this are wrapped, breaking between words where possible.
+    pub max_width: Option<f32>,
+
+    /// How lines are aligned horizontally. If there is a max width, lines are aligned within
+    /// it, otherwise they are aligned with the widest line.
+    pub align: TextAlign,
+
+    /// Which part of the text is placed at the position vertically.
+    pub anchor: TextAnchor,
+
+    /// Multiplier for the distance between lines.
+    pub line_spacing: f32,
+}
+
+impl Default for TextOptions {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            max_width: None,
+            align: TextAlign::Left,
+            anchor: TextAnchor::Baseline,
+            line_spacing: 1.0,
+        }
+    }
+}
+
+impl TextOptions {
+    #[inline]
+    pub fn with_max_width(self, max_width: impl Into<Option<f32>>) -> Self {
+        Self {
+            max_width: max_width.into(),
+            ..self
+        }
+    }
+
+    #[inline]
+    pub fn with_align(self, align: TextAlign) -> Self {
+        Self { align, ..self }
+    }
+
+    #[inline]
+    pub fn with_anchor(self, anchor: TextAnchor) -> Self {
+        Self { anchor, ..self }
+    }
+
+    #[inline]
+    pub fn with_line_spacing(self, line_spacing: f32) -> Self {
+        Self {
+            line_spacing,
+            ..self
+        }
+    }
+}
+
+/// A char placed by a [`TextLayout`].
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct LayoutGlyph {
+    /// The char.
+    pub chr: char,
+
+    /// The byte index of the char in the text.
+    pub index: usize,
+
+    /// Where the glyph is drawn, on the line's baseline.
+    pub pos: Vec2F,
+
+    /// Which line the glyph is on.
+    pub line: usize,
+}
+
+/// A line of text in a [`TextLayout`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct LayoutLine {
+    /// The line's range of glyphs in the layout.
+    pub glyphs: Range<usize>,
+
+    /// Where the line starts horizontally.
+    pub x: f32,
+
+    /// Vertical position of the line's baseline.
+    pub baseline: f32,
+
+    /// Width of the line.
+    pub width: f32,
+
+    /// If the line was wrapped because it was too long, rather than ending in a newline.
+    pub wrapped: bool,
+}
+
+/// Text that has been measured and laid out for a font, accounting for kerning, newlines, word
+/// wrapping, and alignment. Positions are in the font's units, relative to where the text is
+/// drawn.
+///
+/// Laying out text takes some work, so a layout can be kept and drawn every frame with
+/// [`Draw::text_layout`](super::Draw::text_layout), and only updated when the text changes.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TextLayout {
+    text: String,
+    options: TextOptions,
+    glyphs: Vec<LayoutGlyph>,
+    lines: Vec<LayoutLine>,
+    bounds: RectF,
+}
+
+impl TextLayout {
+    /// Lay out the text with the font.
+    pub fn new<F: FontMetrics + ?Sized>(font: &F, text: &str, options: TextOptions) -> Self {
+        let mut layout = Self {
+            text: text.to_string(),
+            options,
+            ..Default::default()
+        };
+        layout.relayout(font);
+        layout
+    }
+
+    /// Lay out new text or options, if they are different from the current ones. Returns
+    /// `true` if the layout changed.
+    pub fn update<F: FontMetrics + ?Sized>(
+        &mut self,
+        font: &F,
+        text: &str,
+        options: TextOptions,
+    ) -> bool {
+        if self.text == text && self.options == options {
+            return false;
+        }
+        self.text.clear();
+        self.text.push_str(text);
+        self.options = options;
+        self.relayout(font);
+        true
+    }
+
+    /// Lay out the text again, such as if the font's glyphs or metrics changed.
+    pub fn relayout<F: FontMetrics + ?Sized>(&mut self, font: &F) {
+        self.glyphs.clear();
+        self.lines.clear();
+
+        let mut builder = LineBuilder {
+            font,
+            max_width: self.options.max_width,
+            glyphs: &mut self.glyphs,
+            lines: &mut self.lines,
+            line_start: 0,
+            x: 0.0,
+            prev: None,
+        };
+
+        let mut para_start = 0;
+        for para in self.text.split('\n') {
+            let mut word_start = para_start;
+            for (i, word) in para
+                .strip_suffix('\r')
+                .unwrap_or(para)
+                .split(' ')
+                .enumerate()
+            {
+                // words after the first have a space before them, which is dropped if the line
+                // wraps there instead
+                if i > 0 {
+                    let fits = builder.is_line_empty()
+                        || builder
+                            .max_width
+                            .is_none_or(|max| builder.x + builder.measure(' ', word) <= max);
+                    match fits {
+                        true => builder.push(' ', word_start - 1),
+                        false => builder.end_line(true),
+                    }
+                }
+                builder.push_word(word, word_start);
+                word_start += word.len() + 1;
+            }
+            builder.end_line(false);
+            para_start += para.len() + 1;
+        }
+
+        self.place(font);
+    }
+
+    /// Position the lines based on the alignment and anchor.
+    fn place<F: FontMetrics + ?Sized>(&mut self, font: &F) {
+        let TextOptions {
+            max_width,
+            align,
+            anchor,
+            line_spacing,
+        } = self.options;
+
+        // lines are aligned in a box that is placed at the position based on the alignment
+        let box_w = max_width.unwrap_or_else(|| {
+            self.lines
+                .iter()
+                .fold(0.0, |w: f32, line| w.max(line.width))
+        });
+        let box_x = match align {
+            TextAlign::Left | TextAlign::Justify => 0.0,
+            TextAlign::Center => -box_w * 0.5,
+            TextAlign::Right => -box_w,
+        };
+
+        let ascent = font.ascent();
+        let descent = font.descent();
+        let line_height = (ascent - descent + font.line_gap()) * line_spacing;
+        let last_baseline = (self.lines.len().max(1) - 1) as f32 * line_height;
+        let height = last_baseline + ascent - descent;
+        let top = match anchor {
+            TextAnchor::Top => ascent,
+            TextAnchor::Middle => ascent - height * 0.5,
+            TextAnchor::Baseline => 0.0,
+            TextAnchor::Bottom => descent - last_baseline,
+        };
+
+        for (i, line) in self.lines.iter_mut().enumerate() {
+            line.baseline = top + i as f32 * line_height;
+            line.x = box_x
+                + match align {
+                    TextAlign::Left | TextAlign::Justify => 0.0,
+                    TextAlign::Center => (box_w - line.width) * 0.5,
+                    TextAlign::Right => box_w - line.width,
+                };
+
+            // stretch the spaces of wrapped lines so they fill the box
+            let glyphs = &mut self.glyphs[line.glyphs.clone()];
+            let spaces = glyphs.iter().filter(|g| g.chr == ' ').count();
+            let extra = match align == TextAlign::Justify && line.wrapped && spaces > 0 {
+                true => (box_w - line.width) / spaces as f32,
+                false => 0.0,
+            };
+            let mut offset = line.x;
+            for g in glyphs {
+                g.pos = vec2(g.pos.x + offset, line.baseline);
+                if g.chr == ' ' {
+                    offset += extra;
+                }
+            }
+            if extra > 0.0 {
+                line.width = box_w;
+            }
+        }
+
+        self.bounds = RectF::new(box_x, top - ascent, box_w, height);
+    }
+
+    /// The text that was laid out.
+    #[inline]
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    #[inline]
+    pub fn options(&self) -> &TextOptions {
+        &self.options
+    }
+
+    /// All the glyphs in the layout, including spaces.
+    #[inline]
+    pub fn glyphs(&self) -> &[LayoutGlyph] {
+        &self.glyphs
+    }
+
+    #[inline]
+    pub fn lines(&self) -> &[LayoutLine] {
+        &self.lines
+    }
+
+    /// The box the text is aligned in, from the top of the first line to the bottom of the last.
+    #[inline]
+    pub fn bounds(&self) -> RectF {
+        self.bounds
+    }
+
+    #[inline]
+    pub fn size(&self) -> Vec2F {
+        self.bounds.size()
+    }
+
+    #[inline]
+    pub fn width(&self) -> f32 {
+        self.bounds.w
+    }
+
+    #[inline]
+    pub fn height(&self) -> f32 {
+        self.bounds.h
+    }
+}
+
+/// Collects glyphs into lines, tracking the cursor and previous char for kerning.
+struct LineBuilder<'a, F: ?Sized> {
+    font: &'a F,
+    max_width: Option<f32>,
+    glyphs: &'a mut Vec<LayoutGlyph>,
+    lines: &'a mut Vec<LayoutLine>,
+    line_start: usize,
+    x: f32,
+    prev: Option<char>,
+}
+
+impl<F: FontMetrics + ?Sized> LineBuilder<'_, F> {
+    #[inline]
+    fn is_line_empty(&self) -> bool {
+        self.glyphs.len() == self.line_start
+    }
+
+    /// How far the char moves the cursor, including kerning with the previous char.
+    #[inline]
+    fn step(&self, prev: Option<char>, chr: char) -> Option<(f32, f32)> {
+        let adv = self.font.advance(chr)?;
+        let kern = prev
+            .and_then(|prev| self.font.kerning(prev, chr))
+            .unwrap_or(0.0);
+        Some((kern, adv))
+    }
+
+    /// Width of the char followed by the word, if they were added to the line.
+    fn measure(&self, chr: char, word: &str) -> f32 {
+        let mut prev = self.prev;
+        let mut w = 0.0;
+        for chr in std::iter::once(chr).chain(word.chars()) {
+            if let Some((kern, adv)) = self.step(prev, chr) {
+                w += kern + adv;
+                prev = Some(chr);
+            }
+        }
+        w
+    }
+
+    fn push(&mut self, chr: char, index: usize) {
+        if let Some((kern, adv)) = self.step(self.prev, chr) {
+            self.glyphs.push(LayoutGlyph {
+                chr,
+                index,
+                pos: vec2(self.x + kern, 0.0),
+                line: self.lines.len(),
+            });
+            self.x += kern + adv;
+            self.prev = Some(chr);
+        }
+    }
+
+    /// Add a word to the line, breaking it between chars if it's too long to fit on a line.
+    fn push_word(&mut self, word: &str, start: usize) {
+        for (i, chr) in word.char_indices() {
+            if let Some(max) = self.max_width
+                && !self.is_line_empty()
+                && self
+                    .step(self.prev, chr)
+                    .is_some_and(|(kern, adv)| self.x + kern + adv > max)
+            {
+                self.end_line(true);
+            }
+            self.push(chr, start + i);
+        }
+    }
+
+    fn end_line(&mut self, wrapped: bool) {
+        self.lines.push(LayoutLine {
+            glyphs: self.line_start..self.glyphs.len(),
+            x: 0.0,
+            baseline: 0.0,
+            width: self.x,
+            wrapped,
+        });
+        self.line_start = self.glyphs.len();
+        self.x = 0.0;
+        self.prev = None;
+    }
+}
+
+#[cfg(feature = "lua")]
+impl mlua::FromLua for TextAlign {
+    #[inline]
+    fn from_lua(value: mlua::Value, lua: &mlua::Lua) -> mlua::Result<Self> {
+        let s = mlua::BorrowedStr::from_lua(value, lua)?;
+        Ok(match s.as_ref() {
+            "left" => Self::Left,
+            "center" => Self::Center,
+            "right" => Self::Right,
+            "justify" => Self::Justify,
+            s => return Err(mlua::Error::runtime(format!("invalid text align {s:?}"))),
+        })
+    }
+}
+
+#[cfg(feature = "lua")]
+impl mlua::IntoLua for TextAlign {
+    #[inline]
+    fn into_lua(self, lua: &mlua::Lua) -> mlua::Result<mlua::Value> {
+        match self {
+            Self::Left => "left",
+            Self::Center => "center",
+            Self::Right => "right",
+            Self::Justify => "justify",
+        }
+        .into_lua(lua)
+    }
+}
+
+#[cfg(feature = "lua")]
+impl mlua::FromLua for TextAnchor {
+    #[inline]
+    fn from_lua(value: mlua::Value, lua: &mlua::Lua) -> mlua::Result<Self> {
+        let s = mlua::BorrowedStr::from_lua(value, lua)?;
+        Ok(match s.as_ref() {
+            "top" => Self::Top,
+            "middle" => Self::Middle,
+            "baseline" => Self::Baseline,
+            "bottom" => Self::Bottom,
+            s => return Err(mlua::Error::runtime(format!("invalid text anchor {s:?}"))),
+        })
+    }
+}
+
+#[cfg(feature = "lua")]
+impl mlua::IntoLua for TextAnchor {
+    #[inline]
+    fn into_lua(self, lua: &mlua::Lua) -> mlua::Result<mlua::Value> {
+        match self {
+            Self::Top => "top",
+            Self::Middle => "middle",
+            Self::Baseline => "baseline",
+            Self::Bottom => "bottom",
+        }
+        .into_lua(lua)
+    }
+}
+
+#[cfg(feature = "lua")]
+impl mlua::FromLua for TextOptions {
+    fn from_lua(value: mlua::Value, lua: &mlua::Lua) -> mlua::Result<Self> {
+        let t = mlua::Table::from_lua(value, lua)?;
+        Ok(Self {
+            max_width: t.get("max_width")?,
+            align: t.get::<Option<TextAlign>>("align")?.unwrap_or_default(),
+            anchor: t.get::<Option<TextAnchor>>("anchor")?.unwrap_or_default(),
+            line_spacing: t.get::<Option<f32>>("line_spacing")?.unwrap_or(1.0),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Every char is 4 wide, and "AV" is kerned by -1.
+    struct TestFont;
+
+    impl FontMetrics for TestFont {
+        fn ascent(&self) -> f32 {
+            6.0
+        }
+
+        fn descent(&self) -> f32 {
+            -2.0
+        }
+
+        fn line_gap(&self) -> f32 {
+            2.0
+        }
+
+        fn advance(&self, chr: char) -> Option<f32> {
+            (chr != '#').then_some(4.0)
+        }
+
+        fn kerning(&self, left: char, right: char) -> Option<f32> {
+            ((left, right) == ('A', 'V')).then_some(-1.0)
+        }
+    }
+
+    #[test]
+    fn layout_wrap_and_align() {
+        let layout = TextLayout::new(&TestFont, "AV#\nab", TextOptions::default());
+        let xs: Vec<f32> = layout.glyphs().iter().map(|g| g.pos.x).collect();
+        assert_eq!(xs, [0.0, 3.0, 0.0, 4.0]);
+        assert_eq!(layout.lines()[1].baseline, 10.0);
+        assert_eq!(layout.bounds(), RectF::new(0.0, -6.0, 8.0, 18.0));
+
+        // words wrap at spaces, and words too long for a line break between chars
+        let opts = TextOptions::default()
+            .with_max_width(20.0)
+            .with_align(TextAlign::Right)
+            .with_anchor(TextAnchor::Top);
+        let layout = TextLayout::new(&TestFont, "abc de fghijkl", opts);
+        let lines: Vec<(f32, f32, f32)> = layout
+            .lines()
+            .iter()
+            .map(|l| (l.x, l.baseline, l.width))
+            .collect();
+        assert_eq!(
+            lines,
+            [
+                (-12.0, 6.0, 12.0),
+                (-8.0, 16.0, 8.0),
+                (-20.0, 26.0, 20.0),
+                (-8.0, 36.0, 8.0)
+            ]
+        );
+
+        // justified lines that wrapped are stretched to fill the width
+        let opts = opts.with_align(TextAlign::Justify);
+        let layout = TextLayout::new(&TestFont, "ab c de", opts);
+        assert_eq!(layout.lines()[0].width, 20.0);
+        assert_eq!(layout.glyphs()[3].pos.x, 16.0);
+        assert_eq!(layout.lines()[1].width, 8.0);
+    }
+}
@@ -1,6 +1,8 @@
-use crate::gfx::{SubTexture, TextureFormat};
-use crate::grid::VecGrid;
+use crate::gfx::{SubTexture, TextureDownload, TextureDownloadError, TextureFormat, TexturePixel};
+use crate::grid::{Grid, VecGrid};
+use crate::img::{DynImage, Image, ImageFormat, ImageRgba8};
 use crate::math::{Numeric, RectU, Vec2U};
+use bytemuck::cast_slice;
 use std::cmp::Ordering;
 use std::fmt::{Debug, Formatter};
 use std::sync::Arc;
@@ -45,6 +47,7 @@ impl PartialOrd for Texture {
 #[derive(Debug)]
 pub(crate) struct Inner {
     pub texture: wgpu::Texture,
+    device: Device,
     queue: Queue,
     size: Vec2U,
     format: TextureFormat,
@@ -58,7 +61,8 @@ impl Texture {
         format: TextureFormat,
         surface: bool,
     ) -> Self {
-        let mut usage = TextureUsages::COPY_DST | TextureUsages::TEXTURE_BINDING;
+        let mut usage =
+            TextureUsages::COPY_DST | TextureUsages::COPY_SRC | TextureUsages::TEXTURE_BINDING;
         if surface {
             usage |= TextureUsages::RENDER_ATTACHMENT;
         }
@@ -78,6 +82,7 @@ impl Texture {
         });
         Self(Arc::new(Inner {
             texture,
+            device: device.clone(),
             queue,
             size,
             format,
@@ -86,14 +91,21 @@ impl Texture {
 
     pub(crate) fn upload_bytes(&self, data: &[u8]) {
         assert_eq!(data.len(), self.size_in_bytes());
-        let (width, height) = self.0.size.into();
-        let bytes_per_row = Some(self.0.format.bytes_per_pixel().to_u32() * width);
-        let rows_per_image = Some(height);
+        self.write_region(RectU::sized(self.0.size), data);
+    }
+
+    fn write_region(&self, rect: RectU, data: &[u8]) {
+        let bytes_per_row = Some(self.0.format.bytes_per_pixel().to_u32() * rect.w);
+        let rows_per_image = Some(rect.h);
         self.0.queue.write_texture(
             TexelCopyTextureInfo {
                 texture: &self.0.texture,
                 mip_level: 0,
-                origin: Origin3d::ZERO,
+                origin: Origin3d {
+                    x: rect.x,
+                    y: rect.y,
+                    z: 0,
+                },
                 aspect: TextureAspect::All,
             },
             data,
@@ -103,43 +115,165 @@ impl Texture {
                 rows_per_image,
             },
             Extent3d {
-                width,
-                height,
+                width: rect.w,
+                height: rect.h,
                 depth_or_array_layers: 1,
             },
         );
     }
 
-    // pub fn upload_pixels<P: TexturePixel>(&self, pixels: &[P]) -> Result<(), TextureUploadError> {
-    //     if P::TEXTURE_FORMAT != self.format() {
-    //         return Err(TextureUploadError::FormatMismatch {
-    //             expected: self.format(),
-    //             got: P::TEXTURE_FORMAT,
-    //         });
-    //     }
-    //     if pixels.len() < self.pixel_count() {
-    //         return Err(TextureUploadError::InsufficientPixels {
-    //             expected: self.pixel_count(),
-    //             got: pixels.len(),
-    //         });
-    //     }
-    //     self.upload_bytes(cast_slice(pixels));
-    //     Ok(())
-    // }
-
-    // pub fn upload_img<P: TexturePixel, S: AsRef<[P::Channel]>>(
-    //     &self,
-    //     img: &Image<P, S>,
-    // ) -> Result<(), TextureUploadError> {
-    //     if self.size() == img.size() {
-    //         self.upload_pixels(img.pixels())
-    //     } else {
-    //         Err(TextureUploadError::InvalidSize {
-    //             expected: self.size(),
-    //             got: img.size(),
-    //         })
-    //     }
-    // }
+    fn check_format<P: TexturePixel>(&self) -> Result<(), TextureUploadError> {
+        if P::TEXTURE_FORMAT == self.format() {
+            Ok(())
+        } else {
+            Err(TextureUploadError::FormatMismatch {
+                expected: self.format(),
+                got: P::TEXTURE_FORMAT,
+            })
+        }
+    }
+
+    fn check_region(&self, rect: RectU) -> Result<(), TextureUploadError> {
+        // check the edges without overflowing, since huge regions can come from scripts
+        let size = self.size();
+        let right = rect.x.checked_add(rect.w);
+        let bottom = rect.y.checked_add(rect.h);
+        if right.is_some_and(|r| r <= size.x) && bottom.is_some_and(|b| b <= size.y) {
+            Ok(())
+        } else {
+            Err(TextureUploadError::RegionOutOfBounds {
+                region: rect,
+                size: self.size(),
+            })
+        }
+    }
+
+    /// Upload pixels to the entire texture. The pixel type must match the texture's format.
+    pub fn upload_pixels<P: TexturePixel>(&self, pixels: &[P]) -> Result<(), TextureUploadError> {
+        self.check_format::<P>()?;
+        if pixels.len() < self.pixel_count() {
+            return Err(TextureUploadError::InsufficientPixels {
+                expected: self.pixel_count(),
+                got: pixels.len(),
+            });
+        }
+        self.write_region(
+            RectU::sized(self.size()),
+            cast_slice(&pixels[..self.pixel_count()]),
+        );
+        Ok(())
+    }
+
+    /// Upload an image to the entire texture. The image must be the same size as the texture,
+    /// and its pixel type must match the texture's format.
+    pub fn upload_img<P: TexturePixel, S: AsRef<[P::Channel]>>(
+        &self,
+        img: &Image<P, S>,
+    ) -> Result<(), TextureUploadError> {
+        if self.size() == img.size() {
+            self.upload_pixels(img.pixels())
+        } else {
+            Err(TextureUploadError::InvalidSize {
+                expected: self.size(),
+                got: img.size(),
+            })
+        }
+    }
+
+    /// Upload pixels to a region of the texture, leaving the rest of its pixels untouched. The
+    /// pixel type must match the texture's format, and the region must fit inside the texture.
+    pub fn upload_region<P: TexturePixel>(
+        &self,
+        rect: impl Into<RectU>,
+        pixels: &[P],
+    ) -> Result<(), TextureUploadError> {
+        let rect = rect.into();
+        self.check_format::<P>()?;
+        self.check_region(rect)?;
+        let count = rect.area().to_usize();
+        if pixels.len() < count {
+            return Err(TextureUploadError::InsufficientPixels {
+                expected: count,
+                got: pixels.len(),
+            });
+        }
+        self.write_region(rect, cast_slice(&pixels[..count]));
+        Ok(())
+    }
+
+    /// Upload an image to a region of the texture, leaving the rest of its pixels untouched.
+    /// The image must be the same size as the region, and its pixel type must match the
+    /// texture's format.
+    pub fn upload_img_region<P: TexturePixel, S: AsRef<[P::Channel]>>(
+        &self,
+        rect: impl Into<RectU>,
+        img: &Image<P, S>,
+    ) -> Result<(), TextureUploadError> {
+        let rect = rect.into();
+        if rect.size() == img.size() {
+            self.upload_region(rect, img.pixels())
+        } else {
+            Err(TextureUploadError::InvalidSize {
+                expected: rect.size(),
+                got: img.size(),
+            })
+        }
+    }
+
+    /// Upload a [`DynImage`] to the entire texture. The image must be the same size as the
+    /// texture, and its format must be the texture format's equivalent, so RGB images can
+    /// never be uploaded.
+    #[inline]
+    pub fn upload_dyn_img(&self, img: &DynImage) -> Result<(), TextureUploadError> {
+        self.upload_dyn_img_region(RectU::sized(self.size()), img)
+    }
+
+    /// Upload a [`DynImage`] to a region of the texture, leaving the rest of its pixels
+    /// untouched. The image must be the same size as the region, and its format must be the
+    /// texture format's equivalent, so RGB images can never be uploaded.
+    pub fn upload_dyn_img_region(
+        &self,
+        rect: impl Into<RectU>,
+        img: &DynImage,
+    ) -> Result<(), TextureUploadError> {
+        let rect = rect.into();
+        if img.format() != self.format().image_format() {
+            return Err(TextureUploadError::ImageFormatMismatch {
+                expected: self.format().image_format(),
+                got: img.format(),
+            });
+        }
+        self.check_region(rect)?;
+        if rect.size() != img.size() {
+            return Err(TextureUploadError::InvalidSize {
+                expected: rect.size(),
+                got: img.size(),
+            });
+        }
+        self.write_region(rect, img.bytes());
+        Ok(())
+    }
+
+    /// Start downloading the texture's pixels from the GPU. The download includes everything
+    /// drawn to the texture in frames that have already been rendered.
+    pub fn download(&self) -> TextureDownload {
+        let download = TextureDownload::new(self.0.device.clone());
+        download.start(&self.0.queue, &self.0.texture, self.0.format, false);
+        download
+    }
+
+    /// Download the texture's pixels from the GPU, blocking until they are available.
+    #[inline]
+    pub fn download_blocking(&self) -> Result<DynImage, TextureDownloadError> {
+        self.download().wait()
+    }
+
+    /// Download the texture's pixels from the GPU converted to [`Rgba8`](crate::color::Rgba8),
+    /// blocking until they are available.
+    #[inline]
+    pub fn download_rgba8_blocking(&self) -> Result<ImageRgba8, TextureDownloadError> {
+        self.download().wait_rgba8()
+    }
 
     /// Size of the texture in pixels.
     #[inline]
@@ -195,21 +329,38 @@ impl Texture {
     }
 }
 
-// /// An error uploading data to a texture.
-// #[derive(Debug, thiserror::Error)]
-// pub enum TextureUploadError {
-//     #[error("tried to upload pixels of type {expected:?} to texture of type {got:?}")]
-//     FormatMismatch {
-//         expected: TextureFormat,
-//         got: TextureFormat,
-//     },
-//
-//     #[error("tried to upload {got:?} pixels to texture that requires at least {expected:?}")]
-//     InsufficientPixels { expected: usize, got: usize },
-//
-//     #[error("tried to upload an image of size ({got}) to a texture of size ({expected})")]
-//     InvalidSize { expected: Vec2U, got: Vec2U },
-// }
+/// An error uploading data to a texture.
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum TextureUploadError {
+    #[error("tried to upload pixels of type {got:?} to texture of type {expected:?}")]
+    FormatMismatch {
+        expected: TextureFormat,
+        got: TextureFormat,
+    },
+
+    #[error("tried to upload an image of format {got:?} to texture of image format {expected:?}")]
+    ImageFormatMismatch {
+        expected: ImageFormat,
+        got: ImageFormat,
+    },
+
+    #[error("tried to upload {got:?} pixels to texture that requires at least {expected:?}")]
+    InsufficientPixels { expected: usize, got: usize },
+
+    #[error("tried to upload an image of size ({got}) to a region of size ({expected})")]
+    InvalidSize { expected: Vec2U, got: Vec2U },
+
+    #[error("tried to upload to region ({region}) of texture of size ({size})")]
+    RegionOutOfBounds { region: RectU, size: Vec2U },
+}
+
+#[cfg(feature = "lua")]
+impl From<TextureUploadError> for mlua::Error {
+    #[inline]
+    fn from(value: TextureUploadError) -> Self {
+        mlua::Error::external(value)
+    }
+}
 
 impl AsRef<Texture> for Texture {
     #[inline]
@@ -217,3 +368,30 @@ impl AsRef<Texture> for Texture {
         self
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::Rgba8;
+    use crate::core::{GameBuilder, test_frame};
+
+    #[test]
+    fn region_overflow() {
+        test_frame(GameBuilder::new().unwrap(), |ctx, _| {
+            let texture = ctx
+                .graphics
+                .create_texture(Vec2U::new(4, 4), &[Rgba8::WHITE; 16]);
+            let pixels = [Rgba8::WHITE; 4];
+            assert!(
+                texture
+                    .upload_region(RectU::new(2, 2, 2, 2), &pixels)
+                    .is_ok()
+            );
+            let err = texture
+                .upload_region(RectU::new(u32::MAX, 0, 2, 2), &pixels)
+                .unwrap_err();
+            assert!(matches!(err, TextureUploadError::RegionOutOfBounds { .. }));
+            Ok(())
+        });
+    }
+}
@@ -0,0 +1,331 @@
+use crate::gfx::TextureFormat;
+use crate::img::{DynImage, Image, ImageRgba8};
+use crate::math::{Numeric, Vec2U};
+use std::fmt::{Debug, Formatter};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+use wgpu::{
+    BufferAsyncError, BufferDescriptor, BufferUsages, COPY_BYTES_PER_ROW_ALIGNMENT,
+    CommandEncoderDescriptor, Device, Extent3d, MapMode, Origin3d, PollError, PollType, Queue,
+    TexelCopyBufferInfo, TexelCopyBufferLayout, TexelCopyTextureInfo, TextureAspect,
+};
+
+/// A pending download of a texture's pixels from the GPU.
+///
+/// Obtained from [`Texture::download`](super::Texture::download) or
+/// [`Graphics::screenshot`](super::Graphics::screenshot). The pixels are copied once the
+/// commands for the current frame have been submitted, and the download completes some time
+/// after that, usually a frame or two later.
+///
+/// The download can be awaited as a future, checked each frame with
+/// [`try_take`](Self::try_take), or blocked on with [`wait`](Self::wait). Futures are only woken
+/// when the graphics device is polled, which happens at the end of every frame.
+pub struct TextureDownload {
+    device: Device,
+    state: Arc<Mutex<State>>,
+}
+
+struct State {
+    result: Option<Result<DynImage, TextureDownloadError>>,
+    waker: Option<Waker>,
+    taken: bool,
+}
+
+impl Debug for TextureDownload {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("TextureDownload").finish_non_exhaustive()
+    }
+}
+
+impl TextureDownload {
+    pub(crate) fn new(device: Device) -> Self {
+        Self {
+            device,
+            state: Arc::new(Mutex::new(State {
+                result: None,
+                waker: None,
+                taken: false,
+            })),
+        }
+    }
+
+    /// Another handle to the same download, so it can be started later.
+    #[inline]
+    pub(crate) fn handle(&self) -> Self {
+        Self {
+            device: self.device.clone(),
+            state: self.state.clone(),
+        }
+    }
+
+    /// Copy the texture into a readback buffer and start mapping it. The texture must have been
+    /// created with [`COPY_SRC`](wgpu::TextureUsages::COPY_SRC) usage. If `bgra` is true, the
+    /// red and blue channels of the texture are swapped, which is needed for window frames.
+    ///
+    /// If the texture is too large to be copied into a single buffer, the download fails with
+    /// [`TextureDownloadError::TooLarge`].
+    pub(crate) fn start(
+        &self,
+        queue: &Queue,
+        texture: &wgpu::Texture,
+        format: TextureFormat,
+        bgra: bool,
+    ) {
+        let size = Vec2U::new(texture.width(), texture.height());
+        let row_size = format.bytes_per_pixel().to_u32() * size.x;
+        let padded_row = row_size.next_multiple_of(COPY_BYTES_PER_ROW_ALIGNMENT);
+        let buffer_size = padded_row.to_u64() * size.y.to_u64();
+        let max_size = self.device.limits().max_buffer_size;
+        if buffer_size > max_size {
+            self.fail(TextureDownloadError::TooLarge {
+                size: buffer_size,
+                max_size,
+            });
+            return;
+        }
+
+        // copy the texture into a buffer that can be read from the CPU
+        let buffer = self.device.create_buffer(&BufferDescriptor {
+            label: None,
+            size: buffer_size,
+            usage: BufferUsages::COPY_DST | BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+        let mut encoder = self
+            .device
+            .create_command_encoder(&CommandEncoderDescriptor { label: None });
+        encoder.copy_texture_to_buffer(
+            TexelCopyTextureInfo {
+                texture,
+                mip_level: 0,
+                origin: Origin3d::ZERO,
+                aspect: TextureAspect::All,
+            },
+            TexelCopyBufferInfo {
+                buffer: &buffer,
+                layout: TexelCopyBufferLayout {
+                    offset: 0,
+                    bytes_per_row: Some(padded_row),
+                    rows_per_image: Some(size.y),
+                },
+            },
+            Extent3d {
+                width: size.x,
+                height: size.y,
+                depth_or_array_layers: 1,
+            },
+        );
+        queue.submit([encoder.finish()]);
+
+        // once the copy is finished, read the pixels out of the buffer
+        let state = self.state.clone();
+        let mapped = buffer.clone();
+        buffer.map_async(MapMode::Read, .., move |result| {
+            let result = result.map_err(TextureDownloadError::from).map(|_| {
+                let bytes = mapped.get_mapped_range(..);
+                let img = read_pixels(&bytes, size, format, padded_row, bgra);
+                drop(bytes);
+                mapped.unmap();
+                img
+            });
+            state.lock().unwrap().finish(result);
+        });
+    }
+
+    /// Fail the download without starting it.
+    #[inline]
+    pub(crate) fn fail(&self, err: TextureDownloadError) {
+        self.state.lock().unwrap().finish(Err(err));
+    }
+
+    /// If the download has completed and its result hasn't been taken yet.
+    #[inline]
+    pub fn is_ready(&self) -> bool {
+        self.state.lock().unwrap().result.is_some()
+    }
+
+    /// Take the downloaded image if the download has completed, otherwise return `None`. Once
+    /// the image has been taken, this will return [`TextureDownloadError::AlreadyTaken`].
+    pub fn try_take(&self) -> Option<Result<DynImage, TextureDownloadError>> {
+        self.state.lock().unwrap().take()
+    }
+
+    /// Block until the download has completed and return the downloaded image.
+    pub fn wait(self) -> Result<DynImage, TextureDownloadError> {
+        loop {
+            if let Some(result) = self.try_take() {
+                return result;
+            }
+            self.device.poll(PollType::wait_indefinitely())?;
+        }
+    }
+
+    /// Block until the download has completed and return the downloaded image converted to
+    /// [`Rgba8`](crate::color::Rgba8).
+    #[inline]
+    pub fn wait_rgba8(self) -> Result<ImageRgba8, TextureDownloadError> {
+        self.wait().map(DynImage::to_rgba8)
+    }
+}
+
+impl Future for TextureDownload {
+    type Output = Result<DynImage, TextureDownloadError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = self.state.lock().unwrap();
+        match state.take() {
+            Some(result) => Poll::Ready(result),
+            None => {
+                state.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+impl State {
+    fn finish(&mut self, result: Result<DynImage, TextureDownloadError>) {
+        self.result = Some(result);
+        if let Some(waker) = self.waker.take() {
+            waker.wake();
+        }
+    }
+
+    fn take(&mut self) -> Option<Result<DynImage, TextureDownloadError>> {
+        if self.taken {
+            return Some(Err(TextureDownloadError::AlreadyTaken));
+        }
+        let result = self.result.take();
+        self.taken = result.is_some();
+        result
+    }
+}
+
+/// Strip the row padding from the copied bytes and convert them into an image.
+fn read_pixels(
+    bytes: &[u8],
+    size: Vec2U,
+    format: TextureFormat,
+    padded_row: u32,
+    bgra: bool,
+) -> DynImage {
+    let row_size = format.bytes_per_pixel() * size.x.to_usize();
+    let mut data = Vec::with_capacity(row_size * size.y.to_usize());
+    for row in bytes.chunks(padded_row.to_usize()).take(size.y.to_usize()) {
+        data.extend_from_slice(&row[..row_size]);
+    }
+    if bgra {
+        for px in data.chunks_exact_mut(4) {
+            px.swap(0, 2);
+        }
+    }
+    match format {
+        TextureFormat::R8 => DynImage::Grey8(Image::from_raw(size, data)),
+        TextureFormat::R16 => DynImage::Grey16(Image::from_raw(size, cast(&data))),
+        TextureFormat::R32F => DynImage::Grey32F(Image::from_raw(size, cast(&data))),
+        TextureFormat::Rg8 => DynImage::GreyAlpha8(Image::from_raw(size, data)),
+        TextureFormat::Rg16 => DynImage::GreyAlpha16(Image::from_raw(size, cast(&data))),
+        TextureFormat::Rg32F => DynImage::GreyAlpha32F(Image::from_raw(size, cast(&data))),
+        TextureFormat::Rgba8 => DynImage::Rgba8(Image::from_raw(size, data)),
+        TextureFormat::Rgba16 => DynImage::Rgba16(Image::from_raw(size, cast(&data))),
+        TextureFormat::Rgba32F => DynImage::Rgba32F(Image::from_raw(size, cast(&data))),
+    }
+}
+
+#[inline]
+fn cast<T: bytemuck::Pod>(bytes: &[u8]) -> Vec<T> {
+    bytemuck::pod_collect_to_vec(bytes)
+}
+
+/// An error downloading pixels from a texture.
+#[derive(Debug, thiserror::Error)]
+pub enum TextureDownloadError {
+    #[error("failed to read the texture from the graphics device: {0}")]
+    Map(#[from] BufferAsyncError),
+
+    #[error("failed to wait for the graphics device: {0}")]
+    Poll(#[from] PollError),
+
+    #[error("the window's frames cannot be read back on this platform")]
+    Unsupported,
+
+    #[error("the downloaded image was already taken")]
+    AlreadyTaken,
+
+    #[error("the texture needs {size} bytes to download, but the limit is {max_size}")]
+    TooLarge { size: u64, max_size: u64 },
+}
+
+#[cfg(feature = "lua")]
+impl From<TextureDownloadError> for mlua::Error {
+    #[inline]
+    fn from(value: TextureDownloadError) -> Self {
+        mlua::Error::external(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::GameBuilder;
+
+    #[test]
+    fn read_padded_pixels() {
+        // two rows of two bgra pixels, each padded out to 12 bytes
+        let bytes = [
+            1, 2, 3, 4, 5, 6, 7, 8, 0, 0, 0, 0, //
+            9, 10, 11, 12, 13, 14, 15, 16, 0, 0, 0, 0,
+        ];
+        let img = read_pixels(&bytes, Vec2U::new(2, 2), TextureFormat::Rgba8, 12, true);
+        assert_eq!(
+            img.bytes(),
+            [3, 2, 1, 4, 7, 6, 5, 8, 11, 10, 9, 12, 15, 14, 13, 16]
+        );
+
+        let bytes = [0, 0, 128, 63, 0, 0, 0, 0, 0, 0, 0, 64, 0, 0, 0, 0];
+        let img = read_pixels(&bytes, Vec2U::new(1, 2), TextureFormat::R32F, 8, false);
+        let DynImage::Grey32F(img) = img else {
+            panic!("expected a Grey32F image");
+        };
+        assert_eq!(img.channels(), [1.0, 2.0]);
+    }
+
+    #[test]
+    fn screenshot() {
+        let mut runner = GameBuilder::new()
+            .unwrap()
+            .with_size(64, 32)
+            .build_headless::<()>(())
+            .unwrap();
+
+        let screenshot = runner.ctx().graphics.screenshot();
+        assert!(!screenshot.is_ready());
+        runner.step().unwrap();
+        let img = screenshot.wait().unwrap();
+        assert_eq!(img.size(), Vec2U::new(64, 32));
+    }
+
+    #[test]
+    fn download_too_large() {
+        let runner = GameBuilder::new()
+            .unwrap()
+            .build_headless::<()>(())
+            .unwrap();
+
+        // 8192 * 2049 pixels * 16 bytes is just over the default 256 MiB buffer limit
+        let gfx = &runner.ctx().graphics;
+        let surface = gfx.create_surface((8192, 2049), TextureFormat::Rgba32F);
+        let Err(err) = surface.download_blocking() else {
+            panic!("expected the download to fail");
+        };
+        assert!(matches!(
+            err,
+            TextureDownloadError::TooLarge {
+                size: 268_566_528,
+                max_size: 268_435_456,
+            }
+        ));
+    }
+}
@@ -0,0 +1,128 @@
+use crate::input::{GamepadAxis, GamepadButton, Key, MouseButton, VirtualSource};
+use serde::{Deserialize, Serialize};
+
+/// An input that a [`VirtualButton`](super::VirtualButton) can be bound to.
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Binding {
+    /// A keyboard key.
+    Key(Key),
+
+    /// A mouse button.
+    Mouse(MouseButton),
+
+    /// A gamepad button.
+    Button(GamepadButton),
+
+    /// A gamepad axis pushed past a threshold. A positive threshold is down when the axis is
+    /// above it, a negative threshold is down when the axis is below it.
+    Axis { axis: GamepadAxis, threshold: f32 },
+}
+
+impl From<Key> for Binding {
+    #[inline]
+    fn from(value: Key) -> Self {
+        Self::Key(value)
+    }
+}
+
+impl From<MouseButton> for Binding {
+    #[inline]
+    fn from(value: MouseButton) -> Self {
+        Self::Mouse(value)
+    }
+}
+
+impl From<GamepadButton> for Binding {
+    #[inline]
+    fn from(value: GamepadButton) -> Self {
+        Self::Button(value)
+    }
+}
+
+impl Binding {
+    /// Default threshold used for axis bindings.
+    pub const AXIS_THRESHOLD: f32 = 0.5;
+
+    /// Bind to the axis being pushed past a threshold.
+    #[inline]
+    pub const fn axis(axis: GamepadAxis, threshold: f32) -> Self {
+        Self::Axis { axis, threshold }
+    }
+
+    /// If this is a gamepad button or axis, rather than a keyboard or mouse input.
+    #[inline]
+    pub fn is_gamepad(&self) -> bool {
+        matches!(self, Self::Button(_) | Self::Axis { .. })
+    }
+
+    #[inline]
+    fn past(value: f32, threshold: f32) -> bool {
+        if threshold < 0.0 {
+            value <= threshold
+        } else {
+            value >= threshold
+        }
+    }
+
+    /// If the input is down.
+    pub fn down(&self, source: &VirtualSource) -> bool {
+        match *self {
+            Self::Key(key) => source.keyboard().down(key),
+            Self::Mouse(btn) => source.mouse().down(btn),
+            Self::Button(btn) => source.read(|pad| pad.down(btn)).unwrap_or(false),
+            Self::Axis { axis, threshold } => source
+                .read(|pad| Self::past(pad.axis(axis), threshold))
+                .unwrap_or(false),
+        }
+    }
+
+    /// If the input was pressed this frame.
+    pub fn pressed(&self, source: &VirtualSource) -> bool {
+        match *self {
+            Self::Key(key) => source.keyboard().pressed(key),
+            Self::Mouse(btn) => source.mouse().pressed(btn),
+            Self::Button(btn) => source.read(|pad| pad.pressed(btn)).unwrap_or(false),
+            Self::Axis { axis, threshold } => source
+                .read(|pad| {
+                    Self::past(pad.axis(axis), threshold)
+                        && !Self::past(pad.prev_axis(axis), threshold)
+                })
+                .unwrap_or(false),
+        }
+    }
+
+    /// If the input was released this frame.
+    pub fn released(&self, source: &VirtualSource) -> bool {
+        match *self {
+            Self::Key(key) => source.keyboard().released(key),
+            Self::Mouse(btn) => source.mouse().released(btn),
+            Self::Button(btn) => source.read(|pad| pad.released(btn)).unwrap_or(false),
+            Self::Axis { axis, threshold } => source
+                .read(|pad| {
+                    !Self::past(pad.axis(axis), threshold)
+                        && Self::past(pad.prev_axis(axis), threshold)
+                })
+                .unwrap_or(false),
+        }
+    }
+
+    /// Value of the input from `0.0` (fully up) to `1.0` (fully down).
+    pub fn value(&self, source: &VirtualSource) -> f32 {
+        match *self {
+            Self::Key(_) | Self::Mouse(_) => match self.down(source) {
+                true => 1.0,
+                false => 0.0,
+            },
+            Self::Button(btn) => source.read(|pad| pad.value(btn)).unwrap_or(0.0),
+            Self::Axis { axis, threshold } => source
+                .read(|pad| {
+                    let value = pad.axis(axis);
+                    match Self::past(value, threshold) {
+                        true => (value * threshold.signum()).clamp(0.0, 1.0),
+                        false => 0.0,
+                    }
+                })
+                .unwrap_or(0.0),
+        }
+    }
+}
@@ -0,0 +1,80 @@
+use crate::input::{Binding, GamepadAxis, VirtualAxis, VirtualStick};
+use serde::{Deserialize, Serialize};
+
+/// A snapshot of all the bindings of a [`VirtualController`](super::VirtualController).
+///
+/// Layouts can be serialized, so players' remapped controls can be saved and restored later.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ControllerLayout {
+    pub direction: StickLayout,
+    pub left_stick: StickLayout,
+    pub right_stick: StickLayout,
+    pub left_bumper: Vec<Binding>,
+    pub right_bumper: Vec<Binding>,
+    pub left_trigger: Vec<Binding>,
+    pub right_trigger: Vec<Binding>,
+    pub dpad_left: Vec<Binding>,
+    pub dpad_right: Vec<Binding>,
+    pub dpad_up: Vec<Binding>,
+    pub dpad_down: Vec<Binding>,
+    pub east: Vec<Binding>,
+    pub south: Vec<Binding>,
+    pub west: Vec<Binding>,
+    pub north: Vec<Binding>,
+    pub start: Vec<Binding>,
+    pub select: Vec<Binding>,
+    pub menu: Vec<Binding>,
+}
+
+/// The bindings of a [`VirtualStick`].
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StickLayout {
+    pub x: AxisLayout,
+    pub y: AxisLayout,
+}
+
+/// The bindings of a [`VirtualAxis`].
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AxisLayout {
+    pub axes: Vec<GamepadAxis>,
+    pub neg: Vec<Binding>,
+    pub pos: Vec<Binding>,
+}
+
+impl StickLayout {
+    /// Copy the bindings of the stick.
+    pub fn from_stick(stick: &VirtualStick) -> Self {
+        Self {
+            x: AxisLayout::from_axis(&stick.x_axis()),
+            y: AxisLayout::from_axis(&stick.y_axis()),
+        }
+    }
+
+    /// Apply the bindings to the stick.
+    pub fn apply(&self, stick: &VirtualStick) {
+        self.x.apply(&stick.x_axis());
+        self.y.apply(&stick.y_axis());
+    }
+}
+
+impl AxisLayout {
+    /// Copy the bindings of the axis.
+    pub fn from_axis(axis: &VirtualAxis) -> Self {
+        Self {
+            axes: axis.axes(),
+            neg: axis.neg().bindings(),
+            pos: axis.pos().bindings(),
+        }
+    }
+
+    /// Apply the bindings to the axis. Its buttons are updated in place, so buttons shared with
+    /// other inputs will stay shared.
+    pub fn apply(&self, axis: &VirtualAxis) {
+        axis.set_axes(self.axes.iter().copied());
+        axis.neg().set_bindings(self.neg.iter().copied());
+        axis.pos().set_bindings(self.pos.iter().copied());
+    }
+}
@@ -0,0 +1,133 @@
+use crate::math::{Vec2F, vec2};
+use serde::{Deserialize, Serialize};
+
+/// A deadzone applied to virtual axes and sticks.
+///
+/// Input with a magnitude below `inner` is treated as zero, which stops worn controllers from
+/// drifting, and input with a magnitude above `outer` is treated as fully pushed.
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Deadzone {
+    pub shape: DeadzoneShape,
+    pub inner: f32,
+    pub outer: f32,
+}
+
+/// How a [`Deadzone`] is applied to a 2D stick.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum DeadzoneShape {
+    /// Each axis is cut off and rescaled separately. Makes it easy to hold an exact cardinal
+    /// direction, but distorts diagonals.
+    Axial,
+
+    /// The stick's length is cut off, but not rescaled, so there is a jump in value when
+    /// leaving the deadzone.
+    Radial,
+
+    /// The stick's length is cut off and rescaled, so the value smoothly ramps up from zero
+    /// when leaving the deadzone.
+    ScaledRadial,
+}
+
+impl Default for Deadzone {
+    #[inline]
+    fn default() -> Self {
+        Self::NONE
+    }
+}
+
+impl Deadzone {
+    /// A deadzone that leaves input unchanged.
+    pub const NONE: Self = Self::scaled_radial(0.0, 1.0);
+
+    /// Create an axial deadzone.
+    #[inline]
+    pub const fn axial(inner: f32, outer: f32) -> Self {
+        Self::new(DeadzoneShape::Axial, inner, outer)
+    }
+
+    /// Create a radial deadzone.
+    #[inline]
+    pub const fn radial(inner: f32, outer: f32) -> Self {
+        Self::new(DeadzoneShape::Radial, inner, outer)
+    }
+
+    /// Create a scaled radial deadzone.
+    #[inline]
+    pub const fn scaled_radial(inner: f32, outer: f32) -> Self {
+        Self::new(DeadzoneShape::ScaledRadial, inner, outer)
+    }
+
+    /// Create a new deadzone.
+    #[inline]
+    pub const fn new(shape: DeadzoneShape, inner: f32, outer: f32) -> Self {
+        Self {
+            shape,
+            inner,
+            outer,
+        }
+    }
+
+    /// Map a magnitude from `0.0` to `1.0` through the deadzone.
+    fn magnitude(&self, len: f32, scaled: bool) -> f32 {
+        if len < self.inner {
+            0.0
+        } else if len >= self.outer {
+            1.0
+        } else if scaled {
+            (len - self.inner) / (self.outer - self.inner)
+        } else {
+            len
+        }
+    }
+
+    /// Apply the deadzone to a single axis value from `-1.0` to `1.0`.
+    pub fn apply_axis(&self, value: f32) -> f32 {
+        let scaled = self.shape != DeadzoneShape::Radial;
+        self.magnitude(value.abs(), scaled).copysign(value)
+    }
+
+    /// Apply the deadzone to a stick value. The result will never be longer than `1.0`.
+    pub fn apply(&self, value: Vec2F) -> Vec2F {
+        match self.shape {
+            DeadzoneShape::Axial => {
+                let value = vec2(self.apply_axis(value.x), self.apply_axis(value.y));
+                let len = value.len();
+                if len > 1.0 { value / len } else { value }
+            }
+            DeadzoneShape::Radial | DeadzoneShape::ScaledRadial => {
+                let len = value.len();
+                if len > 0.0 {
+                    let scaled = self.shape == DeadzoneShape::ScaledRadial;
+                    value * (self.magnitude(len, scaled) / len)
+                } else {
+                    Vec2F::ZERO
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deadzone_shapes() {
+        assert_eq!(Deadzone::NONE.apply(vec2(0.25, -0.5)), vec2(0.25, -0.5));
+        assert_eq!(Deadzone::NONE.apply(vec2(0.0, -2.0)), vec2(0.0, -1.0));
+
+        let axial = Deadzone::axial(0.25, 0.75);
+        assert_eq!(axial.apply(vec2(0.125, -0.5)), vec2(0.0, -0.5));
+        assert!((axial.apply(vec2(0.5, 1.0)).len() - 1.0).abs() < 1e-6);
+        assert_eq!(axial.apply_axis(-0.875), -1.0);
+
+        let radial = Deadzone::radial(0.25, 0.75);
+        assert_eq!(radial.apply(vec2(0.125, 0.125)), Vec2F::ZERO);
+        assert_eq!(radial.apply(vec2(0.0, 0.5)), vec2(0.0, 0.5));
+
+        let scaled = Deadzone::scaled_radial(0.25, 0.75);
+        assert_eq!(scaled.apply(vec2(0.125, 0.125)), Vec2F::ZERO);
+        assert_eq!(scaled.apply(vec2(0.0, 0.5)), vec2(0.0, 0.5));
+        assert_eq!(scaled.apply(vec2(-0.375, 0.0)), vec2(-0.25, 0.0));
+    }
+}
@@ -0,0 +1,163 @@
+use crate::math::Vec2F;
+use std::cell::{Cell, RefCell};
+use std::fmt::{Debug, Formatter};
+use std::path::PathBuf;
+use std::rc::Rc;
+
+/// Handle to the state of files being dragged and dropped onto the window.
+///
+/// This handle can be cloned and passed around freely to give objects access to file drops.
+///
+/// Obtained from [`Context`](crate::core::Context). Positions are in window coordinates and are
+/// taken from the mouse position when the files were hovered or dropped, use
+/// [`Screen::drop_pos`](crate::gfx::Screen::drop_pos) and
+/// [`Screen::hover_pos`](crate::gfx::Screen::hover_pos) for on-screen positions.
+#[derive(Clone)]
+pub struct FileDrop(Rc<State>);
+
+impl Debug for FileDrop {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("FileDrop").finish_non_exhaustive()
+    }
+}
+
+#[derive(Debug, Default)]
+struct State {
+    hovered: RefCell<Vec<PathBuf>>,
+    hover_pos: Cell<Option<Vec2F>>,
+    phases: [Phase; 2],
+    phase: Cell<usize>,
+}
+
+#[derive(Debug, Default)]
+struct Phase {
+    dropped: RefCell<Vec<PathBuf>>,
+    drop_pos: Cell<Option<Vec2F>>,
+    hover_cancelled: Cell<bool>,
+}
+
+impl FileDrop {
+    pub(crate) fn new() -> Self {
+        Self(Rc::new(State::default()))
+    }
+
+    #[inline]
+    fn phase(&self) -> &Phase {
+        &self.0.phases[self.0.phase.get()]
+    }
+
+    /// If files are currently being dragged over the window.
+    #[inline]
+    pub fn hovering(&self) -> bool {
+        !self.0.hovered.borrow().is_empty()
+    }
+
+    /// Paths of the files currently being dragged over the window.
+    #[inline]
+    pub fn hovered_files(&self) -> Vec<PathBuf> {
+        self.0.hovered.borrow().clone()
+    }
+
+    /// Position where files are currently being dragged over the window.
+    #[inline]
+    pub fn hover_pos(&self) -> Option<Vec2F> {
+        self.0.hover_pos.get()
+    }
+
+    /// If files were dragged over the window and then away without being dropped this frame.
+    #[inline]
+    pub fn hover_cancelled(&self) -> bool {
+        self.phase().hover_cancelled.get()
+    }
+
+    /// If files were dropped onto the window this frame.
+    #[inline]
+    pub fn dropped(&self) -> bool {
+        !self.phase().dropped.borrow().is_empty()
+    }
+
+    /// Paths of the files that were dropped onto the window this frame.
+    #[inline]
+    pub fn dropped_files(&self) -> Vec<PathBuf> {
+        self.phase().dropped.borrow().clone()
+    }
+
+    /// Position where files were dropped onto the window this frame.
+    #[inline]
+    pub fn drop_pos(&self) -> Option<Vec2F> {
+        self.phase().drop_pos.get()
+    }
+
+    #[inline]
+    pub(crate) fn set_update_phase(&self) {
+        self.0.phase.set(0);
+    }
+
+    #[inline]
+    pub(crate) fn set_render_phase(&self) {
+        self.0.phase.set(1);
+    }
+
+    pub(crate) fn handle_hover(&self, path: PathBuf, pos: Vec2F) {
+        self.0.hovered.borrow_mut().push(path);
+        self.0.hover_pos.set(Some(pos));
+    }
+
+    pub(crate) fn handle_drop(&self, path: PathBuf, pos: Vec2F) {
+        self.0.hovered.borrow_mut().clear();
+        self.0.hover_pos.set(None);
+        for phase in &self.0.phases {
+            phase.dropped.borrow_mut().push(path.clone());
+            phase.drop_pos.set(Some(pos));
+        }
+    }
+
+    pub(crate) fn handle_hover_cancelled(&self) {
+        self.0.hovered.borrow_mut().clear();
+        self.0.hover_pos.set(None);
+        for phase in &self.0.phases {
+            phase.hover_cancelled.set(true);
+        }
+    }
+
+    #[inline]
+    pub(crate) fn clear_phase(&self) {
+        let phase = self.phase();
+        phase.dropped.borrow_mut().clear();
+        phase.drop_pos.set(None);
+        phase.hover_cancelled.set(false);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn drop_phases() {
+        let drop = FileDrop::new();
+        drop.handle_hover("a.png".into(), Vec2F::new(1.0, 2.0));
+        drop.handle_hover("b.png".into(), Vec2F::new(1.0, 2.0));
+        assert!(drop.hovering());
+        assert_eq!(drop.hover_pos(), Some(Vec2F::new(1.0, 2.0)));
+
+        drop.handle_drop("a.png".into(), Vec2F::new(3.0, 4.0));
+        drop.handle_drop("b.png".into(), Vec2F::new(3.0, 4.0));
+        assert!(!drop.hovering());
+
+        // the drop is visible to the update, then the render
+        assert_eq!(drop.dropped_files().len(), 2);
+        drop.clear_phase();
+        assert!(!drop.dropped());
+        drop.set_render_phase();
+        assert_eq!(drop.drop_pos(), Some(Vec2F::new(3.0, 4.0)));
+        drop.clear_phase();
+        assert!(!drop.dropped());
+        drop.set_update_phase();
+
+        drop.handle_hover("c.png".into(), Vec2F::ZERO);
+        drop.handle_hover_cancelled();
+        assert!(!drop.hovering());
+        assert!(drop.hover_cancelled());
+    }
+}
@@ -1,6 +1,6 @@
-use crate::input::{GamepadButton, GamepadStatus};
+use crate::input::{GamepadButton, GamepadStatus, Rumble, RumbleEffects};
 use gilrs::{GamepadId, Gilrs};
-use std::cell::Cell;
+use std::cell::{Cell, RefCell};
 use std::cmp::Ordering;
 use std::fmt::{Debug, Formatter};
 use std::{rc::Rc, time::SystemTime};
@@ -35,8 +35,15 @@ impl PartialOrd for Gamepad {
     }
 }
 
+/// Identifies a connected gamepad, which is either a real device or a simulated one.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub(crate) enum PadId {
+    Gilrs(GamepadId),
+    Virtual(u32),
+}
+
 struct State {
-    id: GamepadId,
+    id: PadId,
     name: String,
     status: Cell<GamepadStatus>,
     down: Cell<[bool; GamepadButton::COUNT]>,
@@ -47,6 +54,7 @@ struct State {
     last_update: Cell<SystemTime>,
     connect_time: SystemTime,
     connected: Cell<bool>,
+    rumble: RefCell<RumbleEffects>,
 }
 
 #[derive(Clone)]
@@ -57,6 +65,7 @@ struct Phase {
     repeated: Cell<[bool; GamepadButton::COUNT]>,
     btn_changed: Cell<[bool; GamepadButton::COUNT]>,
     axis_changed: Cell<[bool; GamepadAxis::COUNT]>,
+    axis_prev: Cell<[f32; GamepadAxis::COUNT]>,
 }
 
 impl Default for Phase {
@@ -68,13 +77,14 @@ impl Default for Phase {
             repeated: Cell::new([false; _]),
             btn_changed: Cell::new([false; _]),
             axis_changed: Cell::new([false; _]),
+            axis_prev: Cell::new([0.0; _]),
         }
     }
 }
 
 impl Gamepad {
     pub(crate) fn new(
-        id: GamepadId,
+        id: PadId,
         name: String,
         status: GamepadStatus,
         connect_time: SystemTime,
@@ -91,6 +101,7 @@ impl Gamepad {
             last_update: Cell::new(connect_time),
             connect_time,
             connected: Cell::new(true),
+            rumble: RefCell::new(RumbleEffects::default()),
         }))
     }
 
@@ -100,6 +111,7 @@ impl Gamepad {
         self.0.down.set([false; _]);
         self.0.btn_value.set([0.0; _]);
         self.0.axis_value.set([0.0; _]);
+        self.0.rumble.borrow_mut().stop_all();
         for phase in &self.0.phases {
             phase.was_connected.set(false);
             phase.pressed.set([false; _]);
@@ -107,6 +119,7 @@ impl Gamepad {
             phase.released.set([false; _]);
             phase.btn_changed.set([false; _]);
             phase.axis_changed.set([false; _]);
+            phase.axis_prev.set([0.0; _]);
         }
     }
 
@@ -187,19 +200,80 @@ impl Gamepad {
         Cell::as_array_of_cells(&self.0.axis_value)[axis as usize].get()
     }
 
+    /// The axis value at the end of the previous frame, from `-1.0` to `1.0`.
+    #[inline]
+    pub fn prev_axis(&self, axis: GamepadAxis) -> f32 {
+        Cell::as_array_of_cells(&self.phase().axis_prev)[axis as usize].get()
+    }
+
     /// If the axis changed this frame.
     #[inline]
     pub fn axis_changed(&self, axis: GamepadAxis) -> bool {
         Cell::as_array_of_cells(&self.phase().axis_changed)[axis as usize].get()
     }
 
+    /// Rumble the gamepad's strong (low frequency) and weak (high frequency) motors, each from
+    /// `0.0` to `1.0`, for the duration in seconds. Use `f32::INFINITY` to rumble until stopped.
+    ///
+    /// Rumble effects are layered, so multiple effects can play at once, in which case each motor
+    /// runs at the level of the strongest effect. The returned handle can be used to stop this
+    /// specific effect early.
+    #[inline]
+    pub fn rumble(&self, strong: f32, weak: f32, duration: f32) -> Rumble {
+        self.0.rumble.borrow_mut().add(strong, weak, duration)
+    }
+
+    /// Stop a rumble effect.
+    #[inline]
+    pub fn stop_rumble(&self, rumble: Rumble) {
+        self.0.rumble.borrow_mut().stop(rumble);
+    }
+
+    /// Stop all rumble effects.
+    #[inline]
+    pub fn stop_all_rumble(&self) {
+        self.0.rumble.borrow_mut().stop_all();
+    }
+
+    /// If the rumble effect is still playing.
+    #[inline]
+    pub fn is_rumbling(&self, rumble: Rumble) -> bool {
+        self.0.rumble.borrow().is_playing(rumble)
+    }
+
+    /// The current levels of the strong and weak motors, from `0.0` to `1.0`, after the global
+    /// rumble intensity is applied. This is updated once per frame, and is also available for
+    /// simulated gamepads, which have no motors.
+    #[inline]
+    pub fn rumble_levels(&self) -> (f32, f32) {
+        self.0.rumble.borrow().levels()
+    }
+
+    #[inline]
+    pub(crate) fn update_rumble(&self, delta: f32, intensity: f32) -> Option<(f32, f32)> {
+        self.0.rumble.borrow_mut().update(delta, intensity)
+    }
+
     #[inline]
     pub(crate) fn update_status(&self, gilrs: &Gilrs, time: SystemTime) {
-        let power_info = gilrs.connected_gamepad(self.0.id).unwrap().power_info();
-        self.0.status.set(GamepadStatus::from(power_info));
+        if let PadId::Gilrs(id) = self.0.id {
+            let power_info = gilrs.connected_gamepad(id).unwrap().power_info();
+            self.0.status.set(GamepadStatus::from(power_info));
+        }
         self.0.last_update.set(time);
     }
 
+    #[inline]
+    pub(crate) fn id(&self) -> PadId {
+        self.0.id
+    }
+
+    pub(crate) fn handle_connect(&self) {
+        for phase in &self.0.phases {
+            phase.was_connected.set(true);
+        }
+    }
+
     pub(crate) fn handle_press(&self, btn: GamepadButton) {
         let btn = btn as usize;
         Cell::as_array_of_cells(&self.0.down)[btn].set(true);
@@ -247,5 +321,6 @@ impl Gamepad {
         phase.released.set([false; _]);
         phase.btn_changed.set([false; _]);
         phase.axis_changed.set([false; _]);
+        phase.axis_prev.set(self.0.axis_value.get());
     }
 }
@@ -1,9 +1,24 @@
 use gilrs::Axis;
-use strum::{EnumCount, FromRepr, VariantArray};
+use serde::{Deserialize, Serialize};
+use strum::{EnumCount, EnumString, FromRepr, IntoStaticStr, VariantArray};
 
 /// A gamepad axis.
 #[derive(
-    Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, EnumCount, FromRepr, VariantArray,
+    Debug,
+    Copy,
+    Clone,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Hash,
+    Serialize,
+    Deserialize,
+    EnumCount,
+    FromRepr,
+    VariantArray,
+    IntoStaticStr,
+    EnumString,
 )]
 pub enum GamepadAxis {
     LeftX,
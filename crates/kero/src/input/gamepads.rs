@@ -1,7 +1,7 @@
-use super::{Gamepad, GamepadAxis, GamepadButton, GamepadStatus};
+use super::{Gamepad, GamepadAxis, GamepadButton, GamepadStatus, PadId, RumbleMotors};
 use crate::core::Context;
 use fnv::FnvHashMap;
-use gilrs::{Event, EventType, GamepadId, Gilrs};
+use gilrs::{Event, EventType, Gilrs};
 use smallvec::SmallVec;
 use std::cell::Cell;
 use std::fmt::{Debug, Formatter};
@@ -23,12 +23,16 @@ impl Debug for Gamepads {
 
 struct State {
     gilrs: Option<RefCell<Gilrs>>,
-    gamepads: RefCell<FnvHashMap<GamepadId, Pad>>,
+    gamepads: RefCell<FnvHashMap<PadId, Pad>>,
+    hidden: RefCell<FnvHashMap<PadId, Pad>>,
     last_active: Cell<SystemTime>,
+    next_virtual_id: Cell<u32>,
+    rumble_intensity: Cell<f32>,
 }
 
 struct Pad {
     pad: Gamepad,
+    motors: Option<RumbleMotors>,
 
     #[cfg(feature = "lua")]
     userdata: mlua::AnyUserData,
@@ -36,13 +40,66 @@ struct Pad {
 
 impl Gamepads {
     pub(crate) fn new() -> Self {
+        Self::with_gilrs(Gilrs::new().ok())
+    }
+
+    /// Create gamepads that ignore real devices, so only simulated gamepads can connect.
+    pub(crate) fn new_headless() -> Self {
+        Self::with_gilrs(None)
+    }
+
+    fn with_gilrs(gilrs: Option<Gilrs>) -> Self {
         Self(Rc::new(State {
-            gilrs: Gilrs::new().ok().map(RefCell::new),
+            gilrs: gilrs.map(RefCell::new),
             gamepads: RefCell::new(FnvHashMap::default()),
+            hidden: RefCell::new(FnvHashMap::default()),
             last_active: Cell::new(SystemTime::UNIX_EPOCH),
+            next_virtual_id: Cell::new(0),
+            rumble_intensity: Cell::new(1.0),
         }))
     }
 
+    /// Connect a simulated gamepad.
+    #[allow(unused_variables)]
+    pub(crate) fn connect_virtual(&self, ctx: &Context, name: &str) -> Gamepad {
+        let time = SystemTime::now();
+        let id = PadId::Virtual(self.0.next_virtual_id.get());
+        self.0.next_virtual_id.update(|id| id + 1);
+        self.0.last_active.set(time);
+        let pad = Gamepad::new(id, name.to_string(), GamepadStatus::Unknown, time);
+        pad.handle_connect();
+        let pad = Pad {
+            #[cfg(feature = "lua")]
+            userdata: ctx.lua.upgrade().create_userdata(pad.clone()).unwrap(),
+            pad,
+            motors: None,
+        };
+        let gamepad = pad.pad.clone();
+        self.0.gamepads.borrow_mut().insert(id, pad);
+        gamepad
+    }
+
+    /// Disconnect a simulated gamepad.
+    pub(crate) fn disconnect_virtual(&self, gamepad: &Gamepad) {
+        if let Some(pad) = self.0.gamepads.borrow_mut().remove(&gamepad.id()) {
+            pad.pad.disconnect();
+        }
+    }
+
+    /// Hide the real gamepads while input is being replayed, so only the replay's simulated
+    /// gamepads are visible. They aren't updated while hidden.
+    pub(crate) fn hide_real(&self) {
+        let mut gamepads = self.0.gamepads.borrow_mut();
+        let real = gamepads.extract_if(|id, _| matches!(id, PadId::Gilrs(_)));
+        self.0.hidden.borrow_mut().extend(real);
+    }
+
+    /// Show the real gamepads again once a replay has finished.
+    pub(crate) fn show_real(&self) {
+        let hidden = self.0.hidden.borrow_mut().drain().collect::<Vec<_>>();
+        self.0.gamepads.borrow_mut().extend(hidden);
+    }
+
     #[allow(unused_variables)]
     pub(crate) fn update(&self, ctx: &Context) {
         let Some(mut gilrs) = self.0.gilrs.as_ref().map(|g| g.borrow_mut()) else {
@@ -58,28 +115,28 @@ impl Gamepads {
             match event {
                 EventType::ButtonPressed(btn, _) => {
                     if let Ok(btn) = GamepadButton::try_from(btn) {
-                        let pad = gamepads.get(&id).unwrap();
+                        let pad = gamepads.get(&PadId::Gilrs(id)).unwrap();
                         pad.pad.update_status(&gilrs, time);
                         pad.pad.handle_press(btn);
                     }
                 }
                 EventType::ButtonRepeated(btn, _) => {
                     if let Ok(btn) = GamepadButton::try_from(btn) {
-                        let pad = gamepads.get(&id).unwrap();
+                        let pad = gamepads.get(&PadId::Gilrs(id)).unwrap();
                         pad.pad.update_status(&gilrs, time);
                         pad.pad.handle_repeat(btn);
                     }
                 }
                 EventType::ButtonReleased(btn, _) => {
                     if let Ok(btn) = GamepadButton::try_from(btn) {
-                        let pad = gamepads.get(&id).unwrap();
+                        let pad = gamepads.get(&PadId::Gilrs(id)).unwrap();
                         pad.pad.update_status(&gilrs, time);
                         pad.pad.handle_release(btn);
                     }
                 }
                 EventType::ButtonChanged(btn, val, _) => {
                     if let Ok(btn) = GamepadButton::try_from(btn) {
-                        let pad = gamepads.get(&id).unwrap();
+                        let pad = gamepads.get(&PadId::Gilrs(id)).unwrap();
                         pad.pad.update_status(&gilrs, time);
                         pad.pad.handle_button_change(btn, val);
                     }
@@ -90,7 +147,7 @@ impl Gamepads {
                             GamepadAxis::LeftY | GamepadAxis::RightY | GamepadAxis::DPadY => -val,
                             _ => val,
                         };
-                        let pad = gamepads.get(&id).unwrap();
+                        let pad = gamepads.get(&PadId::Gilrs(id)).unwrap();
                         pad.pad.update_status(&gilrs, time);
                         pad.pad.handle_axis_change(axis, val);
                     }
@@ -99,16 +156,17 @@ impl Gamepads {
                     let pad = gilrs.connected_gamepad(id).unwrap();
                     let name = pad.name().to_string();
                     let status = GamepadStatus::from(pad.power_info());
-                    let pad = Gamepad::new(id, name, status, time);
+                    let pad = Gamepad::new(PadId::Gilrs(id), name, status, time);
                     let pad = Pad {
                         #[cfg(feature = "lua")]
                         userdata: ctx.lua.upgrade().create_userdata(pad.clone()).unwrap(),
                         pad,
+                        motors: RumbleMotors::new(&mut gilrs, id),
                     };
-                    assert!(gamepads.insert(id, pad).is_none());
+                    assert!(gamepads.insert(PadId::Gilrs(id), pad).is_none());
                 }
                 EventType::Disconnected => {
-                    let pad = gamepads.remove(&id).unwrap();
+                    let pad = gamepads.remove(&PadId::Gilrs(id)).unwrap();
                     pad.pad.disconnect();
                 }
                 EventType::Dropped => {}
@@ -118,6 +176,18 @@ impl Gamepads {
         }
     }
 
+    /// Advance all rumble effects, and update the motors of any gamepads whose levels changed.
+    pub(crate) fn update_rumble(&self, delta: f32) {
+        let intensity = self.0.rumble_intensity.get();
+        for pad in self.0.gamepads.borrow().values() {
+            if let Some(levels) = pad.pad.update_rumble(delta, intensity)
+                && let Some(motors) = &pad.motors
+            {
+                motors.set_levels(levels);
+            }
+        }
+    }
+
     #[inline]
     pub(crate) fn clear_phase(&self) {
         for pad in self.0.gamepads.borrow().values() {
@@ -195,6 +265,19 @@ impl Gamepads {
             .into_iter()
     }
 
+    /// Global rumble intensity from `0.0` to `1.0`, which scales all gamepad rumble effects.
+    #[inline]
+    pub fn rumble_intensity(&self) -> f32 {
+        self.0.rumble_intensity.get()
+    }
+
+    /// Set the global rumble intensity from `0.0` to `1.0`. Set this to `0.0` to disable rumble
+    /// entirely, eg. from an options menu.
+    #[inline]
+    pub fn set_rumble_intensity(&self, intensity: f32) {
+        self.0.rumble_intensity.set(intensity.clamp(0.0, 1.0));
+    }
+
     /// Last time any gamepad was updated.
     #[inline]
     pub fn last_active_time(&self) -> SystemTime {
@@ -0,0 +1,485 @@
+use crate::core::Context;
+use crate::input::{
+    Gamepad, GamepadAxis, GamepadButton, Gamepads, InputEvent, InputFrame, InputRecording, Key,
+    Keyboard, Mouse, MouseButton, PadId, TouchPhase, Touches,
+};
+use crate::math::Vec2F;
+use crate::rand::Rand;
+use dpi::{LogicalPosition, PhysicalPosition};
+use fnv::FnvHashMap;
+use std::cell::{Cell, RefCell};
+use std::fmt::{Debug, Formatter};
+use std::rc::Rc;
+use strum::{EnumCount, VariantArray};
+use winit::event::{MouseScrollDelta, TouchPhase as WinitTouchPhase};
+
+/// Handle to the input recorder, which records and replays play sessions.
+///
+/// This handle can be cloned and passed around freely to give objects access to the recorder.
+///
+/// While recording, the keyboard, IME, mouse, touch, and gamepad input seen by each update is
+/// captured along with the update's delta time. While replaying, real input is ignored and the
+/// recorded input is fed back through the same handlers before each update, so the session plays
+/// back frame-for-frame, with or without a window. When a replay ends, any input it left held
+/// down is released.
+///
+/// For a replay to match, the game must also be deterministic: it should create its random
+/// number generators from [`seed`](Self::seed) and start from the same state it was recorded
+/// from, which is easiest when recording and replaying from startup (see
+/// [`GameBuilder::with_input_recording`](crate::core::GameBuilder::with_input_recording) and
+/// [`GameBuilder::with_input_replay`](crate::core::GameBuilder::with_input_replay)).
+#[derive(Clone)]
+pub struct InputRecorder(Rc<State>);
+
+impl Debug for InputRecorder {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("InputRecorder").finish_non_exhaustive()
+    }
+}
+
+struct State {
+    keyboard: Keyboard,
+    mouse: Mouse,
+    touches: Touches,
+    gamepads: Gamepads,
+    mode: Cell<Mode>,
+    seed: Cell<u64>,
+    recording: RefCell<InputRecording>,
+    replay_frame: Cell<usize>,
+    record_mouse_pos: Cell<Vec2F>,
+    record_ime: RefCell<RecordIme>,
+    record_touches: RefCell<FnvHashMap<u64, (Vec2F, Option<f32>)>>,
+    record_pads: RefCell<FnvHashMap<PadId, RecordPad>>,
+    next_slot: Cell<u16>,
+    replay_pads: RefCell<FnvHashMap<u16, Gamepad>>,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Mode {
+    Idle,
+    Recording,
+    Replaying,
+}
+
+/// The IME state as it will be when the recorded events are replayed.
+#[derive(Default)]
+struct RecordIme {
+    enabled: bool,
+    preedit: String,
+    cursor: Option<(usize, usize)>,
+}
+
+struct RecordPad {
+    slot: u16,
+    btn_value: [f32; GamepadButton::COUNT],
+    axis_value: [f32; GamepadAxis::COUNT],
+}
+
+impl InputRecorder {
+    pub(crate) fn new(
+        keyboard: Keyboard,
+        mouse: Mouse,
+        touches: Touches,
+        gamepads: Gamepads,
+    ) -> Self {
+        let seed = Rand::new().seed();
+        Self(Rc::new(State {
+            keyboard,
+            mouse,
+            touches,
+            gamepads,
+            mode: Cell::new(Mode::Idle),
+            seed: Cell::new(seed),
+            recording: RefCell::new(InputRecording::new(seed)),
+            replay_frame: Cell::new(0),
+            record_mouse_pos: Cell::new(Vec2F::ZERO),
+            record_ime: RefCell::new(RecordIme::default()),
+            record_touches: RefCell::new(FnvHashMap::default()),
+            record_pads: RefCell::new(FnvHashMap::default()),
+            next_slot: Cell::new(0),
+            replay_pads: RefCell::new(FnvHashMap::default()),
+        }))
+    }
+
+    /// The random seed of the session being recorded or replayed. When neither is happening,
+    /// this is a seed chosen randomly at startup.
+    #[inline]
+    pub fn seed(&self) -> u64 {
+        self.0.seed.get()
+    }
+
+    /// If input is being recorded.
+    #[inline]
+    pub fn is_recording(&self) -> bool {
+        self.0.mode.get() == Mode::Recording
+    }
+
+    /// If a recording is being replayed.
+    #[inline]
+    pub fn is_replaying(&self) -> bool {
+        self.0.mode.get() == Mode::Replaying
+    }
+
+    /// How many updates have been recorded or replayed so far.
+    #[inline]
+    pub fn frame(&self) -> usize {
+        match self.0.mode.get() {
+            Mode::Replaying => self.0.replay_frame.get(),
+            _ => self.0.recording.borrow().len(),
+        }
+    }
+
+    /// Start recording input from the next update, stopping any recording or replay in
+    /// progress. The game should reset its random number generators with `seed`.
+    pub fn start_recording(&self, seed: u64) {
+        self.stop_replay();
+        self.0.mode.set(Mode::Recording);
+        self.0.seed.set(seed);
+        self.0.recording.replace(InputRecording::new(seed));
+        self.0.record_ime.replace(RecordIme::default());
+        self.0.record_touches.borrow_mut().clear();
+        self.0.record_pads.borrow_mut().clear();
+        self.0.next_slot.set(0);
+
+        // the mouse is recorded as moving when the recording starts
+        self.0.record_mouse_pos.set(Vec2F::new(f32::NAN, f32::NAN));
+    }
+
+    /// Stop recording and return the recording, or `None` if nothing was being recorded.
+    pub fn stop_recording(&self) -> Option<InputRecording> {
+        self.is_recording().then(|| {
+            self.0.mode.set(Mode::Idle);
+            self.0.recording.replace(InputRecording::new(self.seed()))
+        })
+    }
+
+    /// Start replaying a recording from the next update, stopping any recording or replay in
+    /// progress. The replay stops by itself after its last update. Real gamepads are hidden
+    /// during the replay, so only the gamepads it connects are visible.
+    pub fn start_replay(&self, recording: InputRecording) {
+        self.stop_recording();
+        self.stop_replay();
+        self.0.gamepads.hide_real();
+        self.0.mode.set(Mode::Replaying);
+        self.0.seed.set(recording.seed());
+        self.0.recording.replace(recording);
+        self.0.replay_frame.set(0);
+    }
+
+    /// Stop replaying, returning control to real input. Keys, mouse buttons, and touches the
+    /// replay left held down are released, gamepads connected by the replay are disconnected,
+    /// and the real gamepads are shown again.
+    pub fn stop_replay(&self) {
+        if self.is_replaying() {
+            self.0.mode.set(Mode::Idle);
+            let State {
+                keyboard,
+                mouse,
+                touches,
+                ..
+            } = &*self.0;
+            for key in keyboard.currently_down() {
+                keyboard.handle_key(key, false, false);
+            }
+            if keyboard.ime_enabled() {
+                keyboard.handle_ime_enabled(false);
+            }
+            for &btn in MouseButton::VARIANTS {
+                if mouse.down(btn) {
+                    mouse.handle_button(btn, false);
+                }
+            }
+            for touch in touches.all() {
+                if touch.phase != TouchPhase::Released {
+                    touches.update_touch(
+                        touch.id,
+                        WinitTouchPhase::Ended,
+                        touch.pos,
+                        touch.pressure,
+                    );
+                }
+            }
+            for (_, pad) in self.0.replay_pads.borrow_mut().drain() {
+                self.0.gamepads.disconnect_virtual(&pad);
+            }
+            self.0.gamepads.show_real();
+        }
+    }
+
+    /// Record or replay the input for the current update.
+    pub(crate) fn update(&self, ctx: &Context) {
+        match self.0.mode.get() {
+            Mode::Idle => {}
+            Mode::Recording => {
+                let frame = self.capture(ctx);
+                self.0.recording.borrow_mut().frames.push(frame);
+            }
+            Mode::Replaying => {
+                let i = self.0.replay_frame.get();
+                let frame = self.0.recording.borrow().frames.get(i).cloned();
+                match frame {
+                    Some(frame) => {
+                        self.0.replay_frame.set(i + 1);
+                        self.replay(ctx, frame);
+                    }
+                    None => self.stop_replay(),
+                }
+            }
+        }
+    }
+
+    fn capture(&self, ctx: &Context) -> InputFrame {
+        let mut events = Vec::new();
+
+        // keys that were both pressed and released are ordered so they end up in the right state
+        for &key in Key::VARIANTS {
+            let pressed = ctx.keyboard.pressed(key);
+            let released = ctx.keyboard.released(key);
+            if pressed && released && ctx.keyboard.down(key) {
+                events.push(InputEvent::KeyRelease(key));
+                events.push(InputEvent::KeyPress(key));
+            } else {
+                if pressed {
+                    events.push(InputEvent::KeyPress(key));
+                }
+                if released {
+                    events.push(InputEvent::KeyRelease(key));
+                }
+            }
+            if ctx.keyboard.repeated(key) {
+                events.push(InputEvent::KeyRepeat(key));
+            }
+        }
+        // committed text is included in the text input, so it's replayed without adding it again
+        let text = ctx.keyboard.text_input();
+        if !text.is_empty() {
+            events.push(InputEvent::Text(text.into()));
+        }
+        self.capture_ime(ctx, &ctx.keyboard.ime_commit(), &mut events);
+
+        let pos = ctx.mouse.pos();
+        if pos != self.0.record_mouse_pos.get() {
+            self.0.record_mouse_pos.set(pos);
+            events.push(InputEvent::MouseMove(pos));
+        }
+        for &btn in MouseButton::VARIANTS {
+            let pressed = ctx.mouse.pressed(btn);
+            let released = ctx.mouse.released(btn);
+            if pressed && released && ctx.mouse.down(btn) {
+                events.push(InputEvent::MouseRelease(btn));
+                events.push(InputEvent::MousePress(btn));
+            } else {
+                if pressed {
+                    events.push(InputEvent::MousePress(btn));
+                }
+                if released {
+                    events.push(InputEvent::MouseRelease(btn));
+                }
+            }
+        }
+        let lines = ctx.mouse.scroll_lines();
+        if lines != Vec2F::ZERO {
+            events.push(InputEvent::ScrollLines(lines));
+        }
+        let delta = ctx.mouse.scroll_delta();
+        if delta != Vec2F::ZERO {
+            events.push(InputEvent::ScrollDelta(delta));
+        }
+
+        self.capture_touches(ctx, &mut events);
+        self.capture_gamepads(ctx, &mut events);
+
+        InputFrame {
+            delta: ctx.time.delta(),
+            events,
+        }
+    }
+
+    fn capture_ime(&self, ctx: &Context, commit: &str, events: &mut Vec<InputEvent>) {
+        let mut ime = self.0.record_ime.borrow_mut();
+        let enabled = ctx.keyboard.ime_enabled();
+        if enabled != ime.enabled {
+            events.push(InputEvent::ImeEnabled(enabled));
+            ime.enabled = enabled;
+            if !enabled {
+                ime.preedit.clear();
+                ime.cursor = None;
+            }
+        }
+        if !commit.is_empty() {
+            events.push(InputEvent::ImeCommit(commit.into()));
+            ime.preedit.clear();
+            ime.cursor = None;
+        }
+
+        // committing and disabling clear the preedit, so it's recorded after them
+        let preedit = ctx.keyboard.preedit();
+        let cursor = ctx.keyboard.preedit_cursor();
+        if preedit != ime.preedit || cursor != ime.cursor {
+            events.push(InputEvent::Preedit(preedit.to_string(), cursor));
+            ime.preedit = preedit.into();
+            ime.cursor = cursor;
+        }
+    }
+
+    fn capture_touches(&self, ctx: &Context, events: &mut Vec<InputEvent>) {
+        let mut recorded = self.0.record_touches.borrow_mut();
+
+        // touches that were down before the recording started are ignored
+        for touch in ctx.touches.pressed() {
+            recorded.insert(touch.id, (touch.pos, touch.pressure));
+            events.push(InputEvent::TouchStart(touch.id, touch.pos, touch.pressure));
+        }
+        for touch in ctx.touches.all() {
+            if touch.phase == TouchPhase::Released {
+                continue;
+            }
+            if let Some(state) = recorded.get_mut(&touch.id)
+                && *state != (touch.pos, touch.pressure)
+            {
+                *state = (touch.pos, touch.pressure);
+                events.push(InputEvent::TouchMove(touch.id, touch.pos, touch.pressure));
+            }
+        }
+        for touch in ctx.touches.released() {
+            if recorded.remove(&touch.id).is_some() {
+                events.push(InputEvent::TouchEnd(touch.id, touch.pos, touch.pressure));
+            }
+        }
+    }
+
+    fn capture_gamepads(&self, ctx: &Context, events: &mut Vec<InputEvent>) {
+        let mut pads = self.0.record_pads.borrow_mut();
+        let connected: Vec<Gamepad> = ctx.gamepads.all().collect();
+
+        // gamepads are identified by the order they connected in
+        pads.retain(|id, pad| {
+            let still_connected = connected.iter().any(|p| p.id() == *id);
+            if !still_connected {
+                events.push(InputEvent::PadDisconnect(pad.slot));
+            }
+            still_connected
+        });
+        for gamepad in &connected {
+            let pad = pads.entry(gamepad.id()).or_insert_with(|| {
+                let slot = self.0.next_slot.get();
+                self.0.next_slot.set(slot + 1);
+                events.push(InputEvent::PadConnect(slot, gamepad.name().to_string()));
+                RecordPad {
+                    slot,
+                    btn_value: [0.0; _],
+                    axis_value: [0.0; _],
+                }
+            });
+            let slot = pad.slot;
+            for &btn in GamepadButton::VARIANTS {
+                let pressed = gamepad.pressed(btn);
+                let released = gamepad.released(btn);
+                if pressed && released && gamepad.down(btn) {
+                    events.push(InputEvent::PadRelease(slot, btn));
+                    events.push(InputEvent::PadPress(slot, btn));
+                } else {
+                    if pressed {
+                        events.push(InputEvent::PadPress(slot, btn));
+                    }
+                    if released {
+                        events.push(InputEvent::PadRelease(slot, btn));
+                    }
+                }
+                if gamepad.repeated(btn) {
+                    events.push(InputEvent::PadRepeat(slot, btn));
+                }
+                let value = gamepad.value(btn);
+                if value != pad.btn_value[btn as usize] {
+                    pad.btn_value[btn as usize] = value;
+                    events.push(InputEvent::PadButton(slot, btn, value));
+                }
+            }
+            for &axis in GamepadAxis::VARIANTS {
+                let value = gamepad.axis(axis);
+                if value != pad.axis_value[axis as usize] {
+                    pad.axis_value[axis as usize] = value;
+                    events.push(InputEvent::PadAxis(slot, axis, value));
+                }
+            }
+        }
+    }
+
+    fn replay(&self, ctx: &Context, frame: InputFrame) {
+        // replace the real delta with the recorded one
+        let time = &ctx.time.0;
+        let real_delta = time.delta.replace(frame.delta);
+        time.since_startup.update(|t| t - real_delta + frame.delta);
+
+        let mut pads = self.0.replay_pads.borrow_mut();
+        for event in frame.events {
+            match event {
+                InputEvent::KeyPress(key) => ctx.keyboard.handle_key(key, true, false),
+                InputEvent::KeyRelease(key) => ctx.keyboard.handle_key(key, false, false),
+                InputEvent::KeyRepeat(key) => ctx.keyboard.handle_key(key, true, true),
+                InputEvent::Text(text) => ctx.keyboard.handle_text(&text),
+                InputEvent::ImeEnabled(enabled) => ctx.keyboard.handle_ime_enabled(enabled),
+                InputEvent::Preedit(text, cursor) => ctx.keyboard.handle_preedit(&text, cursor),
+                InputEvent::ImeCommit(text) => ctx.keyboard.update_commit(&text),
+                InputEvent::MouseMove(pos) => {
+                    ctx.mouse.handle_move(LogicalPosition::new(pos.x, pos.y));
+                }
+                InputEvent::MousePress(btn) => ctx.mouse.handle_button(btn, true),
+                InputEvent::MouseRelease(btn) => ctx.mouse.handle_button(btn, false),
+                InputEvent::ScrollLines(lines) => {
+                    ctx.mouse
+                        .handle_scroll(MouseScrollDelta::LineDelta(lines.x, lines.y));
+                }
+                InputEvent::ScrollDelta(delta) => {
+                    let delta = PhysicalPosition::new(delta.x as f64, delta.y as f64);
+                    ctx.mouse.handle_scroll(MouseScrollDelta::PixelDelta(delta));
+                }
+                InputEvent::PadConnect(slot, name) => {
+                    pads.insert(slot, ctx.gamepads.connect_virtual(ctx, &name));
+                }
+                InputEvent::PadDisconnect(slot) => {
+                    if let Some(pad) = pads.remove(&slot) {
+                        self.0.gamepads.disconnect_virtual(&pad);
+                    }
+                }
+                InputEvent::PadPress(slot, btn) => {
+                    if let Some(pad) = pads.get(&slot) {
+                        pad.handle_press(btn);
+                    }
+                }
+                InputEvent::PadRelease(slot, btn) => {
+                    if let Some(pad) = pads.get(&slot) {
+                        pad.handle_release(btn);
+                    }
+                }
+                InputEvent::PadRepeat(slot, btn) => {
+                    if let Some(pad) = pads.get(&slot) {
+                        pad.handle_repeat(btn);
+                    }
+                }
+                InputEvent::PadButton(slot, btn, value) => {
+                    if let Some(pad) = pads.get(&slot) {
+                        pad.handle_button_change(btn, value);
+                    }
+                }
+                InputEvent::PadAxis(slot, axis, value) => {
+                    if let Some(pad) = pads.get(&slot) {
+                        pad.handle_axis_change(axis, value);
+                    }
+                }
+                InputEvent::TouchStart(id, pos, pressure) => {
+                    ctx.touches
+                        .update_touch(id, WinitTouchPhase::Started, pos, pressure);
+                }
+                InputEvent::TouchMove(id, pos, pressure) => {
+                    ctx.touches
+                        .update_touch(id, WinitTouchPhase::Moved, pos, pressure);
+                }
+                InputEvent::TouchEnd(id, pos, pressure) => {
+                    ctx.touches
+                        .update_touch(id, WinitTouchPhase::Ended, pos, pressure);
+                }
+            }
+        }
+    }
+}
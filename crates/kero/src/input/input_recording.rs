@@ -0,0 +1,539 @@
+use crate::input::{GamepadAxis, GamepadButton, Key, MouseButton};
+use crate::math::{Vec2F, vec2};
+use std::path::Path;
+use std::str::FromStr;
+
+const MAGIC: &[u8; 4] = b"KINP";
+const VERSION: u16 = 2;
+
+/// A recorded play session, which can be replayed frame-for-frame.
+///
+/// Each frame holds the delta time of one update and the input that changed before it, along
+/// with the seed the game should use for its random number generator. Recordings are created
+/// and replayed by the [`InputRecorder`](super::InputRecorder).
+#[derive(Debug, Clone, PartialEq)]
+pub struct InputRecording {
+    seed: u64,
+    pub(crate) frames: Vec<InputFrame>,
+}
+
+/// The input for a single update.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct InputFrame {
+    pub delta: f32,
+    pub events: Vec<InputEvent>,
+}
+
+/// An input change, replayed through the same handlers that real input uses. Keys, buttons, and
+/// axes are saved by name, so recordings survive their enums being reordered.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum InputEvent {
+    KeyPress(Key),
+    KeyRelease(Key),
+    KeyRepeat(Key),
+    Text(String),
+    ImeEnabled(bool),
+    Preedit(String, Option<(usize, usize)>),
+    ImeCommit(String),
+    MouseMove(Vec2F),
+    MousePress(MouseButton),
+    MouseRelease(MouseButton),
+    ScrollLines(Vec2F),
+    ScrollDelta(Vec2F),
+    PadConnect(u16, String),
+    PadDisconnect(u16),
+    PadPress(u16, GamepadButton),
+    PadRelease(u16, GamepadButton),
+    PadRepeat(u16, GamepadButton),
+    PadButton(u16, GamepadButton, f32),
+    PadAxis(u16, GamepadAxis, f32),
+    TouchStart(u64, Vec2F, Option<f32>),
+    TouchMove(u64, Vec2F, Option<f32>),
+    TouchEnd(u64, Vec2F, Option<f32>),
+}
+
+impl InputRecording {
+    /// Create an empty recording for a session using the provided random seed.
+    #[inline]
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            frames: Vec::new(),
+        }
+    }
+
+    /// The random seed of the recorded session.
+    #[inline]
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// How many updates were recorded.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// If no updates were recorded.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Total time of the recorded updates, in seconds.
+    #[inline]
+    pub fn duration(&self) -> f32 {
+        self.frames.iter().map(|frame| frame.delta).sum()
+    }
+
+    /// Load a recording from a file.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, InputRecordingError> {
+        Self::from_bytes(&std::fs::read(path)?)
+    }
+
+    /// Save the recording to a file.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), InputRecordingError> {
+        std::fs::write(path, self.to_bytes())?;
+        Ok(())
+    }
+
+    /// Encode the recording into bytes.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut w = Vec::new();
+        w.extend_from_slice(MAGIC);
+        w.extend_from_slice(&VERSION.to_le_bytes());
+        w.extend_from_slice(&self.seed.to_le_bytes());
+        write_u32(&mut w, self.frames.len() as u32);
+        for frame in &self.frames {
+            write_f32(&mut w, frame.delta);
+            write_u32(&mut w, frame.events.len() as u32);
+            for event in &frame.events {
+                write_event(&mut w, event);
+            }
+        }
+        w
+    }
+
+    /// Decode a recording from bytes.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, InputRecordingError> {
+        let mut r = Reader(bytes);
+        if r.bytes(4)? != MAGIC {
+            return Err(InputRecordingError::InvalidHeader);
+        }
+        let version = u16::from_le_bytes(r.array()?);
+        if version != VERSION {
+            return Err(InputRecordingError::UnsupportedVersion(version));
+        }
+        let seed = u64::from_le_bytes(r.array()?);
+        let frames = (0..r.u32()?)
+            .map(|_| {
+                let delta = r.f32()?;
+                let events = (0..r.u32()?).map(|_| r.event()).collect::<Result<_, _>>()?;
+                Ok(InputFrame { delta, events })
+            })
+            .collect::<Result<_, InputRecordingError>>()?;
+        Ok(Self { seed, frames })
+    }
+}
+
+#[inline]
+fn write_u32(w: &mut Vec<u8>, val: u32) {
+    w.extend_from_slice(&val.to_le_bytes());
+}
+
+#[inline]
+fn write_f32(w: &mut Vec<u8>, val: f32) {
+    w.extend_from_slice(&val.to_le_bytes());
+}
+
+#[inline]
+fn write_vec2(w: &mut Vec<u8>, val: Vec2F) {
+    write_f32(w, val.x);
+    write_f32(w, val.y);
+}
+
+#[inline]
+fn write_str(w: &mut Vec<u8>, val: &str) {
+    write_u32(w, val.len() as u32);
+    w.extend_from_slice(val.as_bytes());
+}
+
+#[inline]
+fn write_pad(w: &mut Vec<u8>, tag: u8, slot: u16, name: &str) {
+    w.push(tag);
+    w.extend_from_slice(&slot.to_le_bytes());
+    write_str(w, name);
+}
+
+#[inline]
+fn write_touch(w: &mut Vec<u8>, tag: u8, id: u64, pos: Vec2F, pressure: Option<f32>) {
+    w.push(tag);
+    w.extend_from_slice(&id.to_le_bytes());
+    write_vec2(w, pos);
+    match pressure {
+        Some(pressure) => {
+            w.push(1);
+            write_f32(w, pressure);
+        }
+        None => w.push(0),
+    }
+}
+
+fn write_event(w: &mut Vec<u8>, event: &InputEvent) {
+    use InputEvent::*;
+    match event {
+        KeyPress(key) | KeyRelease(key) | KeyRepeat(key) => {
+            w.push(match event {
+                KeyPress(_) => 0,
+                KeyRelease(_) => 1,
+                _ => 2,
+            });
+            write_str(w, key.into());
+        }
+        Text(text) => {
+            w.push(3);
+            write_str(w, text);
+        }
+        MouseMove(pos) => {
+            w.push(4);
+            write_vec2(w, *pos);
+        }
+        MousePress(btn) => {
+            w.push(5);
+            write_str(w, btn.into());
+        }
+        MouseRelease(btn) => {
+            w.push(6);
+            write_str(w, btn.into());
+        }
+        ScrollLines(lines) => {
+            w.push(7);
+            write_vec2(w, *lines);
+        }
+        ScrollDelta(delta) => {
+            w.push(8);
+            write_vec2(w, *delta);
+        }
+        PadConnect(slot, name) => {
+            w.push(9);
+            w.extend_from_slice(&slot.to_le_bytes());
+            write_str(w, name);
+        }
+        PadDisconnect(slot) => {
+            w.push(10);
+            w.extend_from_slice(&slot.to_le_bytes());
+        }
+        PadPress(slot, btn) => write_pad(w, 11, *slot, btn.into()),
+        PadRelease(slot, btn) => write_pad(w, 12, *slot, btn.into()),
+        PadRepeat(slot, btn) => write_pad(w, 13, *slot, btn.into()),
+        PadButton(slot, btn, val) => {
+            write_pad(w, 14, *slot, btn.into());
+            write_f32(w, *val);
+        }
+        PadAxis(slot, axis, val) => {
+            write_pad(w, 15, *slot, axis.into());
+            write_f32(w, *val);
+        }
+        TouchStart(id, pos, pressure) => write_touch(w, 16, *id, *pos, *pressure),
+        TouchMove(id, pos, pressure) => write_touch(w, 17, *id, *pos, *pressure),
+        TouchEnd(id, pos, pressure) => write_touch(w, 18, *id, *pos, *pressure),
+        ImeEnabled(enabled) => w.extend_from_slice(&[19, *enabled as u8]),
+        Preedit(text, cursor) => {
+            w.push(20);
+            write_str(w, text);
+            match cursor {
+                Some((start, end)) => {
+                    w.push(1);
+                    write_u32(w, *start as u32);
+                    write_u32(w, *end as u32);
+                }
+                None => w.push(0),
+            }
+        }
+        ImeCommit(text) => {
+            w.push(21);
+            write_str(w, text);
+        }
+    }
+}
+
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn bytes(&mut self, len: usize) -> Result<&'a [u8], InputRecordingError> {
+        let (bytes, rest) = self
+            .0
+            .split_at_checked(len)
+            .ok_or(InputRecordingError::UnexpectedEnd)?;
+        self.0 = rest;
+        Ok(bytes)
+    }
+
+    #[inline]
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], InputRecordingError> {
+        Ok(self.bytes(N)?.try_into().unwrap())
+    }
+
+    #[inline]
+    fn u8(&mut self) -> Result<u8, InputRecordingError> {
+        Ok(self.array::<1>()?[0])
+    }
+
+    #[inline]
+    fn u16(&mut self) -> Result<u16, InputRecordingError> {
+        Ok(u16::from_le_bytes(self.array()?))
+    }
+
+    #[inline]
+    fn u32(&mut self) -> Result<u32, InputRecordingError> {
+        Ok(u32::from_le_bytes(self.array()?))
+    }
+
+    #[inline]
+    fn u64(&mut self) -> Result<u64, InputRecordingError> {
+        Ok(u64::from_le_bytes(self.array()?))
+    }
+
+    #[inline]
+    fn f32(&mut self) -> Result<f32, InputRecordingError> {
+        Ok(f32::from_le_bytes(self.array()?))
+    }
+
+    #[inline]
+    fn bool(&mut self) -> Result<bool, InputRecordingError> {
+        match self.u8()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(InputRecordingError::InvalidData),
+        }
+    }
+
+    #[inline]
+    fn vec2(&mut self) -> Result<Vec2F, InputRecordingError> {
+        Ok(vec2(self.f32()?, self.f32()?))
+    }
+
+    fn str(&mut self) -> Result<String, InputRecordingError> {
+        let len = self.u32()? as usize;
+        let bytes = self.bytes(len)?;
+        String::from_utf8(bytes.to_vec()).map_err(|_| InputRecordingError::InvalidData)
+    }
+
+    /// Read a key, button, or axis by its name.
+    #[inline]
+    fn named<T: FromStr>(&mut self) -> Result<T, InputRecordingError> {
+        self.str()?
+            .parse()
+            .map_err(|_| InputRecordingError::InvalidData)
+    }
+
+    fn pad_button(&mut self) -> Result<(u16, GamepadButton), InputRecordingError> {
+        Ok((self.u16()?, self.named()?))
+    }
+
+    fn touch(&mut self) -> Result<(u64, Vec2F, Option<f32>), InputRecordingError> {
+        let id = self.u64()?;
+        let pos = self.vec2()?;
+        let pressure = self.bool()?.then(|| self.f32()).transpose()?;
+        Ok((id, pos, pressure))
+    }
+
+    fn event(&mut self) -> Result<InputEvent, InputRecordingError> {
+        use InputEvent::*;
+        Ok(match self.u8()? {
+            0 => KeyPress(self.named()?),
+            1 => KeyRelease(self.named()?),
+            2 => KeyRepeat(self.named()?),
+            3 => Text(self.str()?),
+            4 => MouseMove(self.vec2()?),
+            5 => MousePress(self.named()?),
+            6 => MouseRelease(self.named()?),
+            7 => ScrollLines(self.vec2()?),
+            8 => ScrollDelta(self.vec2()?),
+            9 => PadConnect(self.u16()?, self.str()?),
+            10 => PadDisconnect(self.u16()?),
+            11 => self.pad_button().map(|(slot, btn)| PadPress(slot, btn))?,
+            12 => self.pad_button().map(|(slot, btn)| PadRelease(slot, btn))?,
+            13 => self.pad_button().map(|(slot, btn)| PadRepeat(slot, btn))?,
+            14 => {
+                let (slot, btn) = self.pad_button()?;
+                PadButton(slot, btn, self.f32()?)
+            }
+            15 => PadAxis(self.u16()?, self.named()?, self.f32()?),
+            16 => self
+                .touch()
+                .map(|(id, pos, pressure)| TouchStart(id, pos, pressure))?,
+            17 => self
+                .touch()
+                .map(|(id, pos, pressure)| TouchMove(id, pos, pressure))?,
+            18 => self
+                .touch()
+                .map(|(id, pos, pressure)| TouchEnd(id, pos, pressure))?,
+            19 => ImeEnabled(self.bool()?),
+            20 => {
+                let text = self.str()?;
+                let cursor = match self.bool()? {
+                    true => Some((self.u32()? as usize, self.u32()? as usize)),
+                    false => None,
+                };
+                Preedit(text, cursor)
+            }
+            21 => ImeCommit(self.str()?),
+            _ => return Err(InputRecordingError::InvalidData),
+        })
+    }
+}
+
+/// An error loading or saving an [`InputRecording`].
+#[derive(Debug, thiserror::Error)]
+pub enum InputRecordingError {
+    #[error("{0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("not an input recording")]
+    InvalidHeader,
+
+    #[error("unsupported input recording version [{0}]")]
+    UnsupportedVersion(u16),
+
+    #[error("input recording ended unexpectedly")]
+    UnexpectedEnd,
+
+    #[error("input recording contains invalid data")]
+    InvalidData,
+}
+
+#[cfg(feature = "lua")]
+impl From<InputRecordingError> for mlua::Error {
+    #[inline]
+    fn from(value: InputRecordingError) -> Self {
+        mlua::Error::external(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{Context, Game, GameBuilder, GameError};
+    use crate::gfx::Draw;
+    use crate::rand::Rand;
+
+    #[test]
+    fn recording_bytes() {
+        let mut rec = InputRecording::new(1234);
+        rec.frames.push(InputFrame {
+            delta: 1.0 / 60.0,
+            events: vec![
+                InputEvent::KeyPress(Key::Space),
+                InputEvent::Text("hé".into()),
+                InputEvent::MouseMove(vec2(3.0, 4.0)),
+                InputEvent::PadConnect(0, "Pad".into()),
+                InputEvent::PadAxis(0, GamepadAxis::LeftX, -0.5),
+                InputEvent::TouchStart(7, vec2(1.0, 2.0), Some(0.5)),
+                InputEvent::TouchEnd(7, vec2(1.0, 2.0), None),
+                InputEvent::Preedit("かな".into(), Some((0, 3))),
+                InputEvent::ImeCommit("仮名".into()),
+            ],
+        });
+        rec.frames.push(InputFrame {
+            delta: 1.0 / 60.0,
+            events: vec![],
+        });
+
+        let bytes = rec.to_bytes();
+        assert_eq!(InputRecording::from_bytes(&bytes).unwrap(), rec);
+        assert!(matches!(
+            InputRecording::from_bytes(&bytes[..bytes.len() - 12]),
+            Err(InputRecordingError::UnexpectedEnd)
+        ));
+        assert!(matches!(
+            InputRecording::from_bytes(b"nope"),
+            Err(InputRecordingError::InvalidHeader)
+        ));
+    }
+
+    struct RecordedGame {
+        rand: Rand,
+        log: Vec<(u64, bool, u32)>,
+    }
+
+    impl Game for RecordedGame {
+        type Config = ();
+
+        fn new(ctx: &Context, _cfg: Self::Config) -> Result<Self, GameError> {
+            Ok(Self {
+                rand: Rand::from_seed(ctx.recorder.seed()),
+                log: Vec::new(),
+            })
+        }
+
+        fn update(&mut self, ctx: &Context) -> Result<(), GameError> {
+            let pad_down = ctx.gamepads.all().any(|pad| pad.down(GamepadButton::South));
+            let roll = self.rand.range(0..100);
+            self.log.push((ctx.time.frame(), pad_down, roll));
+            if ctx.keyboard.pressed(Key::Space) || ctx.mouse.left_pressed() {
+                self.log.push((ctx.time.frame(), true, 0));
+            }
+            for touch in ctx.touches.pressed() {
+                self.log.push((ctx.time.frame(), true, touch.pos.x as u32));
+            }
+            let commit = ctx.keyboard.ime_commit();
+            if !commit.is_empty() || !ctx.keyboard.text_input().is_empty() {
+                let len = commit.len() + ctx.keyboard.text_input().len();
+                self.log.push((ctx.time.frame(), false, len as u32));
+            }
+            Ok(())
+        }
+
+        fn render(&mut self, _ctx: &Context, _draw: &mut Draw) -> Result<(), GameError> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn replay() {
+        let mut runner = GameBuilder::new()
+            .unwrap()
+            .with_input_recording()
+            .build_headless::<RecordedGame>(())
+            .unwrap();
+
+        runner.step().unwrap();
+        runner.press_key(Key::Space);
+        runner.step().unwrap();
+        let pad = runner.connect_gamepad("Pad");
+        runner.press_gamepad_button(&pad, GamepadButton::South);
+        runner.move_mouse((5.0, 5.0));
+        runner.press_mouse(MouseButton::Left);
+        runner.press_touch(1, (7.0, 2.0));
+        runner.ime_preedit("か", Some((0, 3)));
+        runner.run(2).unwrap();
+        runner.release_gamepad_button(&pad, GamepadButton::South);
+        runner.release_touch(1);
+        runner.ime_commit("仮");
+        runner.input_text("a");
+        runner.step().unwrap();
+
+        let recording = runner.ctx().recorder.stop_recording().unwrap();
+        assert_eq!(recording.len(), 5);
+        let recording = InputRecording::from_bytes(&recording.to_bytes()).unwrap();
+        let recorded = runner.into_game().log;
+
+        let mut runner = GameBuilder::new()
+            .unwrap()
+            .with_input_replay(recording)
+            .build_headless::<RecordedGame>(())
+            .unwrap();
+        runner.run(5).unwrap();
+        assert_eq!(runner.game().log, recorded);
+
+        // the replay ends after its last update, releasing everything it held down
+        runner.step().unwrap();
+        let ctx = runner.ctx();
+        assert!(!ctx.recorder.is_replaying());
+        assert_eq!(ctx.gamepads.count(), 0);
+        assert!(!ctx.keyboard.down(Key::Space));
+        assert!(!ctx.mouse.left_down());
+        assert!(!ctx.keyboard.ime_enabled());
+    }
+}
@@ -0,0 +1,38 @@
+use serde::{Deserialize, Serialize};
+
+/// A window of time to look back through a virtual button's input history.
+///
+/// Can be created from an `f32` number of seconds, or a `u32` number of updates.
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub enum InputWindow {
+    /// A number of seconds.
+    Seconds(f32),
+
+    /// A number of updates, where `1` is only the current update.
+    Frames(u32),
+}
+
+impl From<f32> for InputWindow {
+    #[inline]
+    fn from(value: f32) -> Self {
+        Self::Seconds(value)
+    }
+}
+
+impl From<u32> for InputWindow {
+    #[inline]
+    fn from(value: u32) -> Self {
+        Self::Frames(value)
+    }
+}
+
+impl InputWindow {
+    /// If an input at the frame and time falls inside the window, which ends at `now`.
+    #[inline]
+    pub(crate) fn contains(&self, (frame, time): (u64, f32), (now_frame, now): (u64, f32)) -> bool {
+        match *self {
+            Self::Seconds(secs) => now - time <= secs,
+            Self::Frames(frames) => now_frame - frame < frames as u64,
+        }
+    }
+}
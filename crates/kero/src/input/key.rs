@@ -1,5 +1,5 @@
 use serde::{Deserialize, Serialize};
-use strum::{EnumCount, FromRepr, VariantArray};
+use strum::{EnumCount, EnumString, FromRepr, IntoStaticStr, VariantArray};
 use winit::keyboard::KeyCode as Winit;
 
 /// A keyboard key.
@@ -17,6 +17,8 @@ use winit::keyboard::KeyCode as Winit;
     FromRepr,
     EnumCount,
     VariantArray,
+    IntoStaticStr,
+    EnumString,
 )]
 pub enum Key {
     Backquote = 0,
@@ -5,7 +5,7 @@ use std::fmt::{Debug, Formatter};
 use std::rc::Rc;
 use std::time::SystemTime;
 use strum::{EnumCount, VariantArray};
-use winit::event::{ElementState, KeyEvent};
+use winit::event::{ElementState, Ime, KeyEvent};
 use winit::keyboard::PhysicalKey;
 
 /// Handle to the keyboard state.
@@ -21,9 +21,11 @@ impl Debug for Keyboard {
     }
 }
 
-#[derive(Clone)]
 struct State {
     down: Cell<[bool; Key::COUNT]>,
+    ime_enabled: Cell<bool>,
+    preedit: Cell<CompactString>,
+    preedit_cursor: Cell<Option<(usize, usize)>>,
     phases: [Phase; 2],
     phase: Cell<usize>,
     last_active: Cell<SystemTime>,
@@ -33,11 +35,15 @@ impl Default for State {
     fn default() -> Self {
         Self {
             down: Cell::new([false; _]),
+            ime_enabled: Cell::new(false),
+            preedit: Cell::new(CompactString::default()),
+            preedit_cursor: Cell::new(None),
             phases: std::array::repeat(Phase {
                 pressed: Cell::new([false; _]),
                 released: Cell::new([false; _]),
                 repeated: Cell::new([false; _]),
                 text_input: Cell::new(CompactString::default()),
+                ime_commit: Cell::new(CompactString::default()),
             }),
             phase: Cell::new(0),
             last_active: Cell::new(SystemTime::now()),
@@ -50,6 +56,7 @@ struct Phase {
     pub released: Cell<[bool; Key::COUNT]>,
     pub repeated: Cell<[bool; Key::COUNT]>,
     pub text_input: Cell<CompactString>,
+    pub ime_commit: Cell<CompactString>,
 }
 
 impl Clone for Phase {
@@ -59,15 +66,19 @@ impl Clone for Phase {
             pressed: self.pressed.clone(),
             released: self.released.clone(),
             repeated: self.repeated.clone(),
-            text_input: {
-                let text = self.text_input.take();
-                self.text_input.set(text.clone());
-                Cell::new(text)
-            },
+            text_input: Cell::new(take_clone(&self.text_input)),
+            ime_commit: Cell::new(take_clone(&self.ime_commit)),
         }
     }
 }
 
+#[inline]
+fn take_clone(cell: &Cell<CompactString>) -> CompactString {
+    let text = cell.take();
+    cell.set(text.clone());
+    text
+}
+
 impl Keyboard {
     pub(crate) fn new() -> Self {
         Self(Rc::new(State::default()))
@@ -120,13 +131,40 @@ impl Keyboard {
         self.pressed(key) || self.repeated(key)
     }
 
-    /// Text input that occurred this frame.
+    /// Text input that occurred this frame, including text committed by the IME.
     #[inline]
     pub fn text_input(&self) -> CompactString {
-        let phase = self.phase();
-        let text = phase.text_input.take();
-        phase.text_input.set(text.clone());
-        text
+        take_clone(&self.phase().text_input)
+    }
+
+    /// If an IME (input method editor) is currently active. IME input must first be allowed
+    /// with [`Window::set_ime_allowed`](crate::core::Window::set_ime_allowed).
+    #[inline]
+    pub fn ime_enabled(&self) -> bool {
+        self.0.ime_enabled.get()
+    }
+
+    /// Text the IME is currently composing, which has not been committed yet. This should be
+    /// displayed inline where the text is being typed, and is empty when nothing is being
+    /// composed.
+    #[inline]
+    pub fn preedit(&self) -> CompactString {
+        take_clone(&self.0.preedit)
+    }
+
+    /// Byte range of the cursor in the [`preedit`](Self::preedit) text. If the start and end are
+    /// the same, it's a caret, otherwise it's a selection. `None` means the cursor should be
+    /// hidden.
+    #[inline]
+    pub fn preedit_cursor(&self) -> Option<(usize, usize)> {
+        self.0.preedit_cursor.get()
+    }
+
+    /// Text the IME committed this frame. This is also included in
+    /// [`text_input`](Self::text_input).
+    #[inline]
+    pub fn ime_commit(&self) -> CompactString {
+        take_clone(&self.phase().ime_commit)
     }
 
     /// All keys that are currently down.
@@ -184,38 +222,76 @@ impl Keyboard {
         self.0.last_active.set(SystemTime::now());
 
         if let Some(txt) = event.text {
-            for phase in &self.0.phases {
-                let mut dst = phase.text_input.take();
-                dst.push_str(&txt);
-                phase.text_input.set(dst);
-            }
+            self.handle_text(&txt);
         }
-        match event.state {
-            ElementState::Pressed => {
-                if let PhysicalKey::Code(key) = event.physical_key {
-                    if let Ok(key) = Key::try_from(key) {
-                        let key = key as usize;
-                        Cell::as_array_of_cells(&self.0.down)[key].set(true);
-                        for phase in &self.0.phases {
-                            if event.repeat {
-                                Cell::as_array_of_cells(&phase.repeated)[key].set(true);
-                            } else {
-                                Cell::as_array_of_cells(&phase.pressed)[key].set(true);
-                            }
-                        }
-                    }
-                }
-            }
-            ElementState::Released => {
-                if let PhysicalKey::Code(key) = event.physical_key {
-                    if let Ok(key) = Key::try_from(key) {
-                        let key = key as usize;
-                        Cell::as_array_of_cells(&self.0.down)[key].set(false);
-                        for phase in &self.0.phases {
-                            Cell::as_array_of_cells(&phase.released)[key].set(true);
-                        }
-                    }
-                }
+        if let PhysicalKey::Code(key) = event.physical_key
+            && let Ok(key) = Key::try_from(key)
+        {
+            self.handle_key(key, event.state == ElementState::Pressed, event.repeat);
+        }
+    }
+
+    #[inline]
+    pub(crate) fn handle_text(&self, txt: &str) {
+        for phase in &self.0.phases {
+            let mut dst = phase.text_input.take();
+            dst.push_str(txt);
+            phase.text_input.set(dst);
+        }
+    }
+
+    pub(crate) fn handle_ime(&self, ime: Ime) {
+        self.0.last_active.set(SystemTime::now());
+
+        match ime {
+            Ime::Enabled => self.handle_ime_enabled(true),
+            Ime::Preedit(text, cursor) => self.handle_preedit(&text, cursor),
+            Ime::Commit(text) => self.handle_commit(&text),
+            Ime::Disabled => self.handle_ime_enabled(false),
+        }
+    }
+
+    pub(crate) fn handle_ime_enabled(&self, enabled: bool) {
+        self.0.ime_enabled.set(enabled);
+        if !enabled {
+            self.handle_preedit("", None);
+        }
+    }
+
+    #[inline]
+    pub(crate) fn handle_preedit(&self, text: &str, cursor: Option<(usize, usize)>) {
+        self.0.preedit.set(text.into());
+        self.0.preedit_cursor.set(cursor);
+    }
+
+    pub(crate) fn handle_commit(&self, text: &str) {
+        self.handle_text(text);
+        self.update_commit(text);
+    }
+
+    /// Commit text from the IME without adding it to the text input. Used by replays, which
+    /// record the text input separately.
+    pub(crate) fn update_commit(&self, text: &str) {
+        self.handle_preedit("", None);
+        for phase in &self.0.phases {
+            let mut dst = phase.ime_commit.take();
+            dst.push_str(text);
+            phase.ime_commit.set(dst);
+        }
+    }
+
+    pub(crate) fn handle_key(&self, key: Key, pressed: bool, repeat: bool) {
+        self.0.last_active.set(SystemTime::now());
+
+        let key = key as usize;
+        Cell::as_array_of_cells(&self.0.down)[key].set(pressed);
+        for phase in &self.0.phases {
+            if !pressed {
+                Cell::as_array_of_cells(&phase.released)[key].set(true);
+            } else if repeat {
+                Cell::as_array_of_cells(&phase.repeated)[key].set(true);
+            } else {
+                Cell::as_array_of_cells(&phase.pressed)[key].set(true);
             }
         }
     }
@@ -227,5 +303,38 @@ impl Keyboard {
         phase.released.set([false; _]);
         phase.repeated.set([false; _]);
         phase.text_input.set(CompactString::default());
+        phase.ime_commit.set(CompactString::default());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ime_composition() {
+        let keyboard = Keyboard::new();
+        keyboard.handle_ime(Ime::Enabled);
+        keyboard.handle_ime(Ime::Preedit("にほ".into(), Some((6, 6))));
+        assert!(keyboard.ime_enabled());
+        assert_eq!(keyboard.preedit(), "にほ");
+        assert_eq!(keyboard.preedit_cursor(), Some((6, 6)));
+        assert_eq!(keyboard.text_input(), "");
+
+        // committing ends the composition and counts as text input
+        keyboard.handle_ime(Ime::Commit("日本".into()));
+        assert_eq!(keyboard.preedit(), "");
+        assert_eq!(keyboard.preedit_cursor(), None);
+        assert_eq!(keyboard.ime_commit(), "日本");
+        assert_eq!(keyboard.text_input(), "日本");
+        keyboard.clear_phase();
+        assert_eq!(keyboard.ime_commit(), "");
+        keyboard.set_render_phase();
+        assert_eq!(keyboard.ime_commit(), "日本");
+
+        keyboard.handle_ime(Ime::Preedit("ご".into(), None));
+        keyboard.handle_ime(Ime::Disabled);
+        assert!(!keyboard.ime_enabled());
+        assert_eq!(keyboard.preedit(), "");
     }
 }
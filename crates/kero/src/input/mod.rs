@@ -1,29 +1,53 @@
-//! Mouse, keyboard, and gamepad input handling.
+//! Mouse, keyboard, gamepad, touch, and file drop input handling.
 
+mod binding;
+mod controller_layout;
+mod deadzone;
+mod file_drop;
 mod gamepad;
 mod gamepad_axis;
 mod gamepad_button;
 mod gamepad_status;
 mod gamepads;
+mod input_recorder;
+mod input_recording;
+mod input_window;
 mod key;
 mod keyboard;
 mod mouse;
 mod mouse_button;
+mod response_curve;
+mod rumble;
+mod stick_snap;
+mod touch;
+mod touches;
 mod virtual_axis;
 mod virtual_button;
 mod virtual_controller;
 mod virtual_source;
 mod virtual_stick;
 
+pub use binding::*;
+pub use controller_layout::*;
+pub use deadzone::*;
+pub use file_drop::*;
 pub use gamepad::*;
 pub use gamepad_axis::*;
 pub use gamepad_button::*;
 pub use gamepad_status::*;
 pub use gamepads::*;
+pub use input_recorder::*;
+pub use input_recording::*;
+pub use input_window::*;
 pub use key::*;
 pub use keyboard::*;
 pub use mouse::*;
 pub use mouse_button::*;
+pub use response_curve::*;
+pub use rumble::*;
+pub use stick_snap::*;
+pub use touch::*;
+pub use touches::*;
 pub use virtual_axis::*;
 pub use virtual_button::*;
 pub use virtual_controller::*;
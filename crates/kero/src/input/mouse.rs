@@ -184,22 +184,21 @@ impl Mouse {
     pub(crate) fn handle_input(&self, button: winit::event::MouseButton, state: ElementState) {
         self.0.last_active.set(SystemTime::now());
 
-        let Ok(button) = MouseButton::try_from(button) else {
-            return;
-        };
+        if let Ok(button) = MouseButton::try_from(button) {
+            self.handle_button(button, state == ElementState::Pressed);
+        }
+    }
+
+    pub(crate) fn handle_button(&self, button: MouseButton, pressed: bool) {
+        self.0.last_active.set(SystemTime::now());
+
         let button = button as usize;
-        match state {
-            ElementState::Pressed => {
-                Cell::as_array_of_cells(&self.0.down)[button].set(true);
-                for phase in &self.0.phases {
-                    Cell::as_array_of_cells(&phase.pressed)[button].set(true);
-                }
-            }
-            ElementState::Released => {
-                Cell::as_array_of_cells(&self.0.down)[button].set(false);
-                for phase in &self.0.phases {
-                    Cell::as_array_of_cells(&phase.released)[button].set(true);
-                }
+        Cell::as_array_of_cells(&self.0.down)[button].set(pressed);
+        for phase in &self.0.phases {
+            if pressed {
+                Cell::as_array_of_cells(&phase.pressed)[button].set(true);
+            } else {
+                Cell::as_array_of_cells(&phase.released)[button].set(true);
             }
         }
     }
@@ -1,9 +1,24 @@
-use strum::{EnumCount, FromRepr, VariantArray};
+use serde::{Deserialize, Serialize};
+use strum::{EnumCount, EnumString, FromRepr, IntoStaticStr, VariantArray};
 use winit::event::MouseButton as Winit;
 
 /// A mouse button.
 #[derive(
-    Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, FromRepr, EnumCount, VariantArray,
+    Debug,
+    Hash,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Clone,
+    Copy,
+    Serialize,
+    Deserialize,
+    FromRepr,
+    EnumCount,
+    VariantArray,
+    IntoStaticStr,
+    EnumString,
 )]
 pub enum MouseButton {
     Left = 0,
@@ -0,0 +1,52 @@
+use std::fmt::{Debug, Formatter};
+use std::rc::Rc;
+
+/// A sensitivity curve applied to virtual axes and sticks after their deadzone.
+///
+/// Curves map a magnitude from `0.0` to `1.0` to a new magnitude, allowing finer control when
+/// the stick is only pushed slightly.
+#[derive(Clone, Default)]
+pub enum ResponseCurve {
+    /// The magnitude is unchanged.
+    #[default]
+    Linear,
+
+    /// The magnitude is squared.
+    Quadratic,
+
+    /// The magnitude is passed through a custom function.
+    Custom(Rc<dyn Fn(f32) -> f32>),
+}
+
+impl Debug for ResponseCurve {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Linear => f.write_str("Linear"),
+            Self::Quadratic => f.write_str("Quadratic"),
+            Self::Custom(_) => f.debug_tuple("Custom").finish_non_exhaustive(),
+        }
+    }
+}
+
+impl ResponseCurve {
+    /// Create a curve from a custom function.
+    #[inline]
+    pub fn custom(f: impl Fn(f32) -> f32 + 'static) -> Self {
+        Self::Custom(Rc::new(f))
+    }
+
+    /// Map a magnitude from `0.0` to `1.0` through the curve.
+    pub fn apply(&self, t: f32) -> f32 {
+        match self {
+            Self::Linear => t,
+            Self::Quadratic => t * t,
+            Self::Custom(f) => f(t).clamp(0.0, 1.0),
+        }
+    }
+
+    /// Apply the curve to an axis value from `-1.0` to `1.0`, keeping its sign.
+    #[inline]
+    pub fn apply_axis(&self, value: f32) -> f32 {
+        self.apply(value.abs()).copysign(value)
+    }
+}
@@ -0,0 +1,139 @@
+use gilrs::ff::{BaseEffect, BaseEffectType, Effect, EffectBuilder};
+use gilrs::{GamepadId, Gilrs};
+
+/// Handle to a rumble effect playing on a [`Gamepad`](super::Gamepad).
+///
+/// Returned by [`Gamepad::rumble`](super::Gamepad::rumble), and can be used to stop the effect
+/// before it finishes.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Rumble(pub(crate) u64);
+
+/// The rumble effects layered on a gamepad.
+#[derive(Debug, Default)]
+pub(crate) struct RumbleEffects {
+    next_id: u64,
+    effects: Vec<RumbleEffect>,
+    levels: (f32, f32),
+}
+
+#[derive(Debug)]
+struct RumbleEffect {
+    id: u64,
+    strong: f32,
+    weak: f32,
+    duration: f32,
+    elapsed: f32,
+}
+
+impl RumbleEffects {
+    pub fn add(&mut self, strong: f32, weak: f32, duration: f32) -> Rumble {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.effects.push(RumbleEffect {
+            id,
+            strong: strong.clamp(0.0, 1.0),
+            weak: weak.clamp(0.0, 1.0),
+            duration,
+            elapsed: 0.0,
+        });
+        Rumble(id)
+    }
+
+    pub fn stop(&mut self, rumble: Rumble) {
+        self.effects.retain(|eff| eff.id != rumble.0);
+    }
+
+    pub fn stop_all(&mut self) {
+        self.effects.clear();
+    }
+
+    pub fn is_playing(&self, rumble: Rumble) -> bool {
+        self.effects.iter().any(|eff| eff.id == rumble.0)
+    }
+
+    pub fn levels(&self) -> (f32, f32) {
+        self.levels
+    }
+
+    /// Calculate the motor levels for this frame, then advance all effects by the delta time.
+    /// Overlapping effects don't add up, instead each motor runs at the level of the strongest
+    /// effect. Returns the new levels if they changed.
+    pub fn update(&mut self, delta: f32, intensity: f32) -> Option<(f32, f32)> {
+        let (strong, weak) = self.effects.iter().fold((0.0f32, 0.0f32), |(s, w), eff| {
+            (s.max(eff.strong), w.max(eff.weak))
+        });
+        let levels = (strong * intensity, weak * intensity);
+        for eff in &mut self.effects {
+            eff.elapsed += delta;
+        }
+        self.effects.retain(|eff| eff.elapsed < eff.duration);
+        (levels != self.levels).then(|| {
+            self.levels = levels;
+            levels
+        })
+    }
+}
+
+/// Drives the rumble motors of a real gamepad using gilrs force feedback. Each motor is a
+/// looping effect whose gain is set to the motor level.
+pub(crate) struct RumbleMotors {
+    strong: Effect,
+    weak: Effect,
+}
+
+impl RumbleMotors {
+    /// Create the motors, or return `None` if the gamepad doesn't support force feedback.
+    pub fn new(gilrs: &mut Gilrs, id: GamepadId) -> Option<Self> {
+        let mut create = |kind| {
+            EffectBuilder::new()
+                .add_effect(BaseEffect {
+                    kind,
+                    ..Default::default()
+                })
+                .gamepads(&[id])
+                .gain(0.0)
+                .finish(gilrs)
+                .ok()
+        };
+        Some(Self {
+            strong: create(BaseEffectType::Strong {
+                magnitude: u16::MAX,
+            })?,
+            weak: create(BaseEffectType::Weak {
+                magnitude: u16::MAX,
+            })?,
+        })
+    }
+
+    pub fn set_levels(&self, (strong, weak): (f32, f32)) {
+        for (eff, level) in [(&self.strong, strong), (&self.weak, weak)] {
+            // failing to rumble isn't worth interrupting the game for
+            let _ = match level > 0.0 {
+                true => eff.set_gain(level).and_then(|_| eff.play()),
+                false => eff.stop(),
+            };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rumble_layering() {
+        let mut effects = RumbleEffects::default();
+        let long = effects.add(0.5, 0.25, 1.0);
+        let short = effects.add(1.0, 0.0, 0.25);
+
+        // the strongest effect wins on each motor, scaled by the intensity
+        assert_eq!(effects.update(0.25, 0.5), Some((0.5, 0.125)));
+        assert!(!effects.is_playing(short));
+        assert_eq!(effects.update(0.25, 0.5), Some((0.25, 0.125)));
+        assert_eq!(effects.update(0.25, 0.5), None);
+
+        effects.stop(long);
+        assert_eq!(effects.update(0.25, 0.5), Some((0.0, 0.0)));
+        assert_eq!(effects.levels(), (0.0, 0.0));
+    }
+}
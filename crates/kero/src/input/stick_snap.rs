@@ -0,0 +1,31 @@
+use crate::math::{Cardinal, Direction, Octal, Vec2F};
+use serde::{Deserialize, Serialize};
+
+/// Snaps the direction of a virtual stick, keeping its length.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum StickSnap {
+    /// The stick can point in any direction.
+    #[default]
+    None,
+
+    /// The stick is snapped to the nearest [`Cardinal`] (4-way) direction.
+    Cardinal,
+
+    /// The stick is snapped to the nearest [`Octal`] (8-way) direction.
+    Octal,
+}
+
+impl StickSnap {
+    /// Snap the stick value.
+    pub fn apply(&self, value: Vec2F) -> Vec2F {
+        if value == Vec2F::ZERO {
+            return value;
+        }
+        let norm: Vec2F = match self {
+            Self::None => return value,
+            Self::Cardinal => Cardinal::from_vec2(value).norm(),
+            Self::Octal => Octal::from_vec2(value).norm(),
+        };
+        norm * value.len()
+    }
+}
@@ -0,0 +1,37 @@
+use crate::math::Vec2F;
+
+/// A finger touching the window.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Touch {
+    /// Unique ID of the finger, which stays the same until it is lifted.
+    pub id: u64,
+
+    /// Position of the finger in window coordinates.
+    pub pos: Vec2F,
+
+    /// Pressure of the finger from `0.0` to `1.0`, if the device supports it.
+    pub pressure: Option<f32>,
+
+    /// Phase of the touch this frame.
+    pub phase: TouchPhase,
+}
+
+impl From<Touch> for Vec2F {
+    #[inline]
+    fn from(value: Touch) -> Self {
+        value.pos
+    }
+}
+
+/// Phase of a [`Touch`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum TouchPhase {
+    /// The finger touched the window this frame.
+    Pressed,
+
+    /// The finger is still touching the window.
+    Held,
+
+    /// The finger was lifted (or the touch was cancelled) this frame.
+    Released,
+}
@@ -0,0 +1,350 @@
+use crate::input::{Mouse, MouseButton, Touch, TouchPhase};
+use crate::math::{Radians, RadiansF, Vec2F};
+use dpi::LogicalPosition;
+use std::cell::{Cell, RefCell};
+use std::fmt::{Debug, Formatter};
+use std::rc::Rc;
+use std::time::SystemTime;
+use winit::event::TouchPhase as Winit;
+
+/// Handle to the state of fingers touching the window, and touch gestures.
+///
+/// This handle can be cloned and passed around freely to give objects access to touches.
+///
+/// Obtained from [`Context`](crate::core::Context). Positions are in window coordinates, use
+/// [`Screen::map_pos`](crate::gfx::Screen::map_pos) or
+/// [`Screen::touches`](crate::gfx::Screen::touches) for on-screen positions.
+///
+/// If mouse emulation is enabled, the first finger to touch the window also moves the mouse and
+/// holds down the left mouse button, so games written for the mouse work on touch screens.
+#[derive(Clone)]
+pub struct Touches(Rc<State>);
+
+impl Debug for Touches {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Touches").finish_non_exhaustive()
+    }
+}
+
+#[derive(Debug)]
+struct State {
+    mouse: Mouse,
+    down: RefCell<Vec<Touch>>,
+    emulate_mouse: Cell<bool>,
+    mouse_finger: Cell<Option<u64>>,
+    phases: [Phase; 2],
+    phase: Cell<usize>,
+    last_active: Cell<SystemTime>,
+}
+
+#[derive(Debug, Default)]
+struct Phase {
+    pressed: RefCell<Vec<u64>>,
+    released: RefCell<Vec<Touch>>,
+    pinch: Cell<f32>,
+    pan: Cell<Vec2F>,
+    rotation: Cell<f32>,
+    double_tapped: Cell<bool>,
+}
+
+impl Touches {
+    pub(crate) fn new(mouse: &Mouse) -> Self {
+        Self(Rc::new(State {
+            mouse: mouse.clone(),
+            down: RefCell::new(Vec::new()),
+            emulate_mouse: Cell::new(false),
+            mouse_finger: Cell::new(None),
+            phases: std::array::from_fn(|_| Phase::default()),
+            phase: Cell::new(0),
+            last_active: Cell::new(SystemTime::UNIX_EPOCH),
+        }))
+    }
+
+    #[inline]
+    fn phase(&self) -> &Phase {
+        &self.0.phases[self.0.phase.get()]
+    }
+
+    /// Time the touch state last changed.
+    #[inline]
+    pub fn last_active(&self) -> SystemTime {
+        self.0.last_active.get()
+    }
+
+    /// All touches this frame, including fingers that were lifted this frame.
+    pub fn all(&self) -> Vec<Touch> {
+        let phase = self.phase();
+        let pressed = phase.pressed.borrow();
+        self.0
+            .down
+            .borrow()
+            .iter()
+            .map(|&touch| Touch {
+                phase: match pressed.contains(&touch.id) {
+                    true => TouchPhase::Pressed,
+                    false => TouchPhase::Held,
+                },
+                ..touch
+            })
+            .chain(phase.released.borrow().iter().copied())
+            .collect()
+    }
+
+    /// Find the touch with the provided finger ID this frame.
+    pub fn get(&self, id: u64) -> Option<Touch> {
+        self.all().into_iter().find(|touch| touch.id == id)
+    }
+
+    /// How many fingers are touching the window.
+    #[inline]
+    pub fn count(&self) -> usize {
+        self.0.down.borrow().len()
+    }
+
+    /// If any fingers are touching the window.
+    #[inline]
+    pub fn any_down(&self) -> bool {
+        !self.0.down.borrow().is_empty()
+    }
+
+    /// If the finger is touching the window.
+    #[inline]
+    pub fn down(&self, id: u64) -> bool {
+        self.0.down.borrow().iter().any(|touch| touch.id == id)
+    }
+
+    /// Touches that started this frame, including taps whose finger was already lifted.
+    pub fn pressed(&self) -> Vec<Touch> {
+        let phase = self.phase();
+        let down = self.0.down.borrow();
+        let released = phase.released.borrow();
+        phase
+            .pressed
+            .borrow()
+            .iter()
+            .filter_map(|&id| {
+                let touch = down.iter().chain(released.iter()).find(|t| t.id == id)?;
+                Some(Touch {
+                    phase: TouchPhase::Pressed,
+                    ..*touch
+                })
+            })
+            .collect()
+    }
+
+    /// Touches that ended this frame.
+    #[inline]
+    pub fn released(&self) -> Vec<Touch> {
+        self.phase().released.borrow().clone()
+    }
+
+    /// How much the user pinched this frame. Positive values zoom in, negative values zoom out.
+    #[inline]
+    pub fn pinch_delta(&self) -> f32 {
+        self.phase().pinch.get()
+    }
+
+    /// How far the user panned with two fingers this frame, in window coordinates.
+    #[inline]
+    pub fn pan_delta(&self) -> Vec2F {
+        self.phase().pan.get()
+    }
+
+    /// How much the user rotated with two fingers this frame. Positive values are
+    /// counter-clockwise.
+    #[inline]
+    pub fn rotation_delta(&self) -> RadiansF {
+        Radians(self.phase().rotation.get())
+    }
+
+    /// If the user double-tapped this frame.
+    #[inline]
+    pub fn double_tapped(&self) -> bool {
+        self.phase().double_tapped.get()
+    }
+
+    /// If the first finger touching the window is emulating the mouse.
+    #[inline]
+    pub fn emulate_mouse(&self) -> bool {
+        self.0.emulate_mouse.get()
+    }
+
+    /// Set whether the first finger touching the window should emulate the mouse. Disabling it
+    /// releases the left mouse button if a finger is holding it down.
+    #[inline]
+    pub fn set_emulate_mouse(&self, emulate: bool) {
+        self.0.emulate_mouse.set(emulate);
+        if !emulate && self.0.mouse_finger.take().is_some() {
+            self.0.mouse.handle_button(MouseButton::Left, false);
+        }
+    }
+
+    #[inline]
+    pub(crate) fn set_update_phase(&self) {
+        self.0.phase.set(0);
+    }
+
+    #[inline]
+    pub(crate) fn set_render_phase(&self) {
+        self.0.phase.set(1);
+    }
+
+    pub(crate) fn handle_touch(&self, id: u64, phase: Winit, pos: Vec2F, pressure: Option<f32>) {
+        self.update_touch(id, phase, pos, pressure);
+        if self.0.emulate_mouse.get() {
+            self.emulate(id, phase, pos);
+        }
+    }
+
+    /// Update a touch without emulating the mouse. Used by replays, which record the mouse
+    /// separately.
+    pub(crate) fn update_touch(&self, id: u64, phase: Winit, pos: Vec2F, pressure: Option<f32>) {
+        self.0.last_active.set(SystemTime::now());
+
+        let touch = Touch {
+            id,
+            pos,
+            pressure,
+            phase: TouchPhase::Held,
+        };
+        let mut down = self.0.down.borrow_mut();
+        match phase {
+            Winit::Started => {
+                down.retain(|t| t.id != id);
+                down.push(touch);
+                for phase in &self.0.phases {
+                    phase.pressed.borrow_mut().push(id);
+                }
+            }
+            Winit::Moved => {
+                if let Some(t) = down.iter_mut().find(|t| t.id == id) {
+                    *t = touch;
+                }
+            }
+            Winit::Ended | Winit::Cancelled => {
+                down.retain(|t| t.id != id);
+                for phase in &self.0.phases {
+                    phase.released.borrow_mut().push(Touch {
+                        phase: TouchPhase::Released,
+                        ..touch
+                    });
+                }
+            }
+        }
+    }
+
+    fn emulate(&self, id: u64, phase: Winit, pos: Vec2F) {
+        let mouse = &self.0.mouse;
+        let finger = self.0.mouse_finger.get();
+        if phase == Winit::Started && finger.is_none() {
+            self.0.mouse_finger.set(Some(id));
+        } else if finger != Some(id) {
+            return;
+        }
+
+        mouse.handle_move(LogicalPosition::new(pos.x, pos.y));
+        match phase {
+            Winit::Started => mouse.handle_button(MouseButton::Left, true),
+            Winit::Moved => {}
+            Winit::Ended | Winit::Cancelled => {
+                self.0.mouse_finger.set(None);
+                mouse.handle_button(MouseButton::Left, false);
+            }
+        }
+    }
+
+    pub(crate) fn handle_pinch(&self, delta: f32) {
+        self.0.last_active.set(SystemTime::now());
+        for phase in &self.0.phases {
+            phase.pinch.update(|p| p + delta);
+        }
+    }
+
+    pub(crate) fn handle_pan(&self, delta: Vec2F) {
+        self.0.last_active.set(SystemTime::now());
+        for phase in &self.0.phases {
+            phase.pan.update(|p| p + delta);
+        }
+    }
+
+    pub(crate) fn handle_rotation(&self, delta: RadiansF) {
+        self.0.last_active.set(SystemTime::now());
+        for phase in &self.0.phases {
+            phase.rotation.update(|r| r + delta.0);
+        }
+    }
+
+    pub(crate) fn handle_double_tap(&self) {
+        self.0.last_active.set(SystemTime::now());
+        for phase in &self.0.phases {
+            phase.double_tapped.set(true);
+        }
+    }
+
+    #[inline]
+    pub(crate) fn clear_phase(&self) {
+        let phase = self.phase();
+        phase.pressed.borrow_mut().clear();
+        phase.released.borrow_mut().clear();
+        phase.pinch.set(0.0);
+        phase.pan.set(Vec2F::ZERO);
+        phase.rotation.set(0.0);
+        phase.double_tapped.set(false);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn touch_phases() {
+        let mouse = Mouse::new();
+        let touches = Touches::new(&mouse);
+        touches.set_emulate_mouse(true);
+
+        touches.handle_touch(1, Winit::Started, Vec2F::new(1.0, 2.0), None);
+        touches.handle_touch(2, Winit::Started, Vec2F::new(5.0, 5.0), Some(0.5));
+        assert_eq!(touches.count(), 2);
+        assert_eq!(touches.pressed().len(), 2);
+        assert_eq!(touches.get(2).unwrap().pressure, Some(0.5));
+
+        // only the first finger moves the mouse
+        assert!(mouse.left_pressed());
+        touches.handle_touch(2, Winit::Moved, Vec2F::new(9.0, 9.0), None);
+        touches.handle_touch(1, Winit::Moved, Vec2F::new(3.0, 4.0), None);
+        assert_eq!(mouse.pos(), Vec2F::new(3.0, 4.0));
+
+        touches.clear_phase();
+        assert_eq!(touches.get(1).unwrap().phase, TouchPhase::Held);
+        touches.handle_touch(1, Winit::Ended, Vec2F::new(3.0, 4.0), None);
+        touches.handle_pinch(0.25);
+        assert!(!mouse.left_down());
+        assert!(!touches.down(1));
+        assert_eq!(touches.get(1).unwrap().phase, TouchPhase::Released);
+        assert_eq!(touches.pinch_delta(), 0.25);
+
+        // the render phase still sees everything since the last render
+        touches.set_render_phase();
+        assert_eq!(touches.pressed().len(), 2);
+        assert_eq!(touches.released().len(), 1);
+        assert_eq!(touches.pinch_delta(), 0.25);
+
+        // taps that start and end in the same frame are still pressed
+        touches.clear_phase();
+        touches.set_update_phase();
+        touches.clear_phase();
+        touches.handle_touch(3, Winit::Started, Vec2F::new(7.0, 7.0), None);
+        touches.handle_touch(3, Winit::Ended, Vec2F::new(7.0, 7.0), None);
+        let pressed = touches.pressed();
+        assert_eq!(pressed.len(), 1);
+        assert_eq!(pressed[0].id, 3);
+        assert_eq!(touches.released().len(), 1);
+
+        // turning emulation off releases the mouse held by a finger
+        touches.handle_touch(4, Winit::Started, Vec2F::new(1.0, 1.0), None);
+        assert!(mouse.left_down());
+        touches.set_emulate_mouse(false);
+        assert!(!mouse.left_down());
+    }
+}
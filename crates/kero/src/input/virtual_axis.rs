@@ -1,13 +1,18 @@
 use super::VirtualButton;
-use crate::input::GamepadAxis;
 use crate::input::virtual_source::VirtualSource;
+use crate::input::{Deadzone, GamepadAxis, ResponseCurve};
 use std::cell::{Cell, RefCell};
 use std::cmp::Ordering;
 use std::rc::Rc;
 
+#[cfg(feature = "lua")]
+pub type VirtualAxisObj = fey_lua::UserDataOf<VirtualAxis>;
+#[cfg(feature = "lua")]
+pub type VirtualAxisRef = mlua::UserDataRef<VirtualAxis>;
+
 /// Handle to a virtual axis.
 ///
-/// This can be used to simultaneously listen to the state of a gamepad axis, but also to
+/// This can be used to simultaneously listen to the state of gamepad axes, but also to
 /// treat a pair of buttons as negative and positive inputs for that axis. This handle can be
 /// cloned and passed around to give objects access to it.
 #[derive(Clone)]
@@ -29,9 +34,11 @@ impl PartialOrd for VirtualAxis {
 
 struct Inner {
     source: VirtualSource,
-    axis: Cell<Option<GamepadAxis>>,
+    axes: RefCell<Vec<GamepadAxis>>,
     neg: RefCell<VirtualButton>,
     pos: RefCell<VirtualButton>,
+    deadzone: Cell<Deadzone>,
+    curve: RefCell<ResponseCurve>,
 }
 
 impl VirtualAxis {
@@ -55,16 +62,24 @@ impl VirtualAxis {
             .unwrap_or_else(|| VirtualButton::new(source, None, None));
         Self(Rc::new(Inner {
             source: source.clone(),
-            axis: Cell::new(axis.into()),
+            axes: RefCell::new(axis.into().into_iter().collect()),
             neg: RefCell::new(neg),
             pos: RefCell::new(pos),
+            deadzone: Cell::new(Deadzone::NONE),
+            curve: RefCell::new(ResponseCurve::Linear),
         }))
     }
 
-    /// The axis this input listens to.
+    /// The first axis this input listens to.
     #[inline]
     pub fn axis(&self) -> Option<GamepadAxis> {
-        self.0.axis.get()
+        self.0.axes.borrow().first().copied()
+    }
+
+    /// All the axes this input listens to.
+    #[inline]
+    pub fn axes(&self) -> Vec<GamepadAxis> {
+        self.0.axes.borrow().clone()
     }
 
     /// The input's button on the negative axis.
@@ -79,9 +94,47 @@ impl VirtualAxis {
         self.0.pos.borrow().clone()
     }
 
-    /// Set the input's gamepad axis.
+    /// Set the input's gamepad axis, replacing any other axes.
     pub fn set_axis(&self, axis: impl Into<Option<GamepadAxis>>) {
-        self.0.axis.set(axis.into());
+        self.set_axes(axis.into());
+    }
+
+    /// Replace all the axes this input listens to.
+    pub fn set_axes(&self, axes: impl IntoIterator<Item = GamepadAxis>) {
+        self.0.axes.replace(axes.into_iter().collect());
+    }
+
+    /// Listen to another axis, if the input isn't listening to it already.
+    pub fn add_axis(&self, axis: GamepadAxis) {
+        let mut axes = self.0.axes.borrow_mut();
+        if !axes.contains(&axis) {
+            axes.push(axis);
+        }
+    }
+
+    /// The axis deadzone.
+    #[inline]
+    pub fn deadzone(&self) -> Deadzone {
+        self.0.deadzone.get()
+    }
+
+    /// Set the axis deadzone. Only its inner and outer values are used, since the axis has no
+    /// second dimension for the shape to apply to.
+    #[inline]
+    pub fn set_deadzone(&self, deadzone: Deadzone) {
+        self.0.deadzone.set(deadzone);
+    }
+
+    /// The axis response curve.
+    #[inline]
+    pub fn curve(&self) -> ResponseCurve {
+        self.0.curve.borrow().clone()
+    }
+
+    /// Set the axis response curve, which is applied after the deadzone.
+    #[inline]
+    pub fn set_curve(&self, curve: ResponseCurve) {
+        self.0.curve.replace(curve);
     }
 
     /// Set the input's negative and positive buttons.
@@ -93,24 +146,29 @@ impl VirtualAxis {
     /// If the axis state changed this frame.
     #[inline]
     pub fn changed(&self) -> bool {
-        self.0
-            .axis
-            .get()
-            .and_then(|axis| self.0.source.read(|pad| pad.axis_changed(axis)))
-            .unwrap_or(false)
-            || self.0.neg.borrow().changed()
+        self.0.axes.borrow().iter().any(|&axis| {
+            self.0
+                .source
+                .read(|pad| pad.axis_changed(axis))
+                .unwrap_or(false)
+        }) || self.0.neg.borrow().changed()
             || self.0.pos.borrow().changed()
     }
 
-    /// The axis value from `-1.0` to `1.0`.
+    /// The axis value from `-1.0` to `1.0`, after applying the deadzone and response curve. If
+    /// listening to multiple axes, the one pushed the furthest is used.
     #[inline]
     pub fn value(&self) -> f32 {
-        let mut value = 0.0;
-        if let Some(axis) = self.0.axis.get() {
-            value += self.0.source.read(|pad| pad.axis(axis)).unwrap_or(0.0);
-        }
+        let mut value = self
+            .0
+            .axes
+            .borrow()
+            .iter()
+            .filter_map(|&axis| self.0.source.read(|pad| pad.axis(axis)))
+            .fold(0.0, |a: f32, b| if b.abs() > a.abs() { b } else { a });
         value -= self.0.neg.borrow().value();
         value += self.0.pos.borrow().value();
-        value.clamp(-1.0, 1.0)
+        let value = self.0.deadzone.get().apply_axis(value.clamp(-1.0, 1.0));
+        self.0.curve.borrow().apply_axis(value)
     }
 }
@@ -1,13 +1,18 @@
 use crate::input::virtual_source::VirtualSource;
-use crate::input::{GamepadButton, Key, Keyboard};
-use std::cell::Cell;
+use crate::input::{Binding, GamepadButton, InputWindow, Key};
+use std::cell::RefCell;
 use std::cmp::Ordering;
-use std::rc::Rc;
+use std::collections::VecDeque;
+use std::rc::{Rc, Weak};
+
+/// How many presses and releases each virtual button remembers.
+const HISTORY_LEN: usize = 16;
 
 /// Handle to a virtual button.
 ///
-/// This can be used to simultaneously listen to the state of a key and gamepad button, and
-/// can be cloned and passed around to give objects access to it.
+/// This can be used to simultaneously listen to the state of several keys, mouse buttons,
+/// gamepad buttons, and gamepad axes, and can be cloned and passed around to give objects access
+/// to it.
 #[derive(Clone)]
 pub struct VirtualButton(Rc<Inner>);
 
@@ -27,14 +32,67 @@ impl PartialOrd for VirtualButton {
 
 struct Inner {
     source: VirtualSource,
-    btn: Cell<Option<GamepadButton>>,
-    key: Cell<Option<Key>>,
+    bindings: RefCell<Vec<Binding>>,
+    history: RefCell<History>,
+}
+
+/// The recent presses and releases of a virtual button, stamped with the update they happened.
+#[derive(Default)]
+struct History {
+    presses: VecDeque<Stamp>,
+    releases: VecDeque<Stamp>,
+    down_since: Option<Stamp>,
+    consumed: Option<u64>,
+}
+
+/// The frame and time of an update.
+type Stamp = (u64, f32);
+
+impl History {
+    fn push(events: &mut VecDeque<Stamp>, stamp: Stamp) {
+        if events.len() == HISTORY_LEN {
+            events.pop_front();
+        }
+        events.push_back(stamp);
+    }
+
+    /// Events within the window that haven't been consumed.
+    fn within(&self, events: &VecDeque<Stamp>, window: InputWindow, now: Stamp) -> usize {
+        events
+            .iter()
+            .rev()
+            .take_while(|&&stamp| window.contains(stamp, now))
+            .filter(|&&(frame, _)| self.consumed.is_none_or(|consumed| frame > consumed))
+            .count()
+    }
+}
+
+/// All virtual buttons that have been created, so their history can be recorded every update,
+/// even when the game isn't checking them.
+#[derive(Clone, Default)]
+pub(crate) struct VirtualButtons(Rc<RefCell<Vec<Weak<Inner>>>>);
+
+impl VirtualButtons {
+    fn register(&self, btn: &Rc<Inner>) {
+        self.0.borrow_mut().push(Rc::downgrade(btn));
+    }
+
+    /// Record the presses and releases of this update, and forget any dropped buttons.
+    pub fn update(&self) {
+        self.0.borrow_mut().retain(|btn| match btn.upgrade() {
+            Some(btn) => {
+                VirtualButton(btn).record();
+                true
+            }
+            None => false,
+        });
+    }
 }
 
 impl VirtualButton {
     /// Create a button with no mappings.
     pub fn empty(source: &VirtualSource) -> Self {
-        Self::new(source, None, None)
+        Self::with_bindings(source, [])
     }
 
     /// Create a button that listens to the provided key and button.
@@ -43,131 +101,323 @@ impl VirtualButton {
         key: impl Into<Option<Key>>,
         btn: impl Into<Option<GamepadButton>>,
     ) -> Self {
-        Self(Rc::new(Inner {
+        let key = key.into().map(Binding::Key);
+        let btn = btn.into().map(Binding::Button);
+        Self::with_bindings(source, key.into_iter().chain(btn))
+    }
+
+    /// Create a button that listens to all the provided inputs.
+    pub fn with_bindings(
+        source: &VirtualSource,
+        bindings: impl IntoIterator<Item = Binding>,
+    ) -> Self {
+        let inner = Rc::new(Inner {
             source: source.clone(),
-            btn: Cell::new(btn.into()),
-            key: Cell::new(key.into()),
-        }))
+            bindings: RefCell::new(bindings.into_iter().collect()),
+            history: RefCell::new(History::default()),
+        });
+        source.buttons().register(&inner);
+        Self(inner)
     }
 
+    /// The inputs this button listens to.
     #[inline]
-    fn keyboard(&self) -> &Keyboard {
-        self.0.source.keyboard()
+    pub fn bindings(&self) -> Vec<Binding> {
+        self.0.bindings.borrow().clone()
     }
 
-    /// Set the gamepad button to listen to.
+    /// Replace all the inputs this button listens to.
+    pub fn set_bindings(&self, bindings: impl IntoIterator<Item = Binding>) {
+        self.0.bindings.replace(bindings.into_iter().collect());
+    }
+
+    /// Listen to another input, if the button isn't listening to it already.
+    pub fn add_binding(&self, binding: impl Into<Binding>) {
+        let binding = binding.into();
+        let mut bindings = self.0.bindings.borrow_mut();
+        if !bindings.contains(&binding) {
+            bindings.push(binding);
+        }
+    }
+
+    /// Stop listening to an input.
+    pub fn remove_binding(&self, binding: impl Into<Binding>) {
+        let binding = binding.into();
+        self.0.bindings.borrow_mut().retain(|b| *b != binding);
+    }
+
+    /// Stop listening to all inputs.
     #[inline]
+    pub fn clear_bindings(&self) {
+        self.0.bindings.borrow_mut().clear();
+    }
+
+    /// Set the gamepad button to listen to, replacing any other gamepad button bindings.
     pub fn set_button(&self, btn: impl Into<Option<GamepadButton>>) {
-        self.0.btn.set(btn.into());
+        let mut bindings = self.0.bindings.borrow_mut();
+        bindings.retain(|b| !matches!(b, Binding::Button(_)));
+        bindings.extend(btn.into().map(Binding::Button));
     }
 
-    /// Set the key to listen to.
-    #[inline]
+    /// Set the key to listen to, replacing any other key bindings.
     pub fn set_key(&self, key: impl Into<Option<Key>>) {
-        self.0.key.set(key.into());
+        let mut bindings = self.0.bindings.borrow_mut();
+        bindings.retain(|b| !matches!(b, Binding::Key(_)));
+        bindings.extend(key.into().map(Binding::Key));
+    }
+
+    /// Listen for the next input (see [`VirtualSource::listen`]), and if there was one this
+    /// frame, bind the button to it. If it's a gamepad input, it replaces the button's gamepad
+    /// bindings, otherwise it replaces its keyboard and mouse bindings. Returns the new binding.
+    ///
+    /// Call this every frame while waiting for the player to choose an input.
+    pub fn rebind(&self) -> Option<Binding> {
+        let binding = self.0.source.listen()?;
+        let mut bindings = self.0.bindings.borrow_mut();
+        bindings.retain(|b| b.is_gamepad() != binding.is_gamepad());
+        bindings.push(binding);
+        Some(binding)
     }
 
-    /// If the button's key or gamepad button is down.
+    #[inline]
+    fn any(&self, f: impl Fn(&Binding) -> bool) -> bool {
+        self.0.bindings.borrow().iter().any(f)
+    }
+
+    /// If any of the button's inputs are down.
     #[inline]
     pub fn down(&self) -> bool {
-        self.0
-            .btn
-            .get()
-            .is_some_and(|btn| self.0.source.read(|pad| pad.down(btn)).unwrap_or(false))
-            || self
-                .0
-                .key
-                .get()
-                .is_some_and(|key| self.keyboard().down(key))
+        self.any(|b| b.down(&self.0.source))
     }
 
-    /// If the button's key or gamepad button was pressed this frame.
+    /// If any of the button's inputs were pressed this frame.
     ///
     /// This treats the mappings as if they were one button, meaning if the gamepad button is held
     /// down and you press the key, it will not count as a press because the virtual button is
     /// already considered down.
     #[inline]
     pub fn pressed(&self) -> bool {
-        let mut pressed = false;
-        if let Some(btn) = self.0.btn.get() {
-            if let Some((press, down)) = self.0.source.read(|pad| (pad.pressed(btn), pad.down(btn)))
-            {
-                if press {
-                    pressed = true;
-                } else if down {
-                    return false;
-                }
-            }
-        }
-        if let Some(key) = self.0.key.get() {
-            if self.keyboard().pressed(key) {
-                pressed = true;
-            } else if self.keyboard().down(key) {
-                return false;
-            }
-        }
-        pressed
+        let source = &self.0.source;
+        self.any(|b| b.pressed(source)) && !self.any(|b| b.down(source) && !b.pressed(source))
     }
 
-    /// If the button's key or gamepad button was pressed this frame.
+    /// If any of the button's inputs were released this frame.
     ///
     /// This treats the mappings as if they were one button, meaning if the gamepad button is
     /// released but the key is still held down, this will not return true until the key is also
     /// released.
     #[inline]
     pub fn released(&self) -> bool {
-        let mut released = false;
-        if let Some(btn) = self.0.btn.get() {
-            if let Some((release, down)) =
-                self.0.source.read(|pad| (pad.released(btn), pad.down(btn)))
-            {
-                if release {
-                    released = true;
-                } else if !down {
-                    return false;
-                }
-            }
-        }
-        if let Some(key) = self.0.key.get() {
-            if self.keyboard().released(key) {
-                released = true;
-            } else if !self.keyboard().down(key) {
-                return false;
-            }
-        }
-        released
+        let source = &self.0.source;
+        self.any(|b| b.released(source)) && !self.down()
     }
 
-    /// If the button state changed this frame.
+    /// If any of the button's inputs were pressed or released this frame.
     #[inline]
     pub fn changed(&self) -> bool {
-        self.0.btn.get().is_some_and(|btn| {
-            self.0
-                .source
-                .read(|pad| pad.btn_changed(btn))
-                .unwrap_or(false)
-        }) || self
-            .0
-            .key
-            .get()
-            .is_some_and(|key| self.keyboard().pressed(key) || self.keyboard().released(key))
-    }
-
-    /// Value of the button.
+        let source = &self.0.source;
+        self.any(|b| b.pressed(source) || b.released(source))
+    }
+
+    /// Value of the button, which is the highest value of its inputs.
     #[inline]
     pub fn value(&self) -> f32 {
-        if self
-            .0
-            .key
-            .get()
-            .is_some_and(|key| self.keyboard().down(key))
-        {
-            return 1.0;
+        self.0
+            .bindings
+            .borrow()
+            .iter()
+            .map(|b| b.value(&self.0.source))
+            .fold(0.0, f32::max)
+    }
+
+    #[inline]
+    fn now(&self) -> Stamp {
+        let time = self.0.source.time();
+        (time.frame(), time.since_startup())
+    }
+
+    fn record(&self) {
+        let now = self.now();
+        let (pressed, released, down) = (self.pressed(), self.released(), self.down());
+        let mut history = self.0.history.borrow_mut();
+        if pressed {
+            History::push(&mut history.presses, now);
+        }
+        if released {
+            History::push(&mut history.releases, now);
         }
+        history.down_since = match down {
+            true if pressed => Some(now),
+            true => history.down_since.or(Some(now)),
+            false => None,
+        };
+    }
+
+    /// If the button was pressed within the window, which can be a number of seconds (`f32`) or
+    /// updates (`u32`). Useful for buffering input, eg. allowing a jump pressed just before
+    /// landing to still count.
+    ///
+    /// Presses are recorded at the start of every update, so this includes the current update.
+    #[inline]
+    pub fn pressed_within(&self, window: impl Into<InputWindow>) -> bool {
+        self.press_count(window) > 0
+    }
+
+    /// If the button was released within the window, which can be a number of seconds (`f32`) or
+    /// updates (`u32`).
+    pub fn released_within(&self, window: impl Into<InputWindow>) -> bool {
+        let history = self.0.history.borrow();
+        history.within(&history.releases, window.into(), self.now()) > 0
+    }
+
+    /// How many times the button was pressed within the window, which can be a number of
+    /// seconds (`f32`) or updates (`u32`). Useful for detecting double-taps.
+    pub fn press_count(&self, window: impl Into<InputWindow>) -> usize {
+        let history = self.0.history.borrow();
+        history.within(&history.presses, window.into(), self.now())
+    }
+
+    /// How long the button has been held down in seconds, or `0.0` if it isn't down.
+    pub fn held_for(&self) -> f32 {
+        let now = self.now().1;
         self.0
-            .btn
-            .get()
-            .and_then(|btn| self.0.source.read(|pad| pad.value(btn)))
-            .unwrap_or(0.0)
+            .history
+            .borrow()
+            .down_since
+            .map_or(0.0, |(_, time)| now - time)
+    }
+
+    /// How many updates the button has been held down, or `0` if it isn't down. A button pressed
+    /// this update has been held for `1` update.
+    pub fn held_frames(&self) -> u64 {
+        let now = self.now().0;
+        self.0
+            .history
+            .borrow()
+            .down_since
+            .map_or(0, |(frame, _)| now - frame + 1)
+    }
+
+    /// Consume the buffered input, so [`pressed_within`](Self::pressed_within),
+    /// [`released_within`](Self::released_within), and [`press_count`](Self::press_count) ignore
+    /// everything up to and including the current update. Call this after acting on a buffered
+    /// press so it doesn't trigger again.
+    #[inline]
+    pub fn consume(&self) {
+        self.0.history.borrow_mut().consumed = Some(self.now().0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{Time, Timestep};
+    use crate::input::{GamepadSelector, Gamepads, Keyboard, Mouse};
+
+    struct Inputs {
+        keyboard: Keyboard,
+        time: Time,
+        buttons: VirtualButtons,
+        source: VirtualSource,
+    }
+
+    impl Inputs {
+        fn new() -> Self {
+            let keyboard = Keyboard::new();
+            let time = Time::new(Timestep::Fixed);
+            let buttons = VirtualButtons::default();
+            let source = VirtualSource::with_buttons(
+                &keyboard,
+                &Mouse::new(),
+                &Gamepads::new_headless(),
+                &time,
+                &buttons,
+                GamepadSelector::Specific(None),
+            );
+            Self {
+                keyboard,
+                time,
+                buttons,
+                source,
+            }
+        }
+
+        /// Run an update with the keys pressed and released beforehand.
+        fn update(&self, press: &[Key], release: &[Key]) {
+            for &key in press {
+                self.keyboard.handle_key(key, true, false);
+            }
+            for &key in release {
+                self.keyboard.handle_key(key, false, false);
+            }
+            self.time.0.frame.update(|f| f + 1);
+            self.time.0.since_startup.update(|t| t + 0.125);
+            self.buttons.update();
+        }
+    }
+
+    #[test]
+    fn multiple_bindings() {
+        let keyboard = Keyboard::new();
+        let mouse = Mouse::new();
+        let gamepads = Gamepads::new_headless();
+        let time = Time::new(Timestep::Fixed);
+        let source = VirtualSource::new_ext(
+            &keyboard,
+            &mouse,
+            &gamepads,
+            &time,
+            GamepadSelector::Specific(None),
+        );
+        let btn = VirtualButton::with_bindings(&source, [Key::Z.into(), Key::Space.into()]);
+
+        keyboard.handle_key(Key::Z, true, false);
+        assert!(btn.pressed() && btn.down());
+        keyboard.clear_phase();
+
+        // pressing a second binding while the first is held isn't a new press
+        keyboard.handle_key(Key::Space, true, false);
+        assert!(!btn.pressed() && btn.down());
+        keyboard.clear_phase();
+
+        keyboard.handle_key(Key::Z, false, false);
+        assert!(!btn.released() && btn.down());
+        keyboard.clear_phase();
+
+        // rebinding replaces the keyboard bindings only
+        btn.add_binding(GamepadButton::South);
+        keyboard.handle_key(Key::X, true, false);
+        assert_eq!(btn.rebind(), Some(Binding::Key(Key::X)));
+        assert_eq!(
+            btn.bindings(),
+            [Binding::Button(GamepadButton::South), Binding::Key(Key::X)]
+        );
+    }
+
+    #[test]
+    fn buffered_input() {
+        let inputs = Inputs::new();
+        let btn = VirtualButton::with_bindings(&inputs.source, [Key::Z.into()]);
+
+        inputs.update(&[Key::Z], &[]);
+        inputs.keyboard.clear_phase();
+        inputs.update(&[], &[]);
+        assert!(!btn.pressed());
+        assert!(btn.pressed_within(2u32) && !btn.pressed_within(1u32));
+        assert!(btn.pressed_within(0.125) && !btn.pressed_within(0.1));
+        assert_eq!((btn.held_frames(), btn.held_for()), (2, 0.125));
+
+        // a consumed press is no longer buffered
+        btn.consume();
+        assert!(!btn.pressed_within(2u32));
+
+        // double tap
+        inputs.keyboard.clear_phase();
+        inputs.update(&[], &[Key::Z]);
+        inputs.keyboard.clear_phase();
+        inputs.update(&[Key::Z], &[]);
+        assert!(btn.released_within(2u32));
+        assert_eq!(btn.press_count(1.0), 1);
+        assert_eq!(btn.held_frames(), 1);
     }
 }
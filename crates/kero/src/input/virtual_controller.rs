@@ -1,7 +1,8 @@
 use crate::core::Context;
 use crate::input::virtual_source::VirtualSource;
 use crate::input::{
-    Gamepad, GamepadAxis, GamepadButton, Key, VirtualAxis, VirtualButton, VirtualStick,
+    ControllerLayout, Gamepad, GamepadAxis, GamepadButton, Key, StickLayout, VirtualAxis,
+    VirtualButton, VirtualStick,
 };
 use std::fmt::{Debug, Formatter};
 
@@ -169,4 +170,55 @@ impl VirtualController {
         self.dpad_up.set_key(Key::ArrowUp);
         self.dpad_down.set_key(Key::ArrowDown);
     }
+
+    /// Copy all of the controller's bindings into a layout, which can be saved and restored.
+    pub fn layout(&self) -> ControllerLayout {
+        ControllerLayout {
+            direction: StickLayout::from_stick(&self.direction),
+            left_stick: StickLayout::from_stick(&self.left_stick),
+            right_stick: StickLayout::from_stick(&self.right_stick),
+            left_bumper: self.left_bumper.bindings(),
+            right_bumper: self.right_bumper.bindings(),
+            left_trigger: self.left_trigger.bindings(),
+            right_trigger: self.right_trigger.bindings(),
+            dpad_left: self.dpad_left.bindings(),
+            dpad_right: self.dpad_right.bindings(),
+            dpad_up: self.dpad_up.bindings(),
+            dpad_down: self.dpad_down.bindings(),
+            east: self.east.bindings(),
+            south: self.south.bindings(),
+            west: self.west.bindings(),
+            north: self.north.bindings(),
+            start: self.start.bindings(),
+            select: self.select.bindings(),
+            menu: self.menu.bindings(),
+        }
+    }
+
+    /// Apply all the bindings of a layout to the controller. The existing virtual inputs are
+    /// updated in place, so any handles to them will see the new bindings.
+    pub fn set_layout(&self, layout: &ControllerLayout) {
+        layout.direction.apply(&self.direction);
+        layout.left_stick.apply(&self.left_stick);
+        layout.right_stick.apply(&self.right_stick);
+        for (btn, bindings) in [
+            (&self.left_bumper, &layout.left_bumper),
+            (&self.right_bumper, &layout.right_bumper),
+            (&self.left_trigger, &layout.left_trigger),
+            (&self.right_trigger, &layout.right_trigger),
+            (&self.dpad_left, &layout.dpad_left),
+            (&self.dpad_right, &layout.dpad_right),
+            (&self.dpad_up, &layout.dpad_up),
+            (&self.dpad_down, &layout.dpad_down),
+            (&self.east, &layout.east),
+            (&self.south, &layout.south),
+            (&self.west, &layout.west),
+            (&self.north, &layout.north),
+            (&self.start, &layout.start),
+            (&self.select, &layout.select),
+            (&self.menu, &layout.menu),
+        ] {
+            btn.set_bindings(bindings.iter().copied());
+        }
+    }
 }
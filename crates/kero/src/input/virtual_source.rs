@@ -1,10 +1,14 @@
-use crate::core::Context;
-use crate::input::{Gamepad, Gamepads, Keyboard};
+use crate::core::{Context, Time};
+use crate::input::{
+    Binding, Gamepad, GamepadAxis, GamepadButton, Gamepads, Key, Keyboard, Mouse, MouseButton,
+    VirtualButtons,
+};
 use std::cell::RefCell;
 use std::cmp::Ordering;
 use std::fmt::{Debug, Formatter};
 use std::ops::Deref;
 use std::rc::Rc;
+use strum::VariantArray;
 
 /// Input source for a virtual input.
 ///
@@ -36,7 +40,10 @@ impl PartialOrd for VirtualSource {
 
 struct Inner {
     pub keyboard: Keyboard,
+    pub mouse: Mouse,
     pub gamepads: Gamepads,
+    pub time: Time,
+    pub buttons: VirtualButtons,
     pub selector: RefCell<GamepadSelector>,
 }
 
@@ -49,14 +56,50 @@ pub enum GamepadSelector {
 impl VirtualSource {
     /// Create a new input source.
     pub fn new(ctx: &Context, selector: GamepadSelector) -> Self {
-        Self::new_ext(&ctx.keyboard, &ctx.gamepads, selector)
+        Self::with_buttons(
+            &ctx.keyboard,
+            &ctx.mouse,
+            &ctx.gamepads,
+            &ctx.time,
+            &ctx.virtual_buttons,
+            selector,
+        )
     }
 
-    /// Create a new input source.
-    pub fn new_ext(keyboard: &Keyboard, gamepads: &Gamepads, selector: GamepadSelector) -> Self {
+    /// Create a new input source. Buttons created from this source aren't recorded by the game
+    /// loop, so they won't have any buffered input.
+    pub fn new_ext(
+        keyboard: &Keyboard,
+        mouse: &Mouse,
+        gamepads: &Gamepads,
+        time: &Time,
+        selector: GamepadSelector,
+    ) -> Self {
+        Self::with_buttons(
+            keyboard,
+            mouse,
+            gamepads,
+            time,
+            &VirtualButtons::default(),
+            selector,
+        )
+    }
+
+    /// Create a new input source whose buttons are recorded by the `buttons` registry.
+    pub(crate) fn with_buttons(
+        keyboard: &Keyboard,
+        mouse: &Mouse,
+        gamepads: &Gamepads,
+        time: &Time,
+        buttons: &VirtualButtons,
+        selector: GamepadSelector,
+    ) -> Self {
         Self(Rc::new(Inner {
             keyboard: keyboard.clone(),
+            mouse: mouse.clone(),
             gamepads: gamepads.clone(),
+            time: time.clone(),
+            buttons: buttons.clone(),
             selector: RefCell::new(selector),
         }))
     }
@@ -66,6 +109,21 @@ impl VirtualSource {
         &self.0.keyboard
     }
 
+    #[inline]
+    pub(crate) fn mouse(&self) -> &Mouse {
+        &self.0.mouse
+    }
+
+    #[inline]
+    pub(crate) fn time(&self) -> &Time {
+        &self.0.time
+    }
+
+    #[inline]
+    pub(crate) fn buttons(&self) -> &VirtualButtons {
+        &self.0.buttons
+    }
+
     /// Create an input source that always listens to the most recently active gamepad.
     pub fn last_active(ctx: &Context) -> Self {
         Self::new(ctx, GamepadSelector::LastActive)
@@ -98,4 +156,37 @@ impl VirtualSource {
             GamepadSelector::Specific(pad) => pad.as_ref().map(f),
         }
     }
+
+    /// Listen for the next input, for rebinding controls. Returns the first key, mouse button, or
+    /// gamepad button pressed this frame, or gamepad axis pushed past
+    /// [`AXIS_THRESHOLD`](Binding::AXIS_THRESHOLD) this frame. Call this every frame while
+    /// waiting for the player to choose an input.
+    pub fn listen(&self) -> Option<Binding> {
+        let keyboard = &self.0.keyboard;
+        if let Some(&key) = Key::VARIANTS.iter().find(|&&key| keyboard.pressed(key)) {
+            return Some(Binding::Key(key));
+        }
+        let mouse = &self.0.mouse;
+        if let Some(&btn) = MouseButton::VARIANTS
+            .iter()
+            .find(|&&btn| mouse.pressed(btn))
+        {
+            return Some(Binding::Mouse(btn));
+        }
+        self.read(|pad| {
+            if let Some(&btn) = GamepadButton::VARIANTS
+                .iter()
+                .find(|&&btn| pad.pressed(btn))
+            {
+                return Some(Binding::Button(btn));
+            }
+            GamepadAxis::VARIANTS.iter().find_map(|&axis| {
+                [Binding::AXIS_THRESHOLD, -Binding::AXIS_THRESHOLD]
+                    .into_iter()
+                    .map(|threshold| Binding::axis(axis, threshold))
+                    .find(|binding| binding.pressed(self))
+            })
+        })
+        .flatten()
+    }
 }
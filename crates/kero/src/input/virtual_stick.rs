@@ -1,11 +1,17 @@
 use super::VirtualAxis;
 use crate::input::virtual_source::VirtualSource;
+use crate::input::{Deadzone, ResponseCurve, StickSnap};
 use crate::math::{Vec2, vec2};
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::cmp::Ordering;
 use std::fmt::{Debug, Formatter};
 use std::rc::Rc;
 
+#[cfg(feature = "lua")]
+pub type VirtualStickObj = fey_lua::UserDataOf<VirtualStick>;
+#[cfg(feature = "lua")]
+pub type VirtualStickRef = mlua::UserDataRef<VirtualStick>;
+
 /// Handle to a virtual stick.
 ///
 /// This combines a virtual x and y axis to simulate a thumbstick, and can be cloned and passed
@@ -37,6 +43,9 @@ impl PartialOrd for VirtualStick {
 struct Inner {
     pub x_axis: RefCell<VirtualAxis>,
     pub y_axis: RefCell<VirtualAxis>,
+    pub deadzone: Cell<Deadzone>,
+    pub curve: RefCell<ResponseCurve>,
+    pub snap: Cell<StickSnap>,
 }
 
 impl VirtualStick {
@@ -56,6 +65,9 @@ impl VirtualStick {
         Self(Rc::new(Inner {
             x_axis: RefCell::new(x_axis),
             y_axis: RefCell::new(y_axis),
+            deadzone: Cell::new(Deadzone::NONE),
+            curve: RefCell::new(ResponseCurve::Linear),
+            snap: Cell::new(StickSnap::None),
         }))
     }
 
@@ -77,29 +89,70 @@ impl VirtualStick {
         *self.0.y_axis.borrow_mut() = y_axis;
     }
 
+    /// The stick deadzone.
+    #[inline]
+    pub fn deadzone(&self) -> Deadzone {
+        self.0.deadzone.get()
+    }
+
+    /// Set the stick deadzone. This is applied to the combined value of both axes, on top of
+    /// any deadzones the axes have themselves.
+    #[inline]
+    pub fn set_deadzone(&self, deadzone: Deadzone) {
+        self.0.deadzone.set(deadzone);
+    }
+
+    /// The stick response curve.
+    #[inline]
+    pub fn curve(&self) -> ResponseCurve {
+        self.0.curve.borrow().clone()
+    }
+
+    /// Set the stick response curve, which is applied to its length after the deadzone.
+    #[inline]
+    pub fn set_curve(&self, curve: ResponseCurve) {
+        self.0.curve.replace(curve);
+    }
+
+    /// How the stick's direction is snapped.
+    #[inline]
+    pub fn snap(&self) -> StickSnap {
+        self.0.snap.get()
+    }
+
+    /// Set how the stick's direction is snapped, which is applied last.
+    #[inline]
+    pub fn set_snap(&self, snap: StickSnap) {
+        self.0.snap.set(snap);
+    }
+
     /// If either axes changed this frame.
     #[inline]
     pub fn changed(&self) -> bool {
         self.0.x_axis.borrow().changed() || self.0.y_axis.borrow().changed()
     }
 
-    /// The stick's non-normalized x-value.
+    /// The stick's raw x-value, before the stick's own processing is applied.
     #[inline]
     pub fn x(&self) -> f32 {
         self.0.x_axis.borrow().value()
     }
 
-    /// The stick's non-normalized y-value.
+    /// The stick's raw y-value, before the stick's own processing is applied.
     #[inline]
     pub fn y(&self) -> f32 {
         self.0.y_axis.borrow().value()
     }
 
-    /// The stick's normalized value.
-    #[inline]
+    /// The stick's value, after applying the deadzone, response curve, and snapping. The value
+    /// will never be longer than `1.0`.
     pub fn value(&self) -> Vec2<f32> {
-        let value = vec2(self.x(), self.y());
+        let value = self.0.deadzone.get().apply(vec2(self.x(), self.y()));
         let len = value.len();
-        if len > 1.0 { value / len } else { value }
+        let value = match len > 0.0 {
+            true => value * (self.0.curve.borrow().apply(len) / len),
+            false => value,
+        };
+        self.0.snap.get().apply(value)
     }
 }
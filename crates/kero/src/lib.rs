@@ -14,6 +14,7 @@
 //! own game engines. It provides:
 //!
 //! - 🖥️ a window, game loop, and rendering context out of the box and ready to go
+//! - 🔊 sound effects, streamed music, and mixing with volume buses
 //! - 🎮 mouse, keyboard, and gamepad input as well as virtual input mapping
 //! - 🖼️ shaders, surfaces, textures, and other graphics resources
 //! - 🖌️ a straightforward but powerful canvas-style drawing API
@@ -93,11 +94,16 @@
 //! in having more contributors. It would be great if this could be polished up, stabilized, and turned
 //! into a reliable game development tool for the Rust ecosystem.
 
+// GameError carries gilrs errors, which are large, and is returned throughout the crate
+#![allow(clippy::result_large_err)]
+
+pub mod audio;
 pub mod core;
 pub mod gfx;
 pub mod input;
 pub mod misc;
 mod new_game;
+pub mod tween;
 
 #[cfg(feature = "lua")]
 pub use fey_lua as lua;
@@ -127,6 +133,7 @@ pub use new_game::new_game;
 
 ///! Include all types and traits.
 pub mod prelude {
+    pub use crate::audio::*;
     pub use crate::color::*;
     pub use crate::core::*;
     pub use crate::gfx::*;
@@ -137,6 +144,7 @@ pub mod prelude {
     pub use crate::math::*;
     pub use crate::misc::*;
     pub use crate::rand::*;
+    pub use crate::tween::*;
 
     #[cfg(feature = "lua")]
     pub use crate::lua::*;
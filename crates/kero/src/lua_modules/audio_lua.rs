@@ -0,0 +1,142 @@
+use crate::audio::{Audio, MusicRef, SoundRef};
+use crate::core::Context;
+use crate::lua::LuaModule;
+use mlua::prelude::LuaResult;
+use mlua::{BorrowedStr, Lua, Value};
+
+pub struct AudioModule;
+
+impl LuaModule for AudioModule {
+    const PATH: &'static str = "Audio";
+
+    fn load(lua: &Lua) -> LuaResult<Value> {
+        let m = lua.create_table()?;
+        m.set(
+            "play",
+            lua.create_function(
+                |lua,
+                 (sound, bus, volume, pan, pitch, looping): (
+                    SoundRef,
+                    Option<BorrowedStr>,
+                    Option<f32>,
+                    Option<f32>,
+                    Option<f32>,
+                    Option<bool>,
+                )| {
+                    let ctx = Context::from_lua(lua);
+                    Ok(ctx.audio.play_ext(
+                        &sound,
+                        bus.as_deref().unwrap_or(Audio::SFX_BUS),
+                        volume.unwrap_or(1.0),
+                        pan.unwrap_or(0.0),
+                        pitch.unwrap_or(1.0),
+                        looping.unwrap_or(false),
+                    ))
+                },
+            )?,
+        )?;
+        m.set(
+            "play_music",
+            lua.create_function(
+                |lua,
+                 (music, bus, volume, looping): (
+                    MusicRef,
+                    Option<BorrowedStr>,
+                    Option<f32>,
+                    Option<bool>,
+                )| {
+                    let ctx = Context::from_lua(lua);
+                    Ok(ctx.audio.play_music_ext(
+                        &music,
+                        bus.as_deref().unwrap_or(Audio::MUSIC_BUS),
+                        volume.unwrap_or(1.0),
+                        looping.unwrap_or(true),
+                    )?)
+                },
+            )?,
+        )?;
+        m.set(
+            "voice_count",
+            lua.create_function(|lua, _: ()| {
+                let ctx = Context::from_lua(lua);
+                Ok(ctx.audio.voice_count())
+            })?,
+        )?;
+        m.set(
+            "sample_rate",
+            lua.create_function(|lua, _: ()| {
+                let ctx = Context::from_lua(lua);
+                Ok(ctx.audio.sample_rate())
+            })?,
+        )?;
+        m.set(
+            "channels",
+            lua.create_function(|lua, _: ()| {
+                let ctx = Context::from_lua(lua);
+                Ok(ctx.audio.channels())
+            })?,
+        )?;
+        m.set(
+            "master_volume",
+            lua.create_function(|lua, _: ()| {
+                let ctx = Context::from_lua(lua);
+                Ok(ctx.audio.master_volume())
+            })?,
+        )?;
+        m.set(
+            "set_master_volume",
+            lua.create_function(|lua, volume: f32| {
+                let ctx = Context::from_lua(lua);
+                ctx.audio.set_master_volume(volume);
+                Ok(())
+            })?,
+        )?;
+        m.set(
+            "bus_volume",
+            lua.create_function(|lua, bus: BorrowedStr| {
+                let ctx = Context::from_lua(lua);
+                Ok(ctx.audio.bus_volume(&bus))
+            })?,
+        )?;
+        m.set(
+            "set_bus_volume",
+            lua.create_function(|lua, (bus, volume): (BorrowedStr, f32)| {
+                let ctx = Context::from_lua(lua);
+                ctx.audio.set_bus_volume(&bus, volume);
+                Ok(())
+            })?,
+        )?;
+        m.set(
+            "bus_paused",
+            lua.create_function(|lua, bus: BorrowedStr| {
+                let ctx = Context::from_lua(lua);
+                Ok(ctx.audio.bus_paused(&bus))
+            })?,
+        )?;
+        m.set(
+            "set_bus_paused",
+            lua.create_function(|lua, (bus, paused): (BorrowedStr, bool)| {
+                let ctx = Context::from_lua(lua);
+                ctx.audio.set_bus_paused(&bus, paused);
+                Ok(())
+            })?,
+        )?;
+        m.set(
+            "stop_bus",
+            lua.create_function(|lua, bus: BorrowedStr| {
+                let ctx = Context::from_lua(lua);
+                ctx.audio.stop_bus(&bus);
+                Ok(())
+            })?,
+        )?;
+        m.set(
+            "stop_all",
+            lua.create_function(|lua, _: ()| {
+                let ctx = Context::from_lua(lua);
+                ctx.audio.stop_all();
+                Ok(())
+            })?,
+        )?;
+        Ok(Value::Table(m))
+    }
+}
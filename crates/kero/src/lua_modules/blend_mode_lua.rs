@@ -1,22 +1,40 @@
-use crate::gfx::BlendMode;
+use crate::gfx::{BlendComponent, BlendFactor, BlendMode, BlendOp, ColorMask};
 use mlua::prelude::LuaError;
-use mlua::{BorrowedStr, FromLua, IntoLua, Lua, Value};
+use mlua::{BorrowedStr, FromLua, IntoLua, Lua, Table, Value};
 
 impl FromLua for BlendMode {
     #[inline]
     fn from_lua(value: Value, lua: &Lua) -> mlua::Result<Self> {
+        if let Value::Table(table) = value {
+            let color: BlendComponent = table.get("color")?;
+            let alpha: Option<BlendComponent> = table.get("alpha")?;
+            let mask: Option<ColorMask> = table.get("mask")?;
+            return Ok(BlendMode::Custom {
+                color,
+                alpha: alpha.unwrap_or(color),
+                mask: mask.unwrap_or_default(),
+            });
+        }
         let s = BorrowedStr::from_lua(value, lua)?;
         Ok(match s.as_ref() {
             "normal" => BlendMode::Normal,
             "add" => BlendMode::Add,
             "subtract" => BlendMode::Subtract,
             "multiply" => BlendMode::Multiply,
+            "screen" => BlendMode::Screen,
+            "lighten" => BlendMode::Lighten,
+            "darken" => BlendMode::Darken,
+            "straight" => BlendMode::Straight,
+            "erase" => BlendMode::Erase,
+            "replace" => BlendMode::Replace,
+            "alpha_only" => BlendMode::AlphaOnly,
             s => return Err(LuaError::runtime(format!("invalid blend mode {s:?}"))),
         })
     }
 }
 
 impl BlendMode {
+    /// The blend mode's name in Lua, which is `"custom"` for custom blend modes.
     #[inline]
     pub fn lua_str(self) -> &'static str {
         match self {
@@ -24,13 +42,161 @@ impl BlendMode {
             Self::Add => "add",
             Self::Subtract => "subtract",
             Self::Multiply => "multiply",
+            Self::Screen => "screen",
+            Self::Lighten => "lighten",
+            Self::Darken => "darken",
+            Self::Straight => "straight",
+            Self::Erase => "erase",
+            Self::Replace => "replace",
+            Self::AlphaOnly => "alpha_only",
+            Self::Custom { .. } => "custom",
         }
     }
 }
 
 impl IntoLua for BlendMode {
+    #[inline]
+    fn into_lua(self, lua: &Lua) -> mlua::Result<Value> {
+        match self {
+            Self::Custom { color, alpha, mask } => {
+                let table = lua.create_table()?;
+                table.set("color", color)?;
+                table.set("alpha", alpha)?;
+                table.set("mask", mask)?;
+                Ok(Value::Table(table))
+            }
+            _ => self.lua_str().into_lua(lua),
+        }
+    }
+}
+
+impl FromLua for BlendComponent {
+    #[inline]
+    fn from_lua(value: Value, lua: &Lua) -> mlua::Result<Self> {
+        let table = Table::from_lua(value, lua)?;
+        let op: Option<BlendOp> = table.get("op")?;
+        Ok(BlendComponent::new(
+            table.get("src")?,
+            table.get("dst")?,
+            op.unwrap_or(BlendOp::Add),
+        ))
+    }
+}
+
+impl IntoLua for BlendComponent {
+    #[inline]
+    fn into_lua(self, lua: &Lua) -> mlua::Result<Value> {
+        let table = lua.create_table()?;
+        table.set("src", self.src)?;
+        table.set("dst", self.dst)?;
+        table.set("op", self.op)?;
+        Ok(Value::Table(table))
+    }
+}
+
+impl FromLua for BlendFactor {
+    #[inline]
+    fn from_lua(value: Value, lua: &Lua) -> mlua::Result<Self> {
+        let s = BorrowedStr::from_lua(value, lua)?;
+        BlendFactor::VARIANTS
+            .into_iter()
+            .find(|factor| factor.lua_str() == s.as_ref())
+            .ok_or_else(|| LuaError::runtime(format!("invalid blend factor {:?}", s.as_ref())))
+    }
+}
+
+impl BlendFactor {
+    /// The blend factor's name in Lua.
+    #[inline]
+    pub fn lua_str(self) -> &'static str {
+        match self {
+            Self::Zero => "zero",
+            Self::One => "one",
+            Self::Src => "src",
+            Self::OneMinusSrc => "one_minus_src",
+            Self::SrcAlpha => "src_alpha",
+            Self::OneMinusSrcAlpha => "one_minus_src_alpha",
+            Self::Dst => "dst",
+            Self::OneMinusDst => "one_minus_dst",
+            Self::DstAlpha => "dst_alpha",
+            Self::OneMinusDstAlpha => "one_minus_dst_alpha",
+            Self::SrcAlphaSaturated => "src_alpha_saturated",
+        }
+    }
+}
+
+impl IntoLua for BlendFactor {
     #[inline]
     fn into_lua(self, lua: &Lua) -> mlua::Result<Value> {
         self.lua_str().into_lua(lua)
     }
 }
+
+impl FromLua for BlendOp {
+    #[inline]
+    fn from_lua(value: Value, lua: &Lua) -> mlua::Result<Self> {
+        let s = BorrowedStr::from_lua(value, lua)?;
+        BlendOp::VARIANTS
+            .into_iter()
+            .find(|op| op.lua_str() == s.as_ref())
+            .ok_or_else(|| LuaError::runtime(format!("invalid blend op {:?}", s.as_ref())))
+    }
+}
+
+impl BlendOp {
+    /// The blend operation's name in Lua.
+    #[inline]
+    pub fn lua_str(self) -> &'static str {
+        match self {
+            Self::Add => "add",
+            Self::Subtract => "subtract",
+            Self::ReverseSubtract => "reverse_subtract",
+            Self::Min => "min",
+            Self::Max => "max",
+        }
+    }
+}
+
+impl IntoLua for BlendOp {
+    #[inline]
+    fn into_lua(self, lua: &Lua) -> mlua::Result<Value> {
+        self.lua_str().into_lua(lua)
+    }
+}
+
+impl FromLua for ColorMask {
+    /// Color masks are strings of the channels that are written, such as `"rgb"` or `"a"`.
+    #[inline]
+    fn from_lua(value: Value, lua: &Lua) -> mlua::Result<Self> {
+        let s = BorrowedStr::from_lua(value, lua)?;
+        let mut mask = ColorMask::NONE;
+        for chr in s.as_ref().chars() {
+            match chr {
+                'r' => mask.r = true,
+                'g' => mask.g = true,
+                'b' => mask.b = true,
+                'a' => mask.a = true,
+                _ => {
+                    return Err(LuaError::runtime(format!(
+                        "invalid color mask {:?}",
+                        s.as_ref()
+                    )));
+                }
+            }
+        }
+        Ok(mask)
+    }
+}
+
+impl IntoLua for ColorMask {
+    #[inline]
+    fn into_lua(self, lua: &Lua) -> mlua::Result<Value> {
+        let mut s = String::new();
+        for (chr, write) in [('r', self.r), ('g', self.g), ('b', self.b), ('a', self.a)] {
+            if write {
+                s.push(chr);
+            }
+        }
+        s.into_lua(lua)
+    }
+}
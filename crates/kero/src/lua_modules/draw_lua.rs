@@ -1,6 +1,6 @@
 use crate::gfx::{
     BlendMode, ColorMode, Draw, FontRef, IndexBufferRef, Sampler, ShaderRef, SubTextureRef,
-    SurfaceRef, Texture, TextureRef, Topology, Vertex, VertexBufferRef,
+    SurfaceRef, TextLayoutRef, Texture, TextureRef, Topology, Vertex, VertexBufferRef,
 };
 use fey_color::Rgba8;
 use fey_lua::LuaModule;
@@ -9,18 +9,36 @@ use fey_math::{
     RectU, TriangleF, Vec2F, Vec3F, Vec4F, circle, line, vec2,
 };
 use mlua::prelude::{LuaError, LuaResult};
-use mlua::{BorrowedStr, Either, IntoLua, Lua, Number, Table, UserData, UserDataMethods, Value};
+use mlua::{
+    BorrowedStr, Either, Function, IntoLua, Lua, Number, Table, UserData, UserDataMethods, Value,
+};
 use std::ops::Deref;
 
 impl Draw {
     pub fn from_lua(lua: &Lua) -> LuaResult<&mut Draw> {
-        // SAFETY: app_data_mut() will panic if the pointer is attempted to be borrowed twice
+        // SAFETY: the pointer is only set while a `&mut Draw` is lent to Lua, and whoever lent it
+        // doesn't touch the draw until Lua returns. Anything that calls back into Lua while holding
+        // the returned reference must lend it again with `lend_to_lua` (eg. scene stack renders)
         let draw = *lua
             .app_data_mut::<*mut Draw>()
             .ok_or_else(|| LuaError::runtime("cannot draw outside of render()"))?
             .deref();
         Ok(unsafe { &mut *draw })
     }
+
+    /// Lend this drawing context to Lua while `f` runs, restoring the previously lent one after.
+    /// Code holding a `&mut Draw` must do this before calling back into Lua, so that the `Draw`
+    /// Lua obtains is borrowed from the innermost reference, never alongside it.
+    pub(crate) fn lend_to_lua<R>(&mut self, lua: &Lua, f: impl FnOnce() -> R) -> R {
+        let draw: *mut Draw = self;
+        let prev = lua.set_app_data(draw);
+        let result = f();
+        match prev {
+            Some(prev) => lua.set_app_data(prev),
+            None => lua.remove_app_data::<*mut Draw>(),
+        };
+        result
+    }
 }
 
 pub struct DrawModule;
@@ -155,6 +173,36 @@ fn add_methods<T, M: UserDataMethods<T>>(methods: &mut M) {
         Draw::from_lua(lua)?.set_clip_rect(value);
         Ok(())
     });
+    methods.add_function("has_depth_stencil", |lua, _: ()| {
+        Ok(Draw::from_lua(lua)?.has_depth_stencil())
+    });
+    methods.add_function("depth_test", |lua, _: ()| {
+        Ok(Draw::from_lua(lua)?.depth_test())
+    });
+    methods.add_function("set_depth_test", |lua, enabled: bool| {
+        Draw::from_lua(lua)?.set_depth_test(enabled);
+        Ok(())
+    });
+    methods.add_function("depth", |lua, _: ()| Ok(Draw::from_lua(lua)?.depth()));
+    methods.add_function("set_depth", |lua, value: f32| {
+        Draw::from_lua(lua)?.set_depth(value);
+        Ok(())
+    });
+    methods.add_function("mask_count", |lua, _: ()| {
+        Ok(Draw::from_lua(lua)?.mask_count())
+    });
+    methods.add_function("push_mask", |lua, f: Function| {
+        Draw::from_lua(lua)?
+            .begin_mask()
+            .map_err(LuaError::external)?;
+        let result = f.call::<()>(());
+        let ended = Draw::from_lua(lua)?.end_mask();
+        result?;
+        ended.map_err(LuaError::external)
+    });
+    methods.add_function("pop_mask", |lua, _: ()| {
+        Draw::from_lua(lua)?.pop_mask().map_err(LuaError::external)
+    });
     methods.add_function("transform", |lua, _: ()| {
         Ok(*Draw::from_lua(lua)?.transform())
     });
@@ -527,6 +575,21 @@ fn add_methods<T, M: UserDataMethods<T>>(methods: &mut M) {
             Ok(())
         },
     );
+    methods.add_function(
+        "text_layout",
+        |lua,
+         (layout, pos, font, col, size): (
+            TextLayoutRef,
+            Vec2F,
+            FontRef,
+            Option<Rgba8>,
+            Option<f32>,
+        )| {
+            let col = col.unwrap_or(Rgba8::WHITE);
+            Draw::from_lua(lua)?.text_layout(&layout, pos, font.deref(), col, size);
+            Ok(())
+        },
+    );
     methods.add_function(
         "custom",
         |lua, (tex, topo, verts, inds): (Option<Texture>, Topology, Table, Table)| {
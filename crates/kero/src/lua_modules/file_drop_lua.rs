@@ -0,0 +1,60 @@
+use crate::core::Context;
+use crate::lua::LuaModule;
+use mlua::prelude::LuaResult;
+use mlua::{Lua, Value};
+use std::path::PathBuf;
+
+pub struct FileDropModule;
+
+impl LuaModule for FileDropModule {
+    const PATH: &'static str = "FileDrop";
+
+    fn load(lua: &Lua) -> LuaResult<Value> {
+        let m = lua.create_table()?;
+
+        m.set(
+            "hovering",
+            lua.create_function(|lua, _: ()| Ok(Context::from_lua(lua).file_drop.hovering()))?,
+        )?;
+        m.set(
+            "hovered_files",
+            lua.create_function(|lua, _: ()| {
+                Ok(paths(Context::from_lua(lua).file_drop.hovered_files()))
+            })?,
+        )?;
+        m.set(
+            "hover_pos",
+            lua.create_function(|lua, _: ()| Ok(Context::from_lua(lua).file_drop.hover_pos()))?,
+        )?;
+        m.set(
+            "hover_cancelled",
+            lua.create_function(|lua, _: ()| {
+                Ok(Context::from_lua(lua).file_drop.hover_cancelled())
+            })?,
+        )?;
+        m.set(
+            "dropped",
+            lua.create_function(|lua, _: ()| Ok(Context::from_lua(lua).file_drop.dropped()))?,
+        )?;
+        m.set(
+            "dropped_files",
+            lua.create_function(|lua, _: ()| {
+                Ok(paths(Context::from_lua(lua).file_drop.dropped_files()))
+            })?,
+        )?;
+        m.set(
+            "drop_pos",
+            lua.create_function(|lua, _: ()| Ok(Context::from_lua(lua).file_drop.drop_pos()))?,
+        )?;
+
+        Ok(Value::Table(m))
+    }
+}
+
+#[inline]
+fn paths(paths: Vec<PathBuf>) -> Vec<String> {
+    paths
+        .into_iter()
+        .map(|path| path.to_string_lossy().into_owned())
+        .collect()
+}
@@ -1,5 +1,7 @@
 use crate::core::Context;
-use crate::gfx::{Font, FontMut, FontRef, SubTexture};
+use crate::gfx::{
+    Font, FontMut, FontRef, SubTexture, TextLayout, TextLayoutMut, TextLayoutRef, TextOptions,
+};
 use crate::lua::LuaModule;
 use crate::misc::BASIC_LATIN;
 use mlua::prelude::{LuaError, LuaResult};
@@ -33,6 +35,33 @@ impl UserData for FontModule {
                     .map(|(font, _)| font)
             },
         );
+        methods.add_function(
+            "sdf_from_ttf_file",
+            |lua,
+             (path, size, spread, multi_channel, chars): (
+                BorrowedStr,
+                f32,
+                f32,
+                bool,
+                Option<BorrowedStr>,
+            )| {
+                let chars = chars
+                    .map(|chrs| chrs.to_string())
+                    .unwrap_or_else(|| BASIC_LATIN.chars().collect());
+                let ctx = Context::from_lua(lua);
+                Font::sdf_from_ttf_file(
+                    &ctx.graphics,
+                    path.as_ref(),
+                    size,
+                    spread,
+                    multi_channel,
+                    chars.chars(),
+                )
+                .map_err(LuaError::external)?
+                .ok_or_else(|| LuaError::runtime("failed to pack font"))
+                .map(|(font, _)| font)
+            },
+        );
         add_methods(methods);
     }
 }
@@ -52,6 +81,19 @@ fn add_methods<T, M: UserDataMethods<T>>(methods: &mut M) {
 
     methods.add_function("size", |_, this: FontRef| Ok(this.size()));
     methods.add_function("pixelated", |_, this: FontRef| Ok(this.pixelated()));
+    methods.add_function("sdf_spread", |_, this: FontRef| Ok(this.sdf_spread()));
+    methods.add_function("ascent", |_, this: FontRef| Ok(this.ascent()));
+    methods.add_function("descent", |_, this: FontRef| Ok(this.descent()));
+    methods.add_function("line_gap", |_, this: FontRef| Ok(this.line_gap()));
+    methods.add_function("height", |_, this: FontRef| Ok(this.height()));
+    methods.add_function("line_height", |_, this: FontRef| Ok(this.line_height()));
+    methods.add_function(
+        "set_metrics",
+        |_, (mut this, ascent, descent, line_gap): (FontMut, f32, f32, f32)| {
+            this.set_metrics(ascent, descent, line_gap);
+            Ok(())
+        },
+    );
     methods.add_function(
         "set_glyph",
         |_, (mut this, chr, sub, adv): (FontMut, BorrowedStr, Option<SubTexture>, f32)| {
@@ -76,4 +118,51 @@ fn add_methods<T, M: UserDataMethods<T>>(methods: &mut M) {
             Ok(this.kerning(left, right).unwrap_or(0.0))
         },
     );
+    methods.add_function(
+        "layout",
+        |_, (this, text, opts): (FontRef, BorrowedStr, Option<TextOptions>)| {
+            Ok(TextLayout::new(&*this, &text, opts.unwrap_or_default()))
+        },
+    );
+    methods.add_function(
+        "text_size",
+        |_, (this, text, opts): (FontRef, BorrowedStr, Option<TextOptions>)| {
+            let size = TextLayout::new(&*this, &text, opts.unwrap_or_default()).size();
+            Ok((size.x, size.y))
+        },
+    );
+}
+
+impl UserData for TextLayout {
+    fn add_methods<M: UserDataMethods<Self>>(methods: &mut M) {
+        methods.add_function("text", |_, this: TextLayoutRef| Ok(this.text().to_string()));
+        methods.add_function("width", |_, this: TextLayoutRef| Ok(this.width()));
+        methods.add_function("height", |_, this: TextLayoutRef| Ok(this.height()));
+        methods.add_function("size", |_, this: TextLayoutRef| Ok(this.size()));
+        methods.add_function("bounds", |_, this: TextLayoutRef| Ok(this.bounds()));
+        methods.add_function("line_count", |_, this: TextLayoutRef| {
+            Ok(this.lines().len())
+        });
+        methods.add_function("glyph_count", |_, this: TextLayoutRef| {
+            Ok(this.glyphs().len())
+        });
+        methods.add_function("glyph", |_, (this, i): (TextLayoutRef, usize)| {
+            let g = i.checked_sub(1).and_then(|i| this.glyphs().get(i));
+            Ok((
+                g.map(|g| g.chr.to_string()),
+                g.map(|g| g.pos),
+                g.map(|g| g.line + 1),
+            ))
+        });
+        methods.add_function(
+            "update",
+            |_,
+             (mut this, font, text, opts): (
+                TextLayoutMut,
+                FontRef,
+                BorrowedStr,
+                Option<TextOptions>,
+            )| { Ok(this.update(&*font, &text, opts.unwrap_or_default())) },
+        );
+    }
 }
@@ -1,5 +1,5 @@
 use crate::core::Context;
-use crate::input::{Gamepad, GamepadAxis, GamepadButton, GamepadStatus};
+use crate::input::{Gamepad, GamepadAxis, GamepadButton, GamepadStatus, Rumble};
 use crate::lua::LuaModule;
 use fey_lua::{UserDataOf, create_fill};
 use mlua::prelude::{LuaError, LuaResult};
@@ -53,6 +53,15 @@ impl UserData for GamepadModule {
             let ctx = Context::from_lua(lua);
             Ok(ctx.gamepads.last_active_lua())
         });
+        methods.add_function("rumble_intensity", |lua, _: ()| {
+            let ctx = Context::from_lua(lua);
+            Ok(ctx.gamepads.rumble_intensity())
+        });
+        methods.add_function("set_rumble_intensity", |lua, intensity: f32| {
+            let ctx = Context::from_lua(lua);
+            ctx.gamepads.set_rumble_intensity(intensity);
+            Ok(())
+        });
         add_methods(methods);
     }
 }
@@ -103,6 +112,28 @@ fn add_methods<T, M: UserDataMethods<T>>(methods: &mut M) {
         "axis_changed",
         |_, (this, axis): (GamepadRef, GamepadAxis)| Ok(this.axis_changed(axis)),
     );
+    methods.add_function(
+        "rumble",
+        |_, (this, strong, weak, duration): (GamepadRef, f32, f32, Option<f32>)| {
+            Ok(this.rumble(strong, weak, duration.unwrap_or(f32::INFINITY)))
+        },
+    );
+    methods.add_function(
+        "stop_rumble",
+        |_, (this, rumble): (GamepadRef, Option<Rumble>)| {
+            match rumble {
+                Some(rumble) => this.stop_rumble(rumble),
+                None => this.stop_all_rumble(),
+            }
+            Ok(())
+        },
+    );
+    methods.add_function("is_rumbling", |_, (this, rumble): (GamepadRef, Rumble)| {
+        Ok(this.is_rumbling(rumble))
+    });
+    methods.add_function("rumble_levels", |_, this: GamepadRef| {
+        Ok(this.rumble_levels())
+    });
 }
 
 pub struct GamepadButtonModule;
@@ -179,3 +210,17 @@ impl IntoLua for GamepadAxis {
         Ok(Value::Integer(self as _))
     }
 }
+
+impl FromLua for Rumble {
+    #[inline]
+    fn from_lua(value: Value, lua: &Lua) -> LuaResult<Self> {
+        u64::from_lua(value, lua).map(Self)
+    }
+}
+
+impl IntoLua for Rumble {
+    #[inline]
+    fn into_lua(self, _lua: &Lua) -> LuaResult<Value> {
+        Ok(Value::Integer(self.0 as _))
+    }
+}
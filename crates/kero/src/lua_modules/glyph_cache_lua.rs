@@ -0,0 +1,71 @@
+use crate::core::Context;
+use crate::gfx::{GlyphCache, GlyphCacheRef};
+use crate::lua::LuaModule;
+use mlua::prelude::{LuaError, LuaResult};
+use mlua::{BorrowedStr, Lua, UserData, UserDataMethods, Value};
+
+pub struct GlyphCacheModule;
+
+impl LuaModule for GlyphCacheModule {
+    const PATH: &'static str = "GlyphCache";
+
+    fn load(lua: &Lua) -> LuaResult<Value> {
+        lua.create_userdata(Self).map(Value::UserData)
+    }
+}
+
+impl UserData for GlyphCacheModule {
+    fn add_methods<M: UserDataMethods<Self>>(methods: &mut M) {
+        methods.add_function(
+            "from_ttf_file",
+            |lua, (path, pixelated): (BorrowedStr, bool)| {
+                let ctx = Context::from_lua(lua);
+                GlyphCache::from_ttf_file(&ctx.graphics, path.as_ref(), pixelated)
+                    .map_err(LuaError::external)
+            },
+        );
+        add_methods(methods);
+    }
+}
+
+impl UserData for GlyphCache {
+    fn add_methods<M: UserDataMethods<Self>>(methods: &mut M) {
+        add_methods(methods);
+    }
+}
+
+fn add_methods<T, M: UserDataMethods<T>>(methods: &mut M) {
+    methods.add_function("font", |_, (this, size): (GlyphCacheRef, f32)| {
+        Ok(this.font(size))
+    });
+    methods.add_function("pixelated", |_, this: GlyphCacheRef| Ok(this.pixelated()));
+    methods.add_function("page_size", |_, this: GlyphCacheRef| Ok(this.page_size()));
+    methods.add_function("set_page_size", |_, (this, size): (GlyphCacheRef, u32)| {
+        this.set_page_size(size);
+        Ok(())
+    });
+    methods.add_function("max_pages", |_, this: GlyphCacheRef| Ok(this.max_pages()));
+    methods.add_function(
+        "set_max_pages",
+        |_, (this, max_pages): (GlyphCacheRef, usize)| {
+            this.set_max_pages(max_pages);
+            Ok(())
+        },
+    );
+    methods.add_function("page_count", |_, this: GlyphCacheRef| Ok(this.page_count()));
+    methods.add_function("pages", |_, this: GlyphCacheRef| Ok(this.pages()));
+    methods.add_function("glyph_count", |_, this: GlyphCacheRef| {
+        Ok(this.glyph_count())
+    });
+    methods.add_function("clear", |_, this: GlyphCacheRef| {
+        this.clear();
+        Ok(())
+    });
+    methods.add_function(
+        "prepare",
+        |_, (this, size, chars): (GlyphCacheRef, f32, BorrowedStr)| {
+            this.prepare(size, chars.chars());
+            Ok(())
+        },
+    );
+}
@@ -39,6 +39,32 @@ impl LuaModule for KeyboardModule {
                 lua.create_string(Context::from_lua(lua).keyboard.text_input())
             })?,
         )?;
+        m.set(
+            "ime_enabled",
+            lua.create_function(|lua, _: ()| Ok(Context::from_lua(lua).keyboard.ime_enabled()))?,
+        )?;
+        m.set(
+            "preedit",
+            lua.create_function(|lua, _: ()| {
+                lua.create_string(Context::from_lua(lua).keyboard.preedit())
+            })?,
+        )?;
+        m.set(
+            "preedit_cursor",
+            lua.create_function(|lua, _: ()| {
+                // convert to an inclusive 1-based range to match string.sub
+                Ok(match Context::from_lua(lua).keyboard.preedit_cursor() {
+                    Some((start, end)) => (Some(start + 1), Some(end)),
+                    None => (None, None),
+                })
+            })?,
+        )?;
+        m.set(
+            "ime_commit",
+            lua.create_function(|lua, _: ()| {
+                lua.create_string(Context::from_lua(lua).keyboard.ime_commit())
+            })?,
+        )?;
         m.set(
             "ctrl",
             lua.create_function(|lua, _: ()| Ok(Context::from_lua(lua).keyboard.ctrl()))?,
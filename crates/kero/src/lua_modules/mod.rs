@@ -1,48 +1,71 @@
 mod app_lua;
+mod audio_lua;
 mod blend_mode_lua;
 mod color_mode_lua;
 mod draw_lua;
+mod file_drop_lua;
 mod font_lua;
 mod gamepad_lua;
+mod glyph_cache_lua;
 mod index_buffer_lua;
 mod key_lua;
 mod keyboard_lua;
 mod monitor_lua;
 mod mouse_button_lua;
 mod mouse_lua;
+mod music_lua;
 mod sampler_lua;
+mod scene_stack_lua;
 mod screen_lua;
 mod shader_lua;
+mod sound_lua;
+mod storage_lua;
 mod sub_texture_lua;
 mod surface_lua;
 mod texture_format_lua;
 mod texture_lua;
 mod time_lua;
+mod timeline_lua;
 mod topology_lua;
+mod touches_lua;
 mod vertex_buffer_lua;
 mod vertex_lua;
 mod video_mode_lua;
+mod virtual_axis_lua;
+mod virtual_stick_lua;
+mod voice_lua;
 mod window_lua;
 
 pub use app_lua::*;
+pub use audio_lua::*;
 pub use color_mode_lua::*;
 pub use draw_lua::*;
+pub use file_drop_lua::*;
 pub use font_lua::*;
 pub use gamepad_lua::*;
+pub use glyph_cache_lua::*;
 pub use index_buffer_lua::*;
 pub use key_lua::*;
 pub use keyboard_lua::*;
 pub use monitor_lua::*;
 pub use mouse_button_lua::*;
 pub use mouse_lua::*;
+pub use music_lua::*;
 pub use sampler_lua::*;
+pub use scene_stack_lua::*;
 pub use screen_lua::*;
 pub use shader_lua::*;
+pub use sound_lua::*;
+pub use storage_lua::*;
 pub use sub_texture_lua::*;
 pub use surface_lua::*;
 pub use texture_lua::*;
 pub use time_lua::*;
+pub use timeline_lua::*;
+pub use touches_lua::*;
 pub use vertex_buffer_lua::*;
 pub use vertex_lua::*;
 pub use video_mode_lua::*;
+pub use virtual_axis_lua::*;
+pub use virtual_stick_lua::*;
 pub use window_lua::*;
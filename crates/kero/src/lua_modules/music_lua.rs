@@ -0,0 +1,41 @@
+use crate::audio::{Music, MusicRef};
+use crate::lua::LuaModule;
+use mlua::prelude::LuaResult;
+use mlua::{BorrowedStr, FromLua, Lua, UserData, UserDataMethods, UserDataRef, Value};
+
+pub struct MusicModule;
+
+impl LuaModule for MusicModule {
+    const PATH: &'static str = "Music";
+
+    fn load(lua: &Lua) -> LuaResult<Value> {
+        lua.create_userdata(Self).map(Value::UserData)
+    }
+}
+
+impl UserData for MusicModule {
+    fn add_methods<M: UserDataMethods<Self>>(methods: &mut M) {
+        methods.add_function("from_file", |_, path: BorrowedStr| {
+            Ok(Music::from_file(path.as_ref())?)
+        });
+        add_methods(methods);
+    }
+}
+
+impl UserData for Music {
+    fn add_methods<M: UserDataMethods<Self>>(methods: &mut M) {
+        add_methods(methods);
+    }
+}
+
+fn add_methods<T, M: UserDataMethods<T>>(methods: &mut M) {
+    methods.add_function("channels", |_, this: MusicRef| Ok(this.channels()));
+    methods.add_function("sample_rate", |_, this: MusicRef| Ok(this.sample_rate()));
+}
+
+impl FromLua for Music {
+    #[inline]
+    fn from_lua(value: Value, lua: &Lua) -> LuaResult<Self> {
+        UserDataRef::<Self>::from_lua(value, lua).map(|h| h.clone())
+    }
+}
@@ -0,0 +1,160 @@
+use crate::core::{
+    Context, GameError, Scene, SceneCommand, SceneStack, SceneStackMut, SceneStackRef,
+    SceneTransition, Scenes, ScenesRef,
+};
+use crate::gfx::Draw;
+use crate::lua::LuaModule;
+use mlua::prelude::LuaResult;
+use mlua::{AnyUserData, Function, IntoLuaMulti, Lua, Table, UserData, UserDataMethods, Value};
+
+pub struct SceneStackModule;
+
+impl LuaModule for SceneStackModule {
+    const PATH: &'static str = "SceneStack";
+
+    fn load(lua: &Lua) -> LuaResult<Value> {
+        lua.create_userdata(Self).map(Value::UserData)
+    }
+}
+
+impl UserData for SceneStackModule {
+    fn add_methods<M: UserDataMethods<Self>>(methods: &mut M) {
+        methods.add_function("new", |lua, scene: Table| {
+            let ctx = Context::from_lua(lua).clone();
+            Ok(SceneStack::new(&ctx, LuaScene::new(scene))?)
+        });
+        add_methods(methods);
+    }
+}
+
+impl UserData for SceneStack {
+    fn add_methods<M: UserDataMethods<Self>>(methods: &mut M) {
+        add_methods(methods);
+    }
+}
+
+fn add_methods<T, M: UserDataMethods<T>>(methods: &mut M) {
+    methods.add_function("scenes", |lua, this: SceneStackRef| {
+        lua.create_userdata(this.scenes().clone())
+    });
+    methods.add_function("len", |_, this: SceneStackRef| Ok(this.len()));
+    methods.add_function("is_transitioning", |_, this: SceneStackRef| {
+        Ok(this.is_transitioning())
+    });
+    methods.add_function("update", |lua, mut this: SceneStackMut| {
+        let ctx = Context::from_lua(lua).clone();
+        this.update(&ctx)?;
+        Ok(())
+    });
+    methods.add_function("render", |lua, mut this: SceneStackMut| {
+        let ctx = Context::from_lua(lua).clone();
+        this.render(&ctx, Draw::from_lua(lua)?)?;
+        Ok(())
+    });
+}
+
+impl UserData for Scenes {
+    fn add_methods<M: UserDataMethods<Self>>(methods: &mut M) {
+        methods.add_function("len", |_, this: ScenesRef| Ok(this.len()));
+        methods.add_function(
+            "push",
+            |_, (this, scene, transition): (ScenesRef, Table, Option<SceneTransition>)| {
+                this.queue(SceneCommand::Push(LuaScene::boxed(scene)), transition);
+                Ok(())
+            },
+        );
+        methods.add_function(
+            "pop",
+            |_, (this, transition): (ScenesRef, Option<SceneTransition>)| {
+                this.queue(SceneCommand::Pop, transition);
+                Ok(())
+            },
+        );
+        methods.add_function(
+            "replace",
+            |_, (this, scene, transition): (ScenesRef, Table, Option<SceneTransition>)| {
+                this.queue(SceneCommand::Replace(LuaScene::boxed(scene)), transition);
+                Ok(())
+            },
+        );
+        methods.add_function(
+            "replace_all",
+            |_, (this, scene, transition): (ScenesRef, Table, Option<SceneTransition>)| {
+                this.queue(SceneCommand::ReplaceAll(LuaScene::boxed(scene)), transition);
+                Ok(())
+            },
+        );
+    }
+}
+
+/// A scene implemented by a Lua table. Its methods are all optional.
+struct LuaScene {
+    table: Table,
+    scenes: Option<AnyUserData>,
+}
+
+impl LuaScene {
+    fn new(table: Table) -> Self {
+        Self {
+            table,
+            scenes: None,
+        }
+    }
+
+    fn boxed(table: Table) -> Box<dyn Scene> {
+        Box::new(Self::new(table))
+    }
+
+    /// Call a method on the table if it is defined.
+    fn call(&self, ctx: &Context, name: &str, args: impl IntoLuaMulti) -> LuaResult<()> {
+        let Some(method) = self.table.get::<Option<Function>>(name)? else {
+            return Ok(());
+        };
+        let mut args = args.into_lua_multi(&ctx.lua.upgrade())?;
+        args.push_front(Value::Table(self.table.clone()));
+        method.call(args)
+    }
+}
+
+impl Scene for LuaScene {
+    fn update(&mut self, ctx: &Context, scenes: &Scenes) -> Result<(), GameError> {
+        if self.scenes.is_none() {
+            self.scenes = Some(ctx.lua.upgrade().create_userdata(scenes.clone())?);
+        }
+        Ok(self.call(ctx, "update", self.scenes.clone())?)
+    }
+
+    fn render(&mut self, ctx: &Context, draw: &mut Draw) -> Result<(), GameError> {
+        // the stack is still using its own borrow of the draw, so lend Lua one borrowed from it
+        let lua = ctx.lua.upgrade();
+        Ok(draw.lend_to_lua(&lua, || self.call(ctx, "render", ()))?)
+    }
+
+    fn is_overlay(&self) -> bool {
+        self.table.get::<Option<bool>>("overlay").ok().flatten() == Some(true)
+    }
+
+    fn pauses_below(&self) -> bool {
+        self.table
+            .get::<Option<bool>>("pauses_below")
+            .ok()
+            .flatten()
+            != Some(false)
+    }
+
+    fn on_enter(&mut self, ctx: &Context) -> Result<(), GameError> {
+        Ok(self.call(ctx, "on_enter", ())?)
+    }
+
+    fn on_exit(&mut self, ctx: &Context) -> Result<(), GameError> {
+        Ok(self.call(ctx, "on_exit", ())?)
+    }
+
+    fn on_cover(&mut self, ctx: &Context) -> Result<(), GameError> {
+        Ok(self.call(ctx, "on_cover", ())?)
+    }
+
+    fn on_uncover(&mut self, ctx: &Context) -> Result<(), GameError> {
+        Ok(self.call(ctx, "on_uncover", ())?)
+    }
+}
@@ -49,6 +49,9 @@ fn add_methods<T, M: UserDataMethods<T>>(methods: &mut M) {
     methods.add_function("mouse_x", |_, this: ScreenRef| Ok(this.mouse_x()));
     methods.add_function("mouse_y", |_, this: ScreenRef| Ok(this.mouse_y()));
     methods.add_function("mouse_y", |_, this: ScreenRef| Ok(this.mouse_y()));
+    methods.add_function("hover_pos", |_, this: ScreenRef| Ok(this.hover_pos()));
+    methods.add_function("drop_pos", |_, this: ScreenRef| Ok(this.drop_pos()));
+    methods.add_function("touches", |_, this: ScreenRef| Ok(this.touches().to_vec()));
     methods.add_function("update", |lua, mut this: ScreenMut| {
         let ctx = lua.app_data_ref::<Context>().unwrap();
         this.update(&ctx);
@@ -20,6 +20,10 @@ impl UserData for ShaderModule {
             let ctx = Context::from_lua(lua);
             Ok(ctx.graphics.default_shader_userdata().clone())
         });
+        methods.add_function("sdf", |lua, _: ()| {
+            let ctx = Context::from_lua(lua);
+            Ok(ctx.graphics.sdf_shader_userdata().clone())
+        });
         methods.add_function("new", |lua, source: BorrowedStr| {
             let ctx = Context::from_lua(lua);
             Ok(ctx.graphics.create_shader(source.as_ref()))
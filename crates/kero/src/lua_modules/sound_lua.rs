@@ -0,0 +1,43 @@
+use crate::audio::{Sound, SoundRef};
+use crate::lua::LuaModule;
+use mlua::prelude::LuaResult;
+use mlua::{BorrowedStr, FromLua, Lua, UserData, UserDataMethods, UserDataRef, Value};
+
+pub struct SoundModule;
+
+impl LuaModule for SoundModule {
+    const PATH: &'static str = "Sound";
+
+    fn load(lua: &Lua) -> LuaResult<Value> {
+        lua.create_userdata(Self).map(Value::UserData)
+    }
+}
+
+impl UserData for SoundModule {
+    fn add_methods<M: UserDataMethods<Self>>(methods: &mut M) {
+        methods.add_function("from_file", |_, path: BorrowedStr| {
+            Ok(Sound::from_file(path.as_ref())?)
+        });
+        add_methods(methods);
+    }
+}
+
+impl UserData for Sound {
+    fn add_methods<M: UserDataMethods<Self>>(methods: &mut M) {
+        add_methods(methods);
+    }
+}
+
+fn add_methods<T, M: UserDataMethods<T>>(methods: &mut M) {
+    methods.add_function("channels", |_, this: SoundRef| Ok(this.channels()));
+    methods.add_function("sample_rate", |_, this: SoundRef| Ok(this.sample_rate()));
+    methods.add_function("frame_count", |_, this: SoundRef| Ok(this.frame_count()));
+    methods.add_function("duration", |_, this: SoundRef| Ok(this.duration()));
+}
+
+impl FromLua for Sound {
+    #[inline]
+    fn from_lua(value: Value, lua: &Lua) -> LuaResult<Self> {
+        UserDataRef::<Self>::from_lua(value, lua).map(|h| h.clone())
+    }
+}
@@ -0,0 +1,84 @@
+use crate::core::{Context, StorageDir};
+use crate::lua::LuaModule;
+use mlua::prelude::LuaResult;
+use mlua::{Lua, LuaSerdeExt, Value};
+
+pub struct StorageModule;
+
+impl LuaModule for StorageModule {
+    const PATH: &'static str = "Storage";
+
+    fn load(lua: &Lua) -> LuaResult<Value> {
+        let m = lua.create_table()?;
+        m.set(
+            "backups",
+            lua.create_function(|lua, _: ()| {
+                let ctx = Context::from_lua(lua);
+                Ok(ctx.storage.backups())
+            })?,
+        )?;
+        m.set(
+            "set_backups",
+            lua.create_function(|lua, backups: usize| {
+                let ctx = Context::from_lua(lua);
+                ctx.storage.set_backups(backups);
+                Ok(())
+            })?,
+        )?;
+        m.set(
+            "exists",
+            lua.create_function(|lua, (key, dir): (String, Option<StorageDir>)| {
+                let ctx = Context::from_lua(lua);
+                Ok(ctx.storage.exists(dir.unwrap_or_default(), &key)?)
+            })?,
+        )?;
+        m.set(
+            "save",
+            lua.create_function(
+                |lua, (key, value, dir): (String, Value, Option<StorageDir>)| {
+                    let ctx = Context::from_lua(lua);
+                    ctx.storage.save(dir.unwrap_or_default(), &key, &value)?;
+                    Ok(())
+                },
+            )?,
+        )?;
+        m.set(
+            "load",
+            lua.create_function(|lua, (key, dir): (String, Option<StorageDir>)| {
+                let ctx = Context::from_lua(lua);
+                match ctx
+                    .storage
+                    .load::<serde_json::Value>(dir.unwrap_or_default(), &key)?
+                {
+                    Some(value) => lua.to_value(&value),
+                    None => Ok(Value::Nil),
+                }
+            })?,
+        )?;
+        m.set(
+            "load_backup",
+            lua.create_function(
+                |lua, (key, index, dir): (String, usize, Option<StorageDir>)| {
+                    let ctx = Context::from_lua(lua);
+                    match ctx.storage.load_backup::<serde_json::Value>(
+                        dir.unwrap_or_default(),
+                        &key,
+                        index,
+                    )? {
+                        Some(value) => lua.to_value(&value),
+                        None => Ok(Value::Nil),
+                    }
+                },
+            )?,
+        )?;
+        m.set(
+            "delete",
+            lua.create_function(|lua, (key, dir): (String, Option<StorageDir>)| {
+                let ctx = Context::from_lua(lua);
+                ctx.storage.delete(dir.unwrap_or_default(), &key)?;
+                Ok(())
+            })?,
+        )?;
+        Ok(Value::Table(m))
+    }
+}
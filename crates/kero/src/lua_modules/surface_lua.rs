@@ -18,10 +18,31 @@ impl UserData for SurfaceModule {
     fn add_methods<M: UserDataMethods<Self>>(methods: &mut M) {
         methods.add_function(
             "new",
-            |lua, (w, h, fmt): (u32, u32, Option<TextureFormat>)| {
+            |lua, (w, h, fmt, depth_stencil): (u32, u32, Option<TextureFormat>, Option<bool>)| {
                 let ctx = Context::from_lua(lua);
                 let fmt = fmt.unwrap_or(TextureFormat::Rgba8);
-                Ok(ctx.graphics.create_surface((w, h), fmt))
+                let depth_stencil = depth_stencil.unwrap_or(false);
+                Ok(ctx
+                    .graphics
+                    .create_surface_ext((w, h), fmt, 1, depth_stencil))
+            },
+        );
+        methods.add_function(
+            "new_msaa",
+            |lua,
+             (w, h, samples, fmt, depth_stencil): (
+                u32,
+                u32,
+                u32,
+                Option<TextureFormat>,
+                Option<bool>,
+            )| {
+                let ctx = Context::from_lua(lua);
+                let fmt = fmt.unwrap_or(TextureFormat::Rgba8);
+                let depth_stencil = depth_stencil.unwrap_or(false);
+                Ok(ctx
+                    .graphics
+                    .create_surface_ext((w, h), fmt, samples, depth_stencil))
             },
         );
         add_methods(methods);
@@ -40,6 +61,13 @@ fn add_methods<T, M: UserDataMethods<T>>(methods: &mut M) {
     methods.add_function("width", |_, this: SurfaceRef| Ok(this.width()));
     methods.add_function("height", |_, this: SurfaceRef| Ok(this.height()));
     methods.add_function("format", |_, this: SurfaceRef| Ok(this.format()));
+    methods.add_function(
+        "sample_count",
+        |_, this: SurfaceRef| Ok(this.sample_count()),
+    );
+    methods.add_function("has_depth_stencil", |_, this: SurfaceRef| {
+        Ok(this.has_depth_stencil())
+    });
     methods.add_function("texture", |_, this: SurfaceRef| Ok(this.texture().clone()));
 }
 
@@ -2,6 +2,7 @@ use crate::core::Context;
 use crate::gfx::{Texture, TextureRef};
 use crate::img::DynImageRef;
 use crate::lua::LuaModule;
+use crate::math::RectU;
 use mlua::prelude::LuaResult;
 use mlua::{FromLua, Lua, UserData, UserDataMethods, UserDataRef, Value};
 
@@ -36,22 +37,17 @@ impl UserData for Texture {
 }
 
 fn add_methods<T, M: UserDataMethods<T>>(methods: &mut M) {
-    // methods.add_function(
-    //     "set_pixels",
-    //     |lua, (tex, img): (TextureRef, DynImageRef)| {
-    //         if tex.format().image_format() != img.format() {
-    //             return Err(LuaError::runtime(format!(
-    //                 "cannot upload [{}] image to [{}] texture",
-    //                 img.format().lua_str(),
-    //                 tex.format().lua_str()
-    //             )));
-    //         }
-    //         let gfx = lua.app_data_ref::<Graphics>().unwrap();
-    //         tex.upload(img.bytes(), &gfx.queue);
-    //         Ok(())
-    //     },
-    // );
-
+    methods.add_function("upload", |_, (tex, img): (TextureRef, DynImageRef)| {
+        tex.upload_dyn_img(&img)?;
+        Ok(())
+    });
+    methods.add_function(
+        "upload_region",
+        |_, (tex, rect, img): (TextureRef, RectU, DynImageRef)| {
+            tex.upload_dyn_img_region(rect, &img)?;
+            Ok(())
+        },
+    );
     methods.add_function("size", |_, tex: TextureRef| Ok(tex.size()));
     methods.add_function("width", |_, tex: TextureRef| Ok(tex.width()));
     methods.add_function("height", |_, tex: TextureRef| Ok(tex.height()));
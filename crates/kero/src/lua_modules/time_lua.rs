@@ -1,7 +1,7 @@
-use crate::core::Context;
+use crate::core::{Context, Timestep};
 use crate::lua::LuaModule;
-use mlua::prelude::LuaResult;
-use mlua::{Lua, Value};
+use mlua::prelude::{LuaError, LuaResult};
+use mlua::{BorrowedStr, Lua, Value};
 
 pub struct TimeModule;
 
@@ -24,6 +24,46 @@ impl LuaModule for TimeModule {
                 Ok(ctx.time.delta())
             })?,
         )?;
+        m.set(
+            "alpha",
+            lua.create_function(|lua, _: ()| {
+                let ctx = Context::from_lua(lua);
+                Ok(ctx.time.alpha())
+            })?,
+        )?;
+        m.set(
+            "timestep",
+            lua.create_function(|lua, _: ()| {
+                let ctx = Context::from_lua(lua);
+                Ok(match ctx.time.timestep() {
+                    Timestep::Fixed => ("fixed", None),
+                    Timestep::Variable => ("variable", None),
+                    Timestep::SemiFixed { max_delta } => ("semi_fixed", Some(max_delta)),
+                })
+            })?,
+        )?;
+        m.set(
+            "set_timestep",
+            lua.create_function(|lua, (mode, max_delta): (BorrowedStr, Option<f32>)| {
+                let ctx = Context::from_lua(lua);
+                ctx.time.set_timestep(match (mode.as_ref(), max_delta) {
+                    ("fixed", _) => Timestep::Fixed,
+                    ("variable", _) => Timestep::Variable,
+                    ("semi_fixed", Some(max_delta)) => {
+                        Timestep::semi_fixed(max_delta).ok_or_else(|| {
+                            LuaError::runtime(format!("invalid max_delta [{max_delta}]"))
+                        })?
+                    }
+                    ("semi_fixed", None) => {
+                        return Err(LuaError::runtime(
+                            "semi_fixed timestep requires a max_delta",
+                        ));
+                    }
+                    (s, _) => return Err(LuaError::runtime(format!("invalid timestep [{s}]"))),
+                });
+                Ok(())
+            })?,
+        )?;
         m.set(
             "since_startup",
             lua.create_function(|lua, _: ()| {
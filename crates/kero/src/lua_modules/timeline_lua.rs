@@ -0,0 +1,248 @@
+use crate::core::{Context, GameError};
+use crate::lua::LuaModule;
+use crate::math::{Interp, Vec2F};
+use crate::tween::{Action, ActionId, Ease, Tween};
+use mlua::prelude::{LuaError, LuaResult};
+use mlua::{
+    Either, Function, IntoLua, Lua, Table, Thread, ThreadStatus, UserData, UserDataRef, Value,
+};
+
+pub struct TimelineModule;
+
+impl LuaModule for TimelineModule {
+    const PATH: &'static str = "Timeline";
+
+    fn load(lua: &Lua) -> LuaResult<Value> {
+        let m = lua.create_table()?;
+        m.set(
+            "run",
+            lua.create_function(|lua, f: Function| {
+                let ctx = Context::from_lua(lua);
+                Ok(ctx.timeline.run(LuaCoroutine {
+                    thread: lua.create_thread(f)?,
+                    waiting: Waiting::None,
+                }))
+            })?,
+        )?;
+        // waiting is just yielding from the coroutine, which is resumed by its action
+        m.set(
+            "wait",
+            lua.globals()
+                .get::<Table>("coroutine")?
+                .get::<Function>("yield")?,
+        )?;
+        m.set(
+            "after",
+            lua.create_function(|lua, (seconds, f): (f32, Function)| {
+                let ctx = Context::from_lua(lua);
+                Ok(ctx.timeline.after(seconds, move |_| Ok(f.call::<()>(())?)))
+            })?,
+        )?;
+        m.set(
+            "every",
+            lua.create_function(|lua, (interval, f): (f32, Function)| {
+                let ctx = Context::from_lua(lua);
+                Ok(ctx.timeline.every(interval, move |_| Ok(f.call::<()>(())?)))
+            })?,
+        )?;
+        m.set(
+            "tween",
+            lua.create_function(
+                |lua,
+                 (from, to, duration, apply, opts): (
+                    Either<f32, Vec2F>,
+                    Either<f32, Vec2F>,
+                    f32,
+                    Function,
+                    Option<Table>,
+                )| {
+                    let ctx = Context::from_lua(lua);
+                    Ok(match (from, to) {
+                        (Either::Left(from), Either::Left(to)) => ctx.timeline.run(LuaTween {
+                            tween: tween_opts(Tween::new(from, to, duration), opts)?,
+                            apply,
+                        }),
+                        (Either::Right(from), Either::Right(to)) => ctx.timeline.run(LuaTween {
+                            tween: tween_opts(Tween::new(from, to, duration), opts)?,
+                            apply,
+                        }),
+                        _ => {
+                            return Err(LuaError::runtime(
+                                "tween values must both be numbers or both be Vec2",
+                            ));
+                        }
+                    })
+                },
+            )?,
+        )?;
+        m.set(
+            "cancel",
+            lua.create_function(|lua, id: UserDataRef<ActionId>| {
+                let ctx = Context::from_lua(lua);
+                ctx.timeline.cancel(*id);
+                Ok(())
+            })?,
+        )?;
+        m.set(
+            "is_running",
+            lua.create_function(|lua, id: UserDataRef<ActionId>| {
+                let ctx = Context::from_lua(lua);
+                Ok(ctx.timeline.is_running(*id))
+            })?,
+        )?;
+        m.set(
+            "clear",
+            lua.create_function(|lua, _: ()| {
+                let ctx = Context::from_lua(lua);
+                ctx.timeline.clear();
+                Ok(())
+            })?,
+        )?;
+        m.set(
+            "len",
+            lua.create_function(|lua, _: ()| {
+                let ctx = Context::from_lua(lua);
+                Ok(ctx.timeline.len())
+            })?,
+        )?;
+        m.set(
+            "is_paused",
+            lua.create_function(|lua, _: ()| {
+                let ctx = Context::from_lua(lua);
+                Ok(ctx.timeline.is_paused())
+            })?,
+        )?;
+        m.set(
+            "pause",
+            lua.create_function(|lua, _: ()| {
+                let ctx = Context::from_lua(lua);
+                ctx.timeline.pause();
+                Ok(())
+            })?,
+        )?;
+        m.set(
+            "resume",
+            lua.create_function(|lua, _: ()| {
+                let ctx = Context::from_lua(lua);
+                ctx.timeline.resume();
+                Ok(())
+            })?,
+        )?;
+        m.set(
+            "time_scale",
+            lua.create_function(|lua, _: ()| {
+                let ctx = Context::from_lua(lua);
+                Ok(ctx.timeline.time_scale())
+            })?,
+        )?;
+        m.set(
+            "set_time_scale",
+            lua.create_function(|lua, scale: f32| {
+                let ctx = Context::from_lua(lua);
+                ctx.timeline.set_time_scale(scale);
+                Ok(())
+            })?,
+        )?;
+        m.set(
+            "ease",
+            lua.create_function(|_, (ease, t): (Ease, f32)| Ok(ease.apply(t)))?,
+        )?;
+        Ok(Value::Table(m))
+    }
+}
+
+impl UserData for ActionId {}
+
+fn tween_opts<T: Interp<Factor = f32> + Copy>(
+    mut tween: Tween<T>,
+    opts: Option<Table>,
+) -> LuaResult<Tween<T>> {
+    let Some(opts) = opts else {
+        return Ok(tween);
+    };
+    if let Some(ease) = opts.get::<Option<Ease>>("ease")? {
+        tween = tween.with_ease(ease);
+    }
+    if let Some(delay) = opts.get::<Option<f32>>("delay")? {
+        tween = tween.with_delay(delay);
+    }
+    if let Some(repeat) = opts.get::<Option<i64>>("repeat")? {
+        tween = match u32::try_from(repeat) {
+            Ok(times) => tween.with_repeat(times),
+            Err(_) => tween.with_repeat_forever(),
+        };
+    }
+    if let Some(yoyo) = opts.get::<Option<bool>>("yoyo")? {
+        tween = tween.with_yoyo(yoyo);
+    }
+    Ok(tween)
+}
+
+/// A tween that passes its value to a Lua function.
+struct LuaTween<T> {
+    tween: Tween<T>,
+    apply: Function,
+}
+
+impl<T> Action for LuaTween<T>
+where
+    T: Interp<Factor = f32> + Copy + IntoLua + 'static,
+{
+    fn update(&mut self, _ctx: &Context, delta: f32) -> Result<Option<f32>, GameError> {
+        self.apply.call::<()>(self.tween.update(delta))?;
+        Ok(self.tween.is_finished().then(|| self.tween.leftover()))
+    }
+}
+
+/// What a coroutine yielded to wait for.
+enum Waiting {
+    None,
+    Time(f32),
+    Action(ActionId),
+}
+
+/// A Lua coroutine, which is resumed whenever what it's waiting for has finished.
+struct LuaCoroutine {
+    thread: Thread,
+    waiting: Waiting,
+}
+
+impl Action for LuaCoroutine {
+    fn update(&mut self, ctx: &Context, mut delta: f32) -> Result<Option<f32>, GameError> {
+        loop {
+            match self.waiting {
+                Waiting::None => {}
+                Waiting::Time(left) if left > delta => {
+                    self.waiting = Waiting::Time(left - delta);
+                    return Ok(None);
+                }
+                Waiting::Time(left) => delta -= left,
+                Waiting::Action(id) if ctx.timeline.is_running(id) => return Ok(None),
+                Waiting::Action(_) => {}
+            }
+
+            let yielded = self.thread.resume::<Value>(())?;
+            if self.thread.status() != ThreadStatus::Resumable {
+                return Ok(Some(delta));
+            }
+
+            // waiting for nothing or no time waits until the next update
+            self.waiting = match yielded {
+                Value::Nil => Waiting::None,
+                Value::Integer(seconds) if seconds > 0 => Waiting::Time(seconds as f32),
+                Value::Number(seconds) if seconds > 0.0 => Waiting::Time(seconds as f32),
+                Value::Integer(_) | Value::Number(_) => Waiting::None,
+                Value::UserData(ud) => Waiting::Action(*ud.borrow::<ActionId>()?),
+                _ => {
+                    return Err(LuaError::runtime(
+                        "can only wait for a number of seconds or a running action",
+                    )
+                    .into());
+                }
+            };
+            if let Waiting::None = self.waiting {
+                return Ok(None);
+            }
+        }
+    }
+}
@@ -0,0 +1,96 @@
+use crate::core::Context;
+use crate::input::{Touch, TouchPhase};
+use crate::lua::LuaModule;
+use mlua::prelude::LuaResult;
+use mlua::{IntoLua, Lua, Result, Value};
+
+pub struct TouchesModule;
+
+impl LuaModule for TouchesModule {
+    const PATH: &'static str = "Touches";
+
+    fn load(lua: &Lua) -> LuaResult<Value> {
+        let m = lua.create_table()?;
+
+        m.set(
+            "all",
+            lua.create_function(|lua, _: ()| Ok(Context::from_lua(lua).touches.all()))?,
+        )?;
+        m.set(
+            "get",
+            lua.create_function(|lua, id: u64| Ok(Context::from_lua(lua).touches.get(id)))?,
+        )?;
+        m.set(
+            "count",
+            lua.create_function(|lua, _: ()| Ok(Context::from_lua(lua).touches.count()))?,
+        )?;
+        m.set(
+            "any_down",
+            lua.create_function(|lua, _: ()| Ok(Context::from_lua(lua).touches.any_down()))?,
+        )?;
+        m.set(
+            "down",
+            lua.create_function(|lua, id: u64| Ok(Context::from_lua(lua).touches.down(id)))?,
+        )?;
+        m.set(
+            "pressed",
+            lua.create_function(|lua, _: ()| Ok(Context::from_lua(lua).touches.pressed()))?,
+        )?;
+        m.set(
+            "released",
+            lua.create_function(|lua, _: ()| Ok(Context::from_lua(lua).touches.released()))?,
+        )?;
+        m.set(
+            "pinch_delta",
+            lua.create_function(|lua, _: ()| Ok(Context::from_lua(lua).touches.pinch_delta()))?,
+        )?;
+        m.set(
+            "pan_delta",
+            lua.create_function(|lua, _: ()| Ok(Context::from_lua(lua).touches.pan_delta()))?,
+        )?;
+        m.set(
+            "rotation_delta",
+            lua.create_function(|lua, _: ()| Ok(Context::from_lua(lua).touches.rotation_delta()))?,
+        )?;
+        m.set(
+            "double_tapped",
+            lua.create_function(|lua, _: ()| Ok(Context::from_lua(lua).touches.double_tapped()))?,
+        )?;
+        m.set(
+            "emulate_mouse",
+            lua.create_function(|lua, _: ()| Ok(Context::from_lua(lua).touches.emulate_mouse()))?,
+        )?;
+        m.set(
+            "set_emulate_mouse",
+            lua.create_function(|lua, emulate: bool| {
+                Context::from_lua(lua).touches.set_emulate_mouse(emulate);
+                Ok(())
+            })?,
+        )?;
+
+        Ok(Value::Table(m))
+    }
+}
+
+impl IntoLua for Touch {
+    fn into_lua(self, lua: &Lua) -> Result<Value> {
+        let t = lua.create_table()?;
+        t.set("id", self.id)?;
+        t.set("pos", self.pos)?;
+        t.set("pressure", self.pressure)?;
+        t.set("phase", self.phase)?;
+        Ok(Value::Table(t))
+    }
+}
+
+impl IntoLua for TouchPhase {
+    #[inline]
+    fn into_lua(self, lua: &Lua) -> Result<Value> {
+        match self {
+            Self::Pressed => "pressed",
+            Self::Held => "held",
+            Self::Released => "released",
+        }
+        .into_lua(lua)
+    }
+}
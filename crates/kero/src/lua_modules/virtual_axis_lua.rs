@@ -0,0 +1,125 @@
+use crate::core::Context;
+use crate::input::{
+    GamepadAxis, Key, ResponseCurve, VirtualAxis, VirtualAxisRef, VirtualButton, VirtualSource,
+};
+use crate::lua::LuaModule;
+use crate::lua_modules::GamepadRef;
+use mlua::prelude::{LuaError, LuaResult};
+use mlua::{BorrowedStr, FromLua, IntoLua, Lua, UserData, UserDataMethods, UserDataRef, Value};
+
+pub struct VirtualAxisModule;
+
+impl LuaModule for VirtualAxisModule {
+    const PATH: &'static str = "VirtualAxis";
+
+    fn load(lua: &Lua) -> LuaResult<Value> {
+        lua.create_userdata(Self).map(Value::UserData)
+    }
+}
+
+impl UserData for VirtualAxisModule {
+    fn add_methods<M: UserDataMethods<Self>>(methods: &mut M) {
+        methods.add_function(
+            "new",
+            |lua,
+             (axis, neg, pos, pad): (
+                Option<GamepadAxis>,
+                Option<Key>,
+                Option<Key>,
+                Option<GamepadRef>,
+            )| {
+                let ctx = Context::from_lua(lua);
+                let source = match pad {
+                    Some(pad) => VirtualSource::specific(&ctx, pad.clone()),
+                    None => VirtualSource::last_active(&ctx),
+                };
+                let neg = VirtualButton::new(&source, neg, None);
+                let pos = VirtualButton::new(&source, pos, None);
+                Ok(VirtualAxis::new(&source, axis, neg, pos))
+            },
+        );
+        add_methods(methods);
+    }
+}
+
+impl UserData for VirtualAxis {
+    fn add_methods<M: UserDataMethods<Self>>(methods: &mut M) {
+        add_methods(methods);
+    }
+}
+
+fn add_methods<T, M: UserDataMethods<T>>(methods: &mut M) {
+    methods.add_function("axis", |_, this: VirtualAxisRef| Ok(this.axis()));
+    methods.add_function("axes", |_, this: VirtualAxisRef| Ok(this.axes()));
+    methods.add_function(
+        "set_axis",
+        |_, (this, axis): (VirtualAxisRef, Option<GamepadAxis>)| {
+            this.set_axis(axis);
+            Ok(())
+        },
+    );
+    methods.add_function(
+        "add_axis",
+        |_, (this, axis): (VirtualAxisRef, GamepadAxis)| {
+            this.add_axis(axis);
+            Ok(())
+        },
+    );
+    methods.add_function("changed", |_, this: VirtualAxisRef| Ok(this.changed()));
+    methods.add_function("value", |_, this: VirtualAxisRef| Ok(this.value()));
+    methods.add_function("deadzone", |_, this: VirtualAxisRef| {
+        let dz = this.deadzone();
+        Ok((dz.inner, dz.outer))
+    });
+    methods.add_function(
+        "set_deadzone",
+        |_, (this, inner, outer): (VirtualAxisRef, f32, Option<f32>)| {
+            let mut dz = this.deadzone();
+            dz.inner = inner;
+            dz.outer = outer.unwrap_or(1.0);
+            this.set_deadzone(dz);
+            Ok(())
+        },
+    );
+    methods.add_function("curve", |_, this: VirtualAxisRef| Ok(this.curve()));
+    methods.add_function(
+        "set_curve",
+        |_, (this, curve): (VirtualAxisRef, ResponseCurve)| {
+            this.set_curve(curve);
+            Ok(())
+        },
+    );
+}
+
+impl FromLua for VirtualAxis {
+    #[inline]
+    fn from_lua(value: Value, lua: &Lua) -> LuaResult<Self> {
+        UserDataRef::<Self>::from_lua(value, lua).map(|h| h.clone())
+    }
+}
+
+impl FromLua for ResponseCurve {
+    fn from_lua(value: Value, lua: &Lua) -> LuaResult<Self> {
+        if let Value::Function(f) = value {
+            return Ok(Self::custom(move |t| f.call::<f32>(t).unwrap_or(t)));
+        }
+        let s = BorrowedStr::from_lua(value, lua)?;
+        Ok(match s.as_ref() {
+            "linear" => Self::Linear,
+            "quadratic" => Self::Quadratic,
+            s => return Err(LuaError::runtime(format!("invalid response curve {s:?}"))),
+        })
+    }
+}
+
+impl IntoLua for ResponseCurve {
+    fn into_lua(self, lua: &Lua) -> LuaResult<Value> {
+        match self {
+            Self::Linear => "linear".into_lua(lua),
+            Self::Quadratic => "quadratic".into_lua(lua),
+            Self::Custom(f) => lua
+                .create_function(move |_, t: f32| Ok(f(t)))
+                .map(Value::Function),
+        }
+    }
+}
@@ -0,0 +1,183 @@
+use crate::core::Context;
+use crate::input::{
+    Deadzone, DeadzoneShape, GamepadAxis, Key, ResponseCurve, StickSnap, VirtualAxis,
+    VirtualButton, VirtualSource, VirtualStick, VirtualStickRef,
+};
+use crate::lua::LuaModule;
+use crate::lua_modules::GamepadRef;
+use mlua::prelude::{LuaError, LuaResult};
+use mlua::{BorrowedStr, FromLua, IntoLua, Lua, UserData, UserDataMethods, UserDataRef, Value};
+
+pub struct VirtualStickModule;
+
+impl LuaModule for VirtualStickModule {
+    const PATH: &'static str = "VirtualStick";
+
+    fn load(lua: &Lua) -> LuaResult<Value> {
+        lua.create_userdata(Self).map(Value::UserData)
+    }
+}
+
+impl UserData for VirtualStickModule {
+    fn add_methods<M: UserDataMethods<Self>>(methods: &mut M) {
+        methods.add_function(
+            "new",
+            |lua, (x_axis, y_axis): (Option<VirtualAxis>, Option<VirtualAxis>)| {
+                let ctx = Context::from_lua(lua);
+                Ok(VirtualStick::new(
+                    &VirtualSource::last_active(&ctx),
+                    x_axis,
+                    y_axis,
+                ))
+            },
+        );
+        methods.add_function("left", |lua, pad: Option<GamepadRef>| {
+            let ctx = Context::from_lua(lua);
+            let source = match pad {
+                Some(pad) => VirtualSource::specific(&ctx, pad.clone()),
+                None => VirtualSource::last_active(&ctx),
+            };
+            Ok(stick(
+                &source,
+                [GamepadAxis::LeftX, GamepadAxis::LeftY],
+                Some([
+                    Key::ArrowLeft,
+                    Key::ArrowRight,
+                    Key::ArrowUp,
+                    Key::ArrowDown,
+                ]),
+            ))
+        });
+        methods.add_function("right", |lua, pad: Option<GamepadRef>| {
+            let ctx = Context::from_lua(lua);
+            let source = match pad {
+                Some(pad) => VirtualSource::specific(&ctx, pad.clone()),
+                None => VirtualSource::last_active(&ctx),
+            };
+            Ok(stick(
+                &source,
+                [GamepadAxis::RightX, GamepadAxis::RightY],
+                None,
+            ))
+        });
+        add_methods(methods);
+    }
+}
+
+fn stick(source: &VirtualSource, [x, y]: [GamepadAxis; 2], keys: Option<[Key; 4]>) -> VirtualStick {
+    let [left, right, up, down] = keys
+        .map(|keys| keys.map(Some))
+        .unwrap_or_default()
+        .map(|key| VirtualButton::new(source, key, None));
+    VirtualStick::new(
+        source,
+        VirtualAxis::new(source, x, left, right),
+        VirtualAxis::new(source, y, up, down),
+    )
+}
+
+impl UserData for VirtualStick {
+    fn add_methods<M: UserDataMethods<Self>>(methods: &mut M) {
+        add_methods(methods);
+    }
+}
+
+fn add_methods<T, M: UserDataMethods<T>>(methods: &mut M) {
+    methods.add_function("x_axis", |_, this: VirtualStickRef| Ok(this.x_axis()));
+    methods.add_function("y_axis", |_, this: VirtualStickRef| Ok(this.y_axis()));
+    methods.add_function(
+        "set_axes",
+        |_, (this, x_axis, y_axis): (VirtualStickRef, VirtualAxis, VirtualAxis)| {
+            this.set_axes(x_axis, y_axis);
+            Ok(())
+        },
+    );
+    methods.add_function("changed", |_, this: VirtualStickRef| Ok(this.changed()));
+    methods.add_function("x", |_, this: VirtualStickRef| Ok(this.x()));
+    methods.add_function("y", |_, this: VirtualStickRef| Ok(this.y()));
+    methods.add_function("value", |_, this: VirtualStickRef| Ok(this.value()));
+    methods.add_function("deadzone", |_, this: VirtualStickRef| {
+        let dz = this.deadzone();
+        Ok((dz.shape, dz.inner, dz.outer))
+    });
+    methods.add_function(
+        "set_deadzone",
+        |_, (this, shape, inner, outer): (VirtualStickRef, DeadzoneShape, f32, Option<f32>)| {
+            this.set_deadzone(Deadzone::new(shape, inner, outer.unwrap_or(1.0)));
+            Ok(())
+        },
+    );
+    methods.add_function("curve", |_, this: VirtualStickRef| Ok(this.curve()));
+    methods.add_function(
+        "set_curve",
+        |_, (this, curve): (VirtualStickRef, ResponseCurve)| {
+            this.set_curve(curve);
+            Ok(())
+        },
+    );
+    methods.add_function("snap", |_, this: VirtualStickRef| Ok(this.snap()));
+    methods.add_function(
+        "set_snap",
+        |_, (this, snap): (VirtualStickRef, StickSnap)| {
+            this.set_snap(snap);
+            Ok(())
+        },
+    );
+}
+
+impl FromLua for VirtualStick {
+    #[inline]
+    fn from_lua(value: Value, lua: &Lua) -> LuaResult<Self> {
+        UserDataRef::<Self>::from_lua(value, lua).map(|h| h.clone())
+    }
+}
+
+impl FromLua for DeadzoneShape {
+    #[inline]
+    fn from_lua(value: Value, lua: &Lua) -> LuaResult<Self> {
+        let s = BorrowedStr::from_lua(value, lua)?;
+        Ok(match s.as_ref() {
+            "axial" => Self::Axial,
+            "radial" => Self::Radial,
+            "scaled_radial" => Self::ScaledRadial,
+            s => return Err(LuaError::runtime(format!("invalid deadzone shape {s:?}"))),
+        })
+    }
+}
+
+impl IntoLua for DeadzoneShape {
+    #[inline]
+    fn into_lua(self, lua: &Lua) -> LuaResult<Value> {
+        match self {
+            Self::Axial => "axial",
+            Self::Radial => "radial",
+            Self::ScaledRadial => "scaled_radial",
+        }
+        .into_lua(lua)
+    }
+}
+
+impl FromLua for StickSnap {
+    #[inline]
+    fn from_lua(value: Value, lua: &Lua) -> LuaResult<Self> {
+        let s = BorrowedStr::from_lua(value, lua)?;
+        Ok(match s.as_ref() {
+            "none" => Self::None,
+            "cardinal" => Self::Cardinal,
+            "octal" => Self::Octal,
+            s => return Err(LuaError::runtime(format!("invalid stick snap {s:?}"))),
+        })
+    }
+}
+
+impl IntoLua for StickSnap {
+    #[inline]
+    fn into_lua(self, lua: &Lua) -> LuaResult<Value> {
+        match self {
+            Self::None => "none",
+            Self::Cardinal => "cardinal",
+            Self::Octal => "octal",
+        }
+        .into_lua(lua)
+    }
+}
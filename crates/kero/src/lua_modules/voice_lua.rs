@@ -0,0 +1,50 @@
+use crate::audio::{Voice, VoiceRef};
+use mlua::prelude::LuaResult;
+use mlua::{FromLua, Lua, UserData, UserDataMethods, UserDataRef, Value};
+
+impl UserData for Voice {
+    fn add_methods<M: UserDataMethods<Self>>(methods: &mut M) {
+        methods.add_function("is_playing", |_, this: VoiceRef| Ok(this.is_playing()));
+        methods.add_function("bus", |_, this: VoiceRef| Ok(this.bus()));
+        methods.add_function("volume", |_, this: VoiceRef| Ok(this.volume()));
+        methods.add_function("set_volume", |_, (this, volume): (VoiceRef, f32)| {
+            this.set_volume(volume);
+            Ok(())
+        });
+        methods.add_function("pan", |_, this: VoiceRef| Ok(this.pan()));
+        methods.add_function("set_pan", |_, (this, pan): (VoiceRef, f32)| {
+            this.set_pan(pan);
+            Ok(())
+        });
+        methods.add_function("pitch", |_, this: VoiceRef| Ok(this.pitch()));
+        methods.add_function("set_pitch", |_, (this, pitch): (VoiceRef, f32)| {
+            this.set_pitch(pitch);
+            Ok(())
+        });
+        methods.add_function("looping", |_, this: VoiceRef| Ok(this.looping()));
+        methods.add_function("set_looping", |_, (this, looping): (VoiceRef, bool)| {
+            this.set_looping(looping);
+            Ok(())
+        });
+        methods.add_function("paused", |_, this: VoiceRef| Ok(this.paused()));
+        methods.add_function("pause", |_, this: VoiceRef| {
+            this.pause();
+            Ok(())
+        });
+        methods.add_function("resume", |_, this: VoiceRef| {
+            this.resume();
+            Ok(())
+        });
+        methods.add_function("stop", |_, this: VoiceRef| {
+            this.stop();
+            Ok(())
+        });
+    }
+}
+
+impl FromLua for Voice {
+    #[inline]
+    fn from_lua(value: Value, lua: &Lua) -> LuaResult<Self> {
+        UserDataRef::<Self>::from_lua(value, lua).map(|h| h.clone())
+    }
+}
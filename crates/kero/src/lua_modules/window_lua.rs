@@ -1,8 +1,9 @@
 use super::VideoModeRef;
-use crate::core::{Context, CursorIcon, DisplayMode, MonitorRef};
+use crate::core::{Context, CursorGrab, CursorIcon, DisplayMode, MonitorRef};
+use crate::img::DynImageRef;
 use crate::lua::LuaModule;
 use crate::math::Numeric;
-use fey_math::Vec2;
+use fey_math::{RectF, Vec2};
 use mlua::prelude::LuaResult;
 use mlua::{BorrowedStr, Lua, Value};
 
@@ -245,6 +246,55 @@ impl LuaModule for WindowModule {
                 Ok(())
             })?,
         )?;
+        m.set(
+            "set_custom_cursor",
+            lua.create_function(|lua, (img, x, y): (DynImageRef, u32, u32)| {
+                let ctx = Context::from_lua(lua);
+                ctx.window
+                    .set_custom_cursor(&(*img).clone().to_rgba8(), (x, y))?;
+                Ok(())
+            })?,
+        )?;
+        m.set(
+            "set_cursor_visible",
+            lua.create_function(|lua, visible: bool| {
+                let ctx = Context::from_lua(lua);
+                ctx.window.set_cursor_visible(visible);
+                Ok(())
+            })?,
+        )?;
+        m.set(
+            "set_cursor_grab",
+            lua.create_function(|lua, grab: CursorGrab| {
+                let ctx = Context::from_lua(lua);
+                ctx.window.set_cursor_grab(grab)?;
+                Ok(())
+            })?,
+        )?;
+        m.set(
+            "set_icon",
+            lua.create_function(|lua, img: DynImageRef| {
+                let ctx = Context::from_lua(lua);
+                ctx.window.set_icon(&(*img).clone().to_rgba8())?;
+                Ok(())
+            })?,
+        )?;
+        m.set(
+            "set_ime_allowed",
+            lua.create_function(|lua, allowed: bool| {
+                let ctx = Context::from_lua(lua);
+                ctx.window.set_ime_allowed(allowed);
+                Ok(())
+            })?,
+        )?;
+        m.set(
+            "set_ime_cursor_area",
+            lua.create_function(|lua, area: RectF| {
+                let ctx = Context::from_lua(lua);
+                ctx.window.set_ime_cursor_area(area);
+                Ok(())
+            })?,
+        )?;
         Ok(Value::Table(m))
     }
 }
@@ -0,0 +1,209 @@
+use crate::core::{Context, GameError};
+use std::collections::VecDeque;
+use std::fmt::{Debug, Formatter};
+
+/// Something that happens over time when run on a [`Timeline`](super::Timeline), such as a
+/// [`Wait`], a [`Tween`](super::Tween), or a [`Sequence`] of other actions.
+pub trait Action: 'static {
+    /// Advance the action by `delta` seconds. If the action finished, returns the time left
+    /// over from `delta`, which a [`Sequence`] passes on to the next action. Otherwise returns
+    /// `None`.
+    fn update(&mut self, ctx: &Context, delta: f32) -> Result<Option<f32>, GameError>;
+}
+
+impl Action for Box<dyn Action> {
+    #[inline]
+    fn update(&mut self, ctx: &Context, delta: f32) -> Result<Option<f32>, GameError> {
+        self.as_mut().update(ctx, delta)
+    }
+}
+
+/// An action that waits for a duration, in seconds.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Wait(pub f32);
+
+impl Action for Wait {
+    #[inline]
+    fn update(&mut self, _ctx: &Context, delta: f32) -> Result<Option<f32>, GameError> {
+        self.0 -= delta;
+        Ok((self.0 <= 0.0).then_some(-self.0))
+    }
+}
+
+/// An action that calls a function once and finishes immediately.
+pub struct Call<F>(Option<F>);
+
+impl<F> Call<F>
+where
+    F: FnOnce(&Context) -> Result<(), GameError> + 'static,
+{
+    #[inline]
+    pub fn new(f: F) -> Self {
+        Self(Some(f))
+    }
+}
+
+impl<F> Debug for Call<F> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Call").finish_non_exhaustive()
+    }
+}
+
+impl<F> Action for Call<F>
+where
+    F: FnOnce(&Context) -> Result<(), GameError> + 'static,
+{
+    #[inline]
+    fn update(&mut self, ctx: &Context, delta: f32) -> Result<Option<f32>, GameError> {
+        if let Some(f) = self.0.take() {
+            f(ctx)?;
+        }
+        Ok(Some(delta))
+    }
+}
+
+/// An action that calls a function every time the interval passes, and never finishes.
+pub(crate) struct Every<F> {
+    pub interval: f32,
+    pub elapsed: f32,
+    pub f: F,
+}
+
+impl<F> Action for Every<F>
+where
+    F: FnMut(&Context) -> Result<(), GameError> + 'static,
+{
+    fn update(&mut self, ctx: &Context, delta: f32) -> Result<Option<f32>, GameError> {
+        if self.interval <= 0.0 {
+            (self.f)(ctx)?;
+            return Ok(None);
+        }
+        self.elapsed += delta;
+        while self.elapsed >= self.interval {
+            self.elapsed -= self.interval;
+            (self.f)(ctx)?;
+        }
+        Ok(None)
+    }
+}
+
+/// An action that runs other actions one after another, finishing after the last one does.
+#[derive(Default)]
+pub struct Sequence(VecDeque<Box<dyn Action>>);
+
+impl Debug for Sequence {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Sequence").field(&self.0.len()).finish()
+    }
+}
+
+impl Sequence {
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an action to the end of the sequence.
+    #[inline]
+    pub fn then(mut self, action: impl Action) -> Self {
+        self.0.push_back(Box::new(action));
+        self
+    }
+
+    /// Add a wait to the end of the sequence.
+    #[inline]
+    pub fn then_wait(self, seconds: f32) -> Self {
+        self.then(Wait(seconds))
+    }
+
+    /// Add a function call to the end of the sequence.
+    #[inline]
+    pub fn then_call<F>(self, f: F) -> Self
+    where
+        F: FnOnce(&Context) -> Result<(), GameError> + 'static,
+    {
+        self.then(Call::new(f))
+    }
+
+    /// How many actions are left in the sequence.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl Action for Sequence {
+    fn update(&mut self, ctx: &Context, mut delta: f32) -> Result<Option<f32>, GameError> {
+        while let Some(action) = self.0.front_mut() {
+            match action.update(ctx, delta)? {
+                Some(leftover) => {
+                    self.0.pop_front();
+                    delta = leftover;
+                }
+                None => return Ok(None),
+            }
+        }
+        Ok(Some(delta))
+    }
+}
+
+/// An action that runs other actions at the same time, finishing after all of them have.
+#[derive(Default)]
+pub struct Parallel(Vec<Box<dyn Action>>);
+
+impl Debug for Parallel {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Parallel").field(&self.0.len()).finish()
+    }
+}
+
+impl Parallel {
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an action to the group.
+    #[inline]
+    pub fn with(mut self, action: impl Action) -> Self {
+        self.0.push(Box::new(action));
+        self
+    }
+
+    /// How many actions in the group are still running.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl Action for Parallel {
+    fn update(&mut self, ctx: &Context, delta: f32) -> Result<Option<f32>, GameError> {
+        // the group finishes when its last action does, which has the least time left over
+        let mut leftover = delta;
+        let mut i = 0;
+        while i < self.0.len() {
+            match self.0[i].update(ctx, delta)? {
+                Some(left) => {
+                    self.0.remove(i);
+                    leftover = leftover.min(left);
+                }
+                None => i += 1,
+            }
+        }
+        Ok(self.0.is_empty().then_some(leftover))
+    }
+}
@@ -0,0 +1,262 @@
+use crate::math::Interp;
+use serde::{Deserialize, Serialize};
+use std::f32::consts::PI;
+use strum::{EnumCount, VariantArray};
+
+/// An easing curve, which remaps linear progress from `0` to `1` to give motion a different
+/// feel. See [easings.net](https://easings.net) for what each curve looks like.
+#[derive(
+    Default,
+    Debug,
+    Copy,
+    Clone,
+    Eq,
+    PartialEq,
+    Hash,
+    Serialize,
+    Deserialize,
+    EnumCount,
+    VariantArray,
+)]
+pub enum Ease {
+    #[default]
+    Linear,
+    SineIn,
+    SineOut,
+    SineInOut,
+    QuadIn,
+    QuadOut,
+    QuadInOut,
+    CubicIn,
+    CubicOut,
+    CubicInOut,
+    QuartIn,
+    QuartOut,
+    QuartInOut,
+    QuintIn,
+    QuintOut,
+    QuintInOut,
+    ExpoIn,
+    ExpoOut,
+    ExpoInOut,
+    CircIn,
+    CircOut,
+    CircInOut,
+    BackIn,
+    BackOut,
+    BackInOut,
+    ElasticIn,
+    ElasticOut,
+    ElasticInOut,
+    BounceIn,
+    BounceOut,
+    BounceInOut,
+}
+
+const BACK: f32 = 1.70158;
+const BACK_IN_OUT: f32 = BACK * 1.525;
+const ELASTIC: f32 = (2.0 * PI) / 3.0;
+const ELASTIC_IN_OUT: f32 = (2.0 * PI) / 4.5;
+
+impl Ease {
+    /// Apply the curve to the progress, which is clamped between `0` and `1`. Some curves, like
+    /// [`BackOut`](Ease::BackOut) and [`ElasticOut`](Ease::ElasticOut), overshoot outside of
+    /// that range before settling.
+    pub fn apply(self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Self::Linear => t,
+            Self::SineIn => 1.0 - (t * PI * 0.5).cos(),
+            Self::SineOut => (t * PI * 0.5).sin(),
+            Self::SineInOut => -((t * PI).cos() - 1.0) * 0.5,
+            Self::QuadIn => pow_in(t, 2),
+            Self::QuadOut => pow_out(t, 2),
+            Self::QuadInOut => pow_in_out(t, 2),
+            Self::CubicIn => pow_in(t, 3),
+            Self::CubicOut => pow_out(t, 3),
+            Self::CubicInOut => pow_in_out(t, 3),
+            Self::QuartIn => pow_in(t, 4),
+            Self::QuartOut => pow_out(t, 4),
+            Self::QuartInOut => pow_in_out(t, 4),
+            Self::QuintIn => pow_in(t, 5),
+            Self::QuintOut => pow_out(t, 5),
+            Self::QuintInOut => pow_in_out(t, 5),
+            Self::ExpoIn => match t {
+                0.0 => 0.0,
+                t => 2f32.powf(10.0 * t - 10.0),
+            },
+            Self::ExpoOut => match t {
+                1.0 => 1.0,
+                t => 1.0 - 2f32.powf(-10.0 * t),
+            },
+            Self::ExpoInOut => match t {
+                0.0 | 1.0 => t,
+                t if t < 0.5 => 2f32.powf(20.0 * t - 10.0) * 0.5,
+                t => (2.0 - 2f32.powf(-20.0 * t + 10.0)) * 0.5,
+            },
+            Self::CircIn => 1.0 - (1.0 - t * t).sqrt(),
+            Self::CircOut => (1.0 - (t - 1.0) * (t - 1.0)).sqrt(),
+            Self::CircInOut => match t < 0.5 {
+                true => (1.0 - (1.0 - 4.0 * t * t).sqrt()) * 0.5,
+                false => ((1.0 - (-2.0 * t + 2.0).powi(2)).sqrt() + 1.0) * 0.5,
+            },
+            Self::BackIn => (BACK + 1.0) * t * t * t - BACK * t * t,
+            Self::BackOut => 1.0 + (BACK + 1.0) * (t - 1.0).powi(3) + BACK * (t - 1.0).powi(2),
+            Self::BackInOut => match t < 0.5 {
+                true => (2.0 * t).powi(2) * ((BACK_IN_OUT + 1.0) * 2.0 * t - BACK_IN_OUT) * 0.5,
+                false => {
+                    ((2.0 * t - 2.0).powi(2)
+                        * ((BACK_IN_OUT + 1.0) * (t * 2.0 - 2.0) + BACK_IN_OUT)
+                        + 2.0)
+                        * 0.5
+                }
+            },
+            Self::ElasticIn => match t {
+                0.0 | 1.0 => t,
+                t => -(2f32.powf(10.0 * t - 10.0)) * ((t * 10.0 - 10.75) * ELASTIC).sin(),
+            },
+            Self::ElasticOut => match t {
+                0.0 | 1.0 => t,
+                t => 2f32.powf(-10.0 * t) * ((t * 10.0 - 0.75) * ELASTIC).sin() + 1.0,
+            },
+            Self::ElasticInOut => match t {
+                0.0 | 1.0 => t,
+                t if t < 0.5 => {
+                    -(2f32.powf(20.0 * t - 10.0) * ((20.0 * t - 11.125) * ELASTIC_IN_OUT).sin())
+                        * 0.5
+                }
+                t => {
+                    2f32.powf(-20.0 * t + 10.0) * ((20.0 * t - 11.125) * ELASTIC_IN_OUT).sin() * 0.5
+                        + 1.0
+                }
+            },
+            Self::BounceIn => 1.0 - bounce_out(1.0 - t),
+            Self::BounceOut => bounce_out(t),
+            Self::BounceInOut => match t < 0.5 {
+                true => (1.0 - bounce_out(1.0 - 2.0 * t)) * 0.5,
+                false => (1.0 + bounce_out(2.0 * t - 1.0)) * 0.5,
+            },
+        }
+    }
+
+    /// Interpolate between two values, with the progress remapped by the curve.
+    #[inline]
+    pub fn interp<T: Interp<Factor = f32>>(self, from: T, to: T, t: f32) -> T {
+        from.lerp(to, self.apply(t))
+    }
+}
+
+#[inline]
+fn pow_in(t: f32, n: i32) -> f32 {
+    t.powi(n)
+}
+
+#[inline]
+fn pow_out(t: f32, n: i32) -> f32 {
+    1.0 - (1.0 - t).powi(n)
+}
+
+#[inline]
+fn pow_in_out(t: f32, n: i32) -> f32 {
+    match t < 0.5 {
+        true => 2f32.powi(n - 1) * t.powi(n),
+        false => 1.0 - (-2.0 * t + 2.0).powi(n) * 0.5,
+    }
+}
+
+fn bounce_out(t: f32) -> f32 {
+    const N: f32 = 7.5625;
+    const D: f32 = 2.75;
+    if t < 1.0 / D {
+        N * t * t
+    } else if t < 2.0 / D {
+        let t = t - 1.5 / D;
+        N * t * t + 0.75
+    } else if t < 2.5 / D {
+        let t = t - 2.25 / D;
+        N * t * t + 0.9375
+    } else {
+        let t = t - 2.625 / D;
+        N * t * t + 0.984375
+    }
+}
+
+#[cfg(feature = "lua")]
+impl Ease {
+    #[inline]
+    pub fn lua_str(self) -> &'static str {
+        match self {
+            Self::Linear => "linear",
+            Self::SineIn => "sine_in",
+            Self::SineOut => "sine_out",
+            Self::SineInOut => "sine_in_out",
+            Self::QuadIn => "quad_in",
+            Self::QuadOut => "quad_out",
+            Self::QuadInOut => "quad_in_out",
+            Self::CubicIn => "cubic_in",
+            Self::CubicOut => "cubic_out",
+            Self::CubicInOut => "cubic_in_out",
+            Self::QuartIn => "quart_in",
+            Self::QuartOut => "quart_out",
+            Self::QuartInOut => "quart_in_out",
+            Self::QuintIn => "quint_in",
+            Self::QuintOut => "quint_out",
+            Self::QuintInOut => "quint_in_out",
+            Self::ExpoIn => "expo_in",
+            Self::ExpoOut => "expo_out",
+            Self::ExpoInOut => "expo_in_out",
+            Self::CircIn => "circ_in",
+            Self::CircOut => "circ_out",
+            Self::CircInOut => "circ_in_out",
+            Self::BackIn => "back_in",
+            Self::BackOut => "back_out",
+            Self::BackInOut => "back_in_out",
+            Self::ElasticIn => "elastic_in",
+            Self::ElasticOut => "elastic_out",
+            Self::ElasticInOut => "elastic_in_out",
+            Self::BounceIn => "bounce_in",
+            Self::BounceOut => "bounce_out",
+            Self::BounceInOut => "bounce_in_out",
+        }
+    }
+}
+
+#[cfg(feature = "lua")]
+impl mlua::FromLua for Ease {
+    #[inline]
+    fn from_lua(value: mlua::Value, lua: &mlua::Lua) -> mlua::Result<Self> {
+        let s = mlua::BorrowedStr::from_lua(value, lua)?;
+        Self::VARIANTS
+            .iter()
+            .copied()
+            .find(|ease| ease.lua_str() == s.as_ref())
+            .ok_or_else(|| mlua::Error::runtime(format!("invalid ease {:?}", s.as_ref())))
+    }
+}
+
+#[cfg(feature = "lua")]
+impl mlua::IntoLua for Ease {
+    #[inline]
+    fn into_lua(self, lua: &mlua::Lua) -> mlua::Result<mlua::Value> {
+        self.lua_str().into_lua(lua)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ease_endpoints() {
+        for &ease in Ease::VARIANTS {
+            assert!(ease.apply(0.0).abs() < 1e-4, "{ease:?} doesn't start at 0");
+            assert!(
+                (ease.apply(1.0) - 1.0).abs() < 1e-4,
+                "{ease:?} doesn't end at 1"
+            );
+        }
+        assert_eq!(Ease::QuadIn.apply(0.5), 0.25);
+        assert_eq!(Ease::QuadOut.apply(0.5), 0.75);
+        assert_eq!(Ease::Linear.interp(2.0f32, 4.0, 0.25), 2.5);
+    }
+}
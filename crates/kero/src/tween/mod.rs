@@ -0,0 +1,12 @@
+//! Tweens, timers, and sequencing actions over time.
+
+mod action;
+mod ease;
+mod timeline;
+#[allow(clippy::module_inception)]
+mod tween;
+
+pub use action::*;
+pub use ease::*;
+pub use timeline::*;
+pub use tween::*;
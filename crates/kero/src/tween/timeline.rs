@@ -0,0 +1,294 @@
+use crate::core::{Context, GameError};
+use crate::math::Interp;
+use crate::tween::{Action, Every, Sequence, Tween};
+use std::cell::{Cell, RefCell};
+use std::collections::HashSet;
+use std::fmt::{Debug, Formatter};
+use std::rc::Rc;
+
+/// Handle to an action running on a [`Timeline`].
+///
+/// Returned by [`Timeline::run`], and can be used to cancel the action before it finishes.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct ActionId(pub(crate) u64);
+
+/// Handle to a timeline, which runs [actions](Action) such as timers and tweens.
+///
+/// The context has a timeline that is advanced by [`Time`](crate::core::Time) right before
+/// every update, but more can be created and updated manually. For example, gameplay could run
+/// on its own timeline that is paused along with the game, while menus use the context's.
+///
+/// This handle can be cloned and passed around freely to give objects access to the timeline.
+///
+/// ```
+/// # use kero::prelude::*;
+/// # fn example(ctx: &Context) {
+/// let alpha = std::rc::Rc::new(std::cell::Cell::new(0.0f32));
+/// let fade = alpha.clone();
+/// ctx.timeline.run(
+///     Sequence::new()
+///         .then_wait(1.0)
+///         .then(Tween::new(0.0, 1.0, 0.5).with_ease(Ease::QuadOut).action(move |a| fade.set(a)))
+///         .then_call(|_ctx| {
+///             println!("faded in!");
+///             Ok(())
+///         }),
+/// );
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct Timeline(Rc<TimelineInner>);
+
+struct TimelineInner {
+    next_id: Cell<u64>,
+    running: RefCell<HashSet<ActionId>>,
+    actions: RefCell<Vec<(ActionId, Box<dyn Action>)>>,
+    added: RefCell<Vec<(ActionId, Box<dyn Action>)>>,
+    paused: Cell<bool>,
+    time_scale: Cell<f32>,
+}
+
+impl Debug for Timeline {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Timeline").finish_non_exhaustive()
+    }
+}
+
+impl PartialEq for Timeline {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Default for Timeline {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Timeline {
+    /// Create a new timeline.
+    pub fn new() -> Self {
+        Self(Rc::new(TimelineInner {
+            next_id: Cell::new(0),
+            running: RefCell::new(HashSet::new()),
+            actions: RefCell::new(Vec::new()),
+            added: RefCell::new(Vec::new()),
+            paused: Cell::new(false),
+            time_scale: Cell::new(1.0),
+        }))
+    }
+
+    /// Run the action, starting on the next update.
+    pub fn run(&self, action: impl Action) -> ActionId {
+        let id = ActionId(self.0.next_id.get());
+        self.0.next_id.set(id.0 + 1);
+        self.0.running.borrow_mut().insert(id);
+        self.0.added.borrow_mut().push((id, Box::new(action)));
+        id
+    }
+
+    /// Call the function once after `seconds` have passed.
+    #[inline]
+    pub fn after<F>(&self, seconds: f32, f: F) -> ActionId
+    where
+        F: FnOnce(&Context) -> Result<(), GameError> + 'static,
+    {
+        self.run(Sequence::new().then_wait(seconds).then_call(f))
+    }
+
+    /// Call the function every time `interval` seconds pass, until it is cancelled.
+    #[inline]
+    pub fn every<F>(&self, interval: f32, f: F) -> ActionId
+    where
+        F: FnMut(&Context) -> Result<(), GameError> + 'static,
+    {
+        self.run(Every {
+            interval,
+            elapsed: 0.0,
+            f,
+        })
+    }
+
+    /// Play the tween, passing its value to `apply` every update.
+    #[inline]
+    pub fn tween<T, F>(&self, tween: Tween<T>, apply: F) -> ActionId
+    where
+        T: Interp<Factor = f32> + Copy + 'static,
+        F: FnMut(T) + 'static,
+    {
+        self.run(tween.action(apply))
+    }
+
+    /// Stop the action before it finishes.
+    pub fn cancel(&self, id: ActionId) {
+        self.0.running.borrow_mut().remove(&id);
+        self.0.added.borrow_mut().retain(|(i, _)| *i != id);
+    }
+
+    /// Stop all running actions.
+    pub fn clear(&self) {
+        self.0.running.borrow_mut().clear();
+        self.0.added.borrow_mut().clear();
+    }
+
+    /// If the action is still running.
+    #[inline]
+    pub fn is_running(&self, id: ActionId) -> bool {
+        self.0.running.borrow().contains(&id)
+    }
+
+    /// How many actions are running.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.0.running.borrow().len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.0.running.borrow().is_empty()
+    }
+
+    /// If the timeline is paused, in which case its actions aren't updated.
+    #[inline]
+    pub fn is_paused(&self) -> bool {
+        self.0.paused.get()
+    }
+
+    #[inline]
+    pub fn pause(&self) {
+        self.0.paused.set(true);
+    }
+
+    #[inline]
+    pub fn resume(&self) {
+        self.0.paused.set(false);
+    }
+
+    /// How fast time passes for the timeline, where `1.0` is normal speed.
+    #[inline]
+    pub fn time_scale(&self) -> f32 {
+        self.0.time_scale.get()
+    }
+
+    /// Set how fast time passes for the timeline, where `1.0` is normal speed.
+    #[inline]
+    pub fn set_time_scale(&self, scale: f32) {
+        self.0.time_scale.set(scale.max(0.0));
+    }
+
+    /// Advance all running actions by the frame's [delta time](crate::core::Time::delta),
+    /// scaled by the time scale. If an action returns an error, it is stopped and the rest are
+    /// updated next time.
+    pub fn update(&self, ctx: &Context) -> Result<(), GameError> {
+        if self.is_paused() {
+            return Ok(());
+        }
+        let delta = ctx.time.delta() * self.time_scale();
+
+        // actions can run or cancel other actions while updating, so they are taken out while
+        // updating and actions run in the meantime start on the next update
+        let mut actions = self.0.actions.take();
+        actions.append(&mut self.0.added.borrow_mut());
+
+        let mut result = Ok(());
+        actions.retain_mut(|(id, action)| {
+            if !self.is_running(*id) {
+                return false;
+            }
+            if result.is_err() {
+                return true;
+            }
+            match action.update(ctx, delta) {
+                Ok(None) => true,
+                Ok(Some(_)) => {
+                    self.0.running.borrow_mut().remove(id);
+                    false
+                }
+                Err(err) => {
+                    self.0.running.borrow_mut().remove(id);
+                    result = Err(err);
+                    false
+                }
+            }
+        });
+        *self.0.actions.borrow_mut() = actions;
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{Game, GameBuilder};
+    use crate::gfx::Draw;
+    use crate::tween::Parallel;
+
+    struct TimelineGame {
+        log: Rc<RefCell<Vec<String>>>,
+    }
+
+    impl Game for TimelineGame {
+        type Config = ();
+
+        fn new(ctx: &Context, _cfg: Self::Config) -> Result<Self, GameError> {
+            let log = Rc::new(RefCell::new(Vec::new()));
+            let (a, b, c) = (log.clone(), log.clone(), log.clone());
+            ctx.timeline.run(
+                Sequence::new()
+                    .then_wait(0.25)
+                    .then(
+                        Parallel::new()
+                            .with(Tween::new(0.0, 1.0, 0.5).action(move |v: f32| {
+                                a.borrow_mut().push(format!("a{v}"));
+                            }))
+                            .with(Tween::new(0.0, 1.0, 0.25).action(move |v: f32| {
+                                b.borrow_mut().push(format!("b{v}"));
+                            })),
+                    )
+                    .then_call(move |_| {
+                        c.borrow_mut().push("done".into());
+                        Ok(())
+                    }),
+            );
+            Ok(Self { log })
+        }
+
+        fn update(&mut self, _ctx: &Context) -> Result<(), GameError> {
+            Ok(())
+        }
+
+        fn render(&mut self, _ctx: &Context, _draw: &mut Draw) -> Result<(), GameError> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn timeline_sequencing() {
+        let mut runner = GameBuilder::new()
+            .unwrap()
+            .with_size(64, 64)
+            .build_headless::<TimelineGame>(())
+            .unwrap();
+        runner.ctx().time.set_target_fps(Some(4.0));
+        let log = runner.game().log.clone();
+
+        // the wait finishes exactly on the first update, and both tweens start
+        runner.step().unwrap();
+        assert_eq!(*log.borrow(), ["a0", "b0"]);
+        runner.step().unwrap();
+        assert_eq!(*log.borrow(), ["a0", "b0", "a0.5", "b1"]);
+
+        // pausing and time scale affect how fast actions advance
+        runner.ctx().timeline.pause();
+        runner.step().unwrap();
+        runner.ctx().timeline.resume();
+        runner.ctx().timeline.set_time_scale(2.0);
+        runner.step().unwrap();
+        assert_eq!(*log.borrow(), ["a0", "b0", "a0.5", "b1", "a1", "done"]);
+        assert!(runner.ctx().timeline.is_empty());
+    }
+}
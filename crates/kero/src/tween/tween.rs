@@ -0,0 +1,255 @@
+use crate::core::{Context, GameError};
+use crate::math::Interp;
+use crate::tween::{Action, Ease};
+
+/// Animates a value from one point to another over time.
+///
+/// A tween can be stored and advanced manually with [`update`](Tween::update), or turned into
+/// an [`Action`] with [`action`](Tween::action) and run on a [`Timeline`](super::Timeline).
+///
+/// ```
+/// # use kero::prelude::*;
+/// let mut tween = Tween::new(0.0f32, 100.0, 0.5)
+///     .with_ease(Ease::QuadOut)
+///     .with_repeat(1)
+///     .with_yoyo(true);
+///
+/// // animates out to 100, then back to 0
+/// assert_eq!(tween.update(0.5), 100.0);
+/// assert_eq!(tween.update(0.5), 0.0);
+/// assert!(tween.is_finished());
+/// ```
+#[derive(Debug, Clone)]
+pub struct Tween<T> {
+    from: T,
+    to: T,
+    duration: f32,
+    delay: f32,
+    ease: Ease,
+    repeat: Option<u32>,
+    yoyo: bool,
+    elapsed: f32,
+}
+
+impl<T: Interp<Factor = f32> + Copy> Tween<T> {
+    /// Create a new tween that animates between the values over `duration` seconds.
+    #[inline]
+    pub fn new(from: T, to: T, duration: f32) -> Self {
+        Self {
+            from,
+            to,
+            duration: duration.max(0.0),
+            delay: 0.0,
+            ease: Ease::Linear,
+            repeat: Some(0),
+            yoyo: false,
+            elapsed: 0.0,
+        }
+    }
+
+    /// Set the easing curve.
+    #[inline]
+    pub fn with_ease(mut self, ease: Ease) -> Self {
+        self.ease = ease;
+        self
+    }
+
+    /// Wait this many seconds before starting.
+    #[inline]
+    pub fn with_delay(mut self, delay: f32) -> Self {
+        self.delay = delay.max(0.0);
+        self
+    }
+
+    /// Play the tween this many more times after the first.
+    #[inline]
+    pub fn with_repeat(mut self, times: u32) -> Self {
+        self.repeat = Some(times);
+        self
+    }
+
+    /// Repeat the tween forever, so it never finishes.
+    #[inline]
+    pub fn with_repeat_forever(mut self) -> Self {
+        self.repeat = None;
+        self
+    }
+
+    /// If the tween should play backwards on every other repeat.
+    #[inline]
+    pub fn with_yoyo(mut self, yoyo: bool) -> Self {
+        self.yoyo = yoyo;
+        self
+    }
+
+    #[inline]
+    pub fn from(&self) -> T {
+        self.from
+    }
+
+    #[inline]
+    pub fn to(&self) -> T {
+        self.to
+    }
+
+    /// How long one play of the tween lasts, in seconds.
+    #[inline]
+    pub fn duration(&self) -> f32 {
+        self.duration
+    }
+
+    #[inline]
+    pub fn delay(&self) -> f32 {
+        self.delay
+    }
+
+    #[inline]
+    pub fn ease(&self) -> Ease {
+        self.ease
+    }
+
+    /// How many times the tween repeats after the first play, or `None` if it repeats forever.
+    #[inline]
+    pub fn repeat(&self) -> Option<u32> {
+        self.repeat
+    }
+
+    #[inline]
+    pub fn yoyo(&self) -> bool {
+        self.yoyo
+    }
+
+    /// Time since the tween started, in seconds, including the delay.
+    #[inline]
+    pub fn elapsed(&self) -> f32 {
+        self.elapsed
+    }
+
+    /// How long the tween lasts including the delay and all repeats, or `None` if it repeats
+    /// forever.
+    #[inline]
+    pub fn total_duration(&self) -> Option<f32> {
+        self.repeat
+            .map(|repeat| self.delay + self.duration * (repeat as f32 + 1.0))
+    }
+
+    /// If the tween has finished playing.
+    #[inline]
+    pub fn is_finished(&self) -> bool {
+        self.total_duration()
+            .is_some_and(|total| self.elapsed >= total)
+    }
+
+    /// How much time the last update went past the end of the tween, or `0` if it hasn't
+    /// finished.
+    #[inline]
+    pub fn leftover(&self) -> f32 {
+        self.total_duration()
+            .map_or(0.0, |total| (self.elapsed - total).max(0.0))
+    }
+
+    /// Progress through the current play of the tween from `0` to `1`, before easing is applied.
+    /// When yoyoing, this goes back from `1` to `0` on every other play.
+    pub fn progress(&self) -> f32 {
+        let time = self.elapsed - self.delay;
+        if time <= 0.0 {
+            return 0.0;
+        }
+        let (play, t) = match self.repeat {
+            Some(repeat) if self.is_finished() || self.duration <= 0.0 => (repeat, 1.0),
+            None if self.duration <= 0.0 => (0, 1.0),
+            _ => {
+                let plays = time / self.duration;
+                (plays as u32, plays.fract())
+            }
+        };
+        match self.yoyo && play % 2 == 1 {
+            true => 1.0 - t,
+            false => t,
+        }
+    }
+
+    /// The current value of the tween.
+    #[inline]
+    pub fn value(&self) -> T {
+        self.ease.interp(self.from, self.to, self.progress())
+    }
+
+    /// Advance the tween by `delta` seconds and return its new value.
+    #[inline]
+    pub fn update(&mut self, delta: f32) -> T {
+        self.elapsed += delta;
+        self.value()
+    }
+
+    /// Restart the tween from the beginning.
+    #[inline]
+    pub fn reset(&mut self) {
+        self.elapsed = 0.0;
+    }
+
+    /// Turn the tween into an action that passes the value to `apply` every update.
+    #[inline]
+    pub fn action<F>(self, apply: F) -> TweenAction<T, F>
+    where
+        F: FnMut(T) + 'static,
+    {
+        TweenAction { tween: self, apply }
+    }
+}
+
+/// An [`Action`] that plays a [`Tween`], passing its value to a function every update.
+///
+/// Created with [`Tween::action`].
+#[derive(Debug, Clone)]
+pub struct TweenAction<T, F> {
+    tween: Tween<T>,
+    apply: F,
+}
+
+impl<T, F> TweenAction<T, F> {
+    #[inline]
+    pub fn tween(&self) -> &Tween<T> {
+        &self.tween
+    }
+}
+
+impl<T, F> Action for TweenAction<T, F>
+where
+    T: Interp<Factor = f32> + Copy + 'static,
+    F: FnMut(T) + 'static,
+{
+    fn update(&mut self, _ctx: &Context, delta: f32) -> Result<Option<f32>, GameError> {
+        (self.apply)(self.tween.update(delta));
+        Ok(self.tween.is_finished().then(|| self.tween.leftover()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tween_repeat_yoyo() {
+        let mut tween = Tween::new(0.0f32, 8.0, 1.0)
+            .with_delay(0.5)
+            .with_repeat(2)
+            .with_yoyo(true);
+        assert_eq!(tween.total_duration(), Some(3.5));
+        assert_eq!(tween.update(0.5), 0.0);
+        assert_eq!(tween.update(0.25), 2.0);
+        assert_eq!(tween.update(1.0), 6.0);
+        assert_eq!(tween.update(1.0), 2.0);
+        assert!(!tween.is_finished());
+        assert_eq!(tween.update(1.25), 8.0);
+        assert!(tween.is_finished());
+        assert_eq!(tween.leftover(), 0.5);
+
+        let mut forever = Tween::new(0.0f32, 4.0, 1.0).with_repeat_forever();
+        assert_eq!(forever.update(10.5), 2.0);
+        assert!(!forever.is_finished());
+
+        let max = Tween::new(0.0f32, 1.0, 1.0).with_repeat(u32::MAX);
+        assert_eq!(max.total_duration(), Some(u32::MAX as f32 + 1.0));
+    }
+}
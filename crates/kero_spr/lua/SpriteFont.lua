@@ -74,6 +74,22 @@ function methods.text_size(self, text, use_line_gap) end
 ---@nodiscard
 function methods.word_wrap(self, width, text) end
 
+---Lay out the text with this font, with kerning, newlines, wrapping, and alignment.
+---@param self SpriteFont
+---@param text string
+---@param opts TextOptions?
+---@return TextLayout
+---@nodiscard
+function methods.layout(self, text, opts) end
+
+---Draws text that was laid out with this font.
+---@param self SpriteFont
+---@param layout TextLayout
+---@param pos Vec2
+---@param color Color?
+---@param mode ColorMode?
+function methods.draw_layout(self, layout, pos, color, mode) end
+
 ---Draws text using this font.
 ---@param self SpriteFont
 ---@param text string
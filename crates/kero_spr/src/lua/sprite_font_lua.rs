@@ -63,6 +63,33 @@ fn add_methods<T, M: UserDataMethods<T>>(methods: &mut M) {
             Ok((wrapped, lines))
         },
     );
+    methods.add_function(
+        "layout",
+        |_, (this, text, opts): (SpriteFontRef, BorrowedStr, Option<TextOptions>)| {
+            Ok(this.layout(&text, opts.unwrap_or_default()))
+        },
+    );
+    methods.add_function(
+        "draw_layout",
+        |lua,
+         (this, layout, pos, col, mode): (
+            SpriteFontRef,
+            TextLayoutRef,
+            Vec2F,
+            Option<Rgba8>,
+            Option<ColorMode>,
+        )| {
+            let draw = Draw::from_lua(lua)?;
+            this.draw_layout(
+                draw,
+                &layout,
+                pos,
+                col.unwrap_or(Rgba8::WHITE),
+                mode.unwrap_or(ColorMode::MULT),
+            );
+            Ok(())
+        },
+    );
     methods.add_function(
         "draw_text",
         |lua,
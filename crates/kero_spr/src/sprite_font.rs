@@ -48,6 +48,13 @@ impl SpriteFont {
         self.glyphs.get(&chr)
     }
 
+    /// Get the glyph to draw for the provided character, falling back to the `'\0'` glyph if
+    /// the font doesn't have one.
+    #[inline]
+    fn glyph_or_default(&self, chr: char) -> Option<&SpriteGlyph> {
+        self.glyphs.get(&chr).or_else(|| self.glyphs.get(&'\0'))
+    }
+
     /// Get the kerning value for the left-right character pair.
     #[inline]
     pub fn kerning(&self, left: char, right: char) -> Option<f32> {
@@ -57,17 +64,7 @@ impl SpriteFont {
     /// Get the width of the provided text when rendered in this font.
     #[inline]
     pub fn text_width(&self, text: &str) -> f32 {
-        let mut w: f32 = 0.0;
-        let mut max_w: f32 = 0.0;
-        for chr in text.chars() {
-            if chr == '\n' {
-                max_w = max_w.max(w);
-                w = 0.0
-            } else if let Some(g) = self.glyphs.get(&chr) {
-                w += g.advance;
-            }
-        }
-        max_w.max(w)
+        self.layout(text, TextOptions::default()).width()
     }
 
     /// Get the height of the provided text when rendered in this font.
@@ -112,32 +109,69 @@ impl SpriteFont {
         lines
     }
 
-    pub fn draw_text_ext(
+    /// Lay out the text with this font.
+    #[inline]
+    pub fn layout(&self, text: &str, options: TextOptions) -> TextLayout {
+        TextLayout::new(self, text, options)
+    }
+
+    /// Draw text that was laid out with this font.
+    pub fn draw_layout(
         &self,
         draw: &mut Draw,
-        text: &str,
+        layout: &TextLayout,
         pos: impl Into<Vec2F>,
         color: Rgba8,
         mode: ColorMode,
     ) {
-        let mut pos = pos.into();
-        let left = pos.x;
-        for chr in text.chars() {
-            if chr == '\n' {
-                pos.x = left;
-                pos.y += self.line_height();
-            } else if let Some(g) = self.glyphs.get(&chr).or_else(|| self.glyphs.get(&'\0')) {
-                if let Some(spr) = g.sprite.as_ref() {
-                    spr.draw_ext(draw, pos, color, mode);
-                }
-                pos.x += g.advance;
-            } else {
-                println!("no glyph for: [{}]", chr);
+        let pos = pos.into();
+        for g in layout.glyphs() {
+            if let Some(spr) = self.glyph_or_default(g.chr).and_then(|g| g.sprite.as_ref()) {
+                spr.draw_ext(draw, pos + g.pos, color, mode);
             }
         }
     }
 
+    pub fn draw_text_ext(
+        &self,
+        draw: &mut Draw,
+        text: &str,
+        pos: impl Into<Vec2F>,
+        color: Rgba8,
+        mode: ColorMode,
+    ) {
+        let layout = self.layout(text, TextOptions::default());
+        self.draw_layout(draw, &layout, pos, color, mode);
+    }
+
     pub fn draw_text(&self, draw: &mut Draw, text: &str, pos: impl Into<Vec2F>, color: Rgba8) {
         self.draw_text_ext(draw, text, pos, color, ColorMode::MULT);
     }
 }
+
+impl FontMetrics for SpriteFont {
+    #[inline]
+    fn ascent(&self) -> f32 {
+        self.ascent
+    }
+
+    #[inline]
+    fn descent(&self) -> f32 {
+        self.descent
+    }
+
+    #[inline]
+    fn line_gap(&self) -> f32 {
+        self.line_gap
+    }
+
+    #[inline]
+    fn advance(&self, chr: char) -> Option<f32> {
+        self.glyph_or_default(chr).map(|g| g.advance)
+    }
+
+    #[inline]
+    fn kerning(&self, left: char, right: char) -> Option<f32> {
+        self.kerning.get(&(left, right)).copied()
+    }
+}